@@ -1,33 +1,137 @@
 use std::hash::{BuildHasher, Hasher};
 
-/// Hasher for City hash implementation of the 64-bit hashing algorithm.
+use crate::{HashAlgorithm, Hasher128};
+
+/// Hasher for City hash implementation of the 128-bit hashing algorithm.
+///
+/// By default the input is buffered until `finish()` is called.
+/// When the total input length is known ahead of time, use `with_length()` to hash the input
+/// incrementally in a fixed amount of memory instead.
 #[derive(Default)]
 pub struct CityHasher128 {
     buffer: Vec<u8>,
+    seed: Option<u128>,
+    // the length given to `with_length()` for short inputs, which are buffered
+    length: Option<u64>,
+    stream: Option<Stream128>,
+}
+
+impl CityHasher128 {
+    /// Create a hasher which uses the given seed, like `hash128_with_seed()`.
+    pub fn with_seed(seed: u128) -> Self {
+        Self {
+            seed: Some(seed),
+            ..Self::default()
+        }
+    }
+
+    /// Create a hasher which processes its input as it is written, keeping only a fixed-size window
+    /// of it in memory.
+    /// CityHash128 mixes the total input length into its initial state, so it must be known up front.
+    ///
+    /// # Panics
+    ///
+    /// `finish()` panics if the number of bytes written differs from `length`.
+    pub fn with_length(length: u64) -> Self {
+        // short inputs are cheap to buffer and take a different path through the algorithm
+        if length < 16 + 128 {
+            return Self {
+                length: Some(length),
+                ..Self::default()
+            };
+        }
+        Self {
+            stream: Some(Stream128::new(length, None)),
+            ..Self::default()
+        }
+    }
+
+    /// Create a hasher like `with_length()` which uses the given seed, like `hash128_with_seed()`.
+    ///
+    /// # Panics
+    ///
+    /// `finish()` panics if the number of bytes written differs from `length`.
+    pub fn with_seed_and_length(seed: u128, length: u64) -> Self {
+        if length < 128 {
+            return Self {
+                length: Some(length),
+                ..Self::with_seed(seed)
+            };
+        }
+        Self {
+            seed: Some(seed),
+            stream: Some(Stream128::new(length, Some(seed))),
+            ..Self::default()
+        }
+    }
 }
 
 impl Hasher for CityHasher128 {
     fn write(&mut self, bytes: &[u8]) {
-        self.buffer.extend(bytes)
+        match &mut self.stream {
+            Some(stream) => stream.write(bytes),
+            None => self.buffer.extend(bytes),
+        }
     }
 
     fn finish(&self) -> u64 {
-        hash128(&self.buffer) as u64
+        self.finish128() as u64
     }
 }
 
-/// Hash builder for City hash implementation of the 64-bit hashing algorithm.
-#[derive(Default)]
-pub struct CityHash128 {}
+impl Hasher128 for CityHasher128 {
+    fn finish128(&self) -> u128 {
+        match &self.stream {
+            Some(stream) => stream.finish(),
+            None => {
+                if let Some(length) = self.length {
+                    assert_eq!(
+                        self.buffer.len() as u64,
+                        length,
+                        "CityHasher128 was created for {} bytes but {} bytes were written",
+                        length,
+                        self.buffer.len()
+                    );
+                }
+                match self.seed {
+                    Some(seed) => hash128_with_seed(&self.buffer, seed),
+                    None => hash128(&self.buffer),
+                }
+            }
+        }
+    }
+}
+
+/// Hash builder for City hash implementation of the 128-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct CityHash128 {
+    seed: Option<u128>,
+}
+
+impl CityHash128 {
+    /// Create a hash builder whose hashers use the given seed, like `hash128_with_seed()`.
+    pub fn with_seed(seed: u128) -> Self {
+        Self { seed: Some(seed) }
+    }
+}
 
 impl BuildHasher for CityHash128 {
     type Hasher = CityHasher128;
 
     fn build_hasher(&self) -> Self::Hasher {
-        Self::Hasher::default()
+        match self.seed {
+            Some(seed) => Self::Hasher::with_seed(seed),
+            None => Self::Hasher::default(),
+        }
     }
 }
 
+crate::random::random_state! {
+    /// Randomly seeded hash builder for City hash implementation of the 128-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct CityRandomState128(CityHash128::with_seed(seed: u128));
+}
+
 // The code below is adapted from C++ code with the following license
 //-----------------------------------------------------------------------------
 // Copyright (c) 2011 Google, Inc.
@@ -59,6 +163,23 @@ const K3: u64 = 0xc949d7c7509e6557;
 
 const K_MUL: u64 = 0x9ddfea08eb382d69;
 
+/// City hash implementation of the 128-bit hashing algorithm.
+pub struct City128;
+
+impl HashAlgorithm for City128 {
+    const NAME: &'static str = "city128";
+    type Seed = u128;
+    type Output = u128;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hash128(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hash128_with_seed(v, seed)
+    }
+}
+
 /// City hash implementation of the 128-bit hashing algorithm.
 /// This version allows you to specify a seed.
 pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u128) -> u128 {
@@ -67,63 +188,155 @@ pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u128) -> u128 {
         return city_murmur(data, seed);
     }
     // >= 128 bytes
-    // keep 56 bytes of state across loops
-    let mut v = (0, 0);
-    let mut w = (0, 0);
-    let mut x = seed as u64;
-    let mut y = (seed >> 64) as u64;
-    let mut z = (data.len() as u64).wrapping_mul(K1);
-    v.0 = (y ^ K1).rotate_right(47).wrapping_mul(K1).wrapping_add(fetch64(data, 0));
-    v.1 = v.0.rotate_right(42).wrapping_mul(K1).wrapping_add(fetch64(data, 8));
-    w.0 = y.wrapping_add(z).rotate_right(35).wrapping_mul(K1).wrapping_add(x);
-    w.1 = x.wrapping_add(fetch64(data, 88)).rotate_right(53).wrapping_mul(K1);
-    
+    let mut state = LoopState::new(seed, data.len() as u64, data);
     let mut len = data.len();
     let mut s = 0; // data index
-    
-    // similar to city hash64 loop
+
     loop {
-        x = x.wrapping_add(y).wrapping_add(v.0).wrapping_add(fetch64(data, s + 8)).rotate_right(37).wrapping_mul(K1);
-        y = y.wrapping_add(v.1).wrapping_add(fetch64(data, s + 48)).rotate_right(42).wrapping_mul(K1);
-        x ^= w.1;
-        y = y.wrapping_add(v.0).wrapping_add(fetch64(data, s + 40));
-        z = z.wrapping_add(w.0).rotate_right(33).wrapping_mul(K1);
-        v = weak_hash_len_32_with_seeds(data, v.1.wrapping_mul(K1), x.wrapping_add(w.0), s);
-        w = weak_hash_len_32_with_seeds(data, z.wrapping_add(w.1), y.wrapping_add(fetch64(data, s + 16)), s + 32);
-        std::mem::swap(&mut z, &mut x);
-        s += 64;
-        x = x.wrapping_add(y).wrapping_add(v.0).wrapping_add(fetch64(data, s + 8)).rotate_right(37).wrapping_mul(K1);
-        y = y.wrapping_add(v.1).wrapping_add(fetch64(data, s + 48)).rotate_right(42).wrapping_mul(K1);
-        x ^= w.1;
-        y = y.wrapping_add(v.0).wrapping_add(fetch64(data, s + 40));
-        z = z.wrapping_add(w.0).rotate_right(33).wrapping_mul(K1);
-        v = weak_hash_len_32_with_seeds(data, v.1.wrapping_mul(K1), x.wrapping_add(w.0), s);
-        w = weak_hash_len_32_with_seeds(data, z.wrapping_add(w.1), y.wrapping_add(fetch64(data, s + 16)), s + 32);
-        std::mem::swap(&mut z, &mut x);
+        state.round(data, s);
+        s += 128;
         len -= 128;
         if len < 128 { break; }
     }
-    x = x.wrapping_add(v.0.wrapping_add(z).rotate_right(49).wrapping_mul(K0));
-    z = x.wrapping_add(w.0.rotate_right(37).wrapping_mul(K0));
-    // handle end of hash data
-    // hash up to 4 32-bit chunks for tail section (up to 128 bits)
-    let mut tail_done = 0;
-    while tail_done < len {
-        tail_done += 32;
-        y = x.wrapping_add(y).rotate_right(42).wrapping_mul(K0).wrapping_add(v.1);
-        w.0 = w.0.wrapping_add(fetch64(data, s + len - tail_done + 16));
-        x = x.wrapping_mul(K0).wrapping_add(w.0);
-        z = z.wrapping_add(w.1.wrapping_add(fetch64(data, s + len - tail_done)));
-        w.1 = w.1.wrapping_add(v.0);
-        v = weak_hash_len_32_with_seeds(data, v.0.wrapping_add(z), v.1, s + len - tail_done);
-    }
-    x = hash_len_16(x, v.0);
-    y = hash_len_16(y.wrapping_add(z), w.0);
-    
-    let low: u64 = hash_len_16(x.wrapping_add(v.1), w.1).wrapping_add(y);
-    let high: u64 = hash_len_16(x.wrapping_add(w.1), y.wrapping_add(v.1));
-    //(high as u128) << 64 | (low as u128)
-    to_u128(low, high)
+    state.finish(data, s, len)
+}
+
+// keep 56 bytes of state across loops
+#[derive(Clone, Copy)]
+struct LoopState {
+    x: u64,
+    y: u64,
+    z: u64,
+    v: (u64, u64),
+    w: (u64, u64),
+}
+
+impl LoopState {
+    // `data` must contain at least the first 96 bytes of the input
+    #[inline(always)]
+    fn new(seed: u128, len: u64, data: &[u8]) -> Self {
+        let x = seed as u64;
+        let y = (seed >> 64) as u64;
+        let z = len.wrapping_mul(K1);
+        let v0 = (y ^ K1).rotate_right(49).wrapping_mul(K1).wrapping_add(fetch64(data, 0));
+        let v1 = v0.rotate_right(42).wrapping_mul(K1).wrapping_add(fetch64(data, 8));
+        let w0 = y.wrapping_add(z).rotate_right(35).wrapping_mul(K1).wrapping_add(x);
+        let w1 = x.wrapping_add(fetch64(data, 88)).rotate_right(53).wrapping_mul(K1);
+        LoopState { x, y, z, v: (v0, v1), w: (w0, w1) }
+    }
+
+    // similar to city hash64 loop, but processes 128 bytes at a time
+    #[inline(always)]
+    fn round(&mut self, data: &[u8], s: usize) {
+        self.half_round(data, s);
+        self.half_round(data, s + 64);
+    }
+
+    #[inline(always)]
+    fn half_round(&mut self, data: &[u8], s: usize) {
+        let LoopState { x, y, z, v, w } = self;
+        *x = x.wrapping_add(*y).wrapping_add(v.0).wrapping_add(fetch64(data, s + 8)).rotate_right(37).wrapping_mul(K1);
+        *y = y.wrapping_add(v.1).wrapping_add(fetch64(data, s + 48)).rotate_right(42).wrapping_mul(K1);
+        *x ^= w.1;
+        *y = y.wrapping_add(v.0).wrapping_add(fetch64(data, s + 40));
+        *z = z.wrapping_add(w.0).rotate_right(33).wrapping_mul(K1);
+        *v = weak_hash_len_32_with_seeds(data, v.1.wrapping_mul(K1), x.wrapping_add(w.0), s);
+        *w = weak_hash_len_32_with_seeds(data, z.wrapping_add(w.1), y.wrapping_add(fetch64(data, s + 16)), s + 32);
+        std::mem::swap(z, x);
+    }
+
+    // `len` (< 128) bytes remain at index `s`, which must be preceded by at least 31 bytes
+    #[inline(always)]
+    fn finish(self, data: &[u8], s: usize, len: usize) -> u128 {
+        let LoopState { mut x, mut y, mut z, mut v, mut w } = self;
+        x = x.wrapping_add(v.0.wrapping_add(z).rotate_right(49).wrapping_mul(K0));
+        z = z.wrapping_add(w.0.rotate_right(37).wrapping_mul(K0));
+        // handle end of hash data
+        // hash up to 4 32-bit chunks for tail section (up to 128 bits)
+        let mut tail_done = 0;
+        while tail_done < len {
+            tail_done += 32;
+            y = x.wrapping_add(y).rotate_right(42).wrapping_mul(K0).wrapping_add(v.1);
+            w.0 = w.0.wrapping_add(fetch64(data, s + len - tail_done + 16));
+            x = x.wrapping_mul(K0).wrapping_add(w.0);
+            z = z.wrapping_add(w.1.wrapping_add(fetch64(data, s + len - tail_done)));
+            w.1 = w.1.wrapping_add(v.0);
+            v = weak_hash_len_32_with_seeds(data, v.0.wrapping_add(z), v.1, s + len - tail_done);
+        }
+        x = hash_len_16(x, v.0);
+        y = hash_len_16(y.wrapping_add(z), w.0);
+
+        let low: u64 = hash_len_16(x.wrapping_add(v.1), w.1).wrapping_add(y);
+        let high: u64 = hash_len_16(x.wrapping_add(w.1), y.wrapping_add(v.1));
+        //(high as u128) << 64 | (low as u128)
+        to_u128(low, high)
+    }
+}
+
+// Incremental state for an input whose length is known in advance.
+// Unless a seed is given, the first 16 bytes become the seed. At least 128 bytes must follow:
+// every complete 128-byte block goes through the main loop, and the tail may reach up to
+// 31 bytes back into the last block.
+struct Stream128 {
+    length: u64,
+    prefix: u64,
+    written: u64,
+    seed: Option<u128>,
+    state: Option<LoopState>,
+    // window[..32] holds the end of the last processed block, window[32..] collects the next one
+    window: [u8; 32 + 128],
+    pending: usize,
+}
+
+impl Stream128 {
+    fn new(length: u64, seed: Option<u128>) -> Self {
+        Stream128 {
+            length,
+            prefix: if seed.is_some() { 0 } else { 16 },
+            written: 0,
+            seed,
+            state: None,
+            window: [0; 32 + 128],
+            pending: 0,
+        }
+    }
+
+    fn write(&mut self, mut bytes: &[u8]) {
+        self.written += bytes.len() as u64;
+        while !bytes.is_empty() {
+            let wanted = if self.seed.is_none() { 16 } else { 128 };
+            let fill = (wanted - self.pending).min(bytes.len());
+            self.window[32 + self.pending..32 + self.pending + fill].copy_from_slice(&bytes[..fill]);
+            self.pending += fill;
+            bytes = &bytes[fill..];
+            if self.pending < wanted {
+                return;
+            }
+            self.pending = 0;
+            match self.seed {
+                None => self.seed = Some(to_u128(fetch64(&self.window, 32) ^ K3, fetch64(&self.window, 40))),
+                Some(seed) => {
+                    let length = self.length - self.prefix;
+                    let window = &self.window;
+                    self.state
+                        .get_or_insert_with(|| LoopState::new(seed, length, &window[32..]))
+                        .round(window, 32);
+                    self.window.copy_within(128.., 0);
+                }
+            }
+        }
+    }
+
+    fn finish(&self) -> u128 {
+        assert_eq!(
+            self.written, self.length,
+            "CityHasher128 was created for {} bytes but {} bytes were written",
+            self.length, self.written
+        );
+        self.state
+            .expect("at least one block has been processed")
+            .finish(&self.window, 32, self.pending)
+    }
 }
 
 /// City hash implementation of the 128-bit hashing algorithm.
@@ -137,7 +350,7 @@ pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
     } else if data.len() >= 8 {
         let seed = to_u128(fetch64(data, 0) ^ (data.len() as u64).wrapping_mul(K0), (fetch64(data, data.len() - 8)) ^ K1);
         //let seed = ((fetch64(data, data.len() - 8) ^ K1) as u128) << 64 | ((fetch64(data, 0) ^ (data.len() as u64).wrapping_mul(K0)) as u128);
-        return hash128_with_seed(&[], seed);
+        return hash128_with_seed([], seed);
     }
     hash128_with_seed(data, to_u128(K0, K1))
 }
@@ -202,7 +415,7 @@ fn hash64_len_0_to_16(data: &[u8]) -> u64 {
         let a = fetch32(data, 0) as u64;
         return hash_len_16((data.len() as u64).wrapping_add(a << 3), fetch32(data, data.len() - 4) as u64);
     }
-    if data.len() > 0 {
+    if !data.is_empty() {
         let a = data[0];
         let b = data[data.len() >> 1];
         let c = data[data.len() - 1];
@@ -253,6 +466,9 @@ fn city_murmur(data: &[u8], seed: u128) -> u128 {
 
 #[cfg(test)]
 mod test {
+    use crate::Hasher128;
+    use std::hash::Hasher;
+
     #[test]
     fn compliance_test() {
         assert_eq!(crate::city::city_128::hash128("abc"), 26133304454536238711123707289922914558);
@@ -266,6 +482,83 @@ mod test {
         assert_eq!(crate::city::city_128::hash128_with_seed(input, seed),
             fasthash::city::hash128_with_seed(input, seed));
     }
-}
 
+    #[test]
+    fn streaming_test() {
+        let input: Vec<u8> = (0..1024u32).map(|i| (i * 7 + 3) as u8).collect();
+        for len in (0..input.len()).step_by(5) {
+            let expected = crate::city::city_128::hash128(&input[..len]);
+            for split in [1, 16, 61, 128, 500] {
+                let mut hasher = crate::city::CityHasher128::with_length(len as u64);
+                for chunk in input[..len].chunks(split) {
+                    hasher.write(chunk);
+                }
+                assert_eq!(hasher.finish(), expected as u64);
+                assert_eq!(hasher.finish128(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn seeded_streaming_test() {
+        use std::hash::BuildHasher;
+        let input: Vec<u8> = (0..1024u32).map(|i| (i * 7 + 3) as u8).collect();
+        let seed = 12345 << 64 | 999;
+        for len in (0..input.len()).step_by(5) {
+            let expected = crate::city::city_128::hash128_with_seed(&input[..len], seed) as u64;
+            let mut hasher = crate::city::CityHash128::with_seed(seed).build_hasher();
+            hasher.write(&input[..len]);
+            assert_eq!(hasher.finish(), expected);
+            let mut hasher = crate::city::CityHasher128::with_seed_and_length(seed, len as u64);
+            for chunk in input[..len].chunks(61) {
+                hasher.write(chunk);
+            }
+            assert_eq!(hasher.finish(), expected);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn streaming_length_mismatch_test() {
+        let mut hasher = crate::city::CityHasher128::with_length(200);
+        hasher.write(&[0; 199]);
+        let _ = hasher.finish();
+    }
+
+    #[test]
+    #[should_panic]
+    fn short_length_mismatch_test() {
+        // short inputs are buffered, but their length is still checked
+        let mut hasher = crate::city::CityHasher128::with_length(100);
+        hasher.write(&[0; 101]);
+        let _ = hasher.finish();
+    }
 
+    #[test]
+    #[should_panic]
+    fn seeded_length_mismatch_test() {
+        let mut hasher = crate::city::CityHasher128::with_seed_and_length(1, 300);
+        hasher.write(&[0; 301]);
+        let _ = hasher.finish();
+    }
+
+    #[test]
+    #[should_panic]
+    fn seeded_short_length_mismatch_test() {
+        let mut hasher = crate::city::CityHasher128::with_seed_and_length(1, 50);
+        hasher.write(&[0; 49]);
+        let _ = hasher.finish();
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn fasthash_long_input_test() {
+        // fasthash switches to CityHashCrc128 above 900 bytes
+        let input: Vec<u8> = (0..900u32).map(|i| (i * 7 + 3) as u8).collect();
+        let seed = 12345 << 64 | 999;
+        assert_eq!(crate::city::city_128::hash128_with_seed(&input, seed),
+            fasthash::city::hash128_with_seed(&input, seed));
+        assert_eq!(crate::city::city_128::hash128(&input),
+            fasthash::city::hash128(&input));
+    }
+}
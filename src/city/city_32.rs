@@ -1,9 +1,22 @@
 use std::hash::{BuildHasher, Hasher};
 
+use crate::HashAlgorithm;
+
 /// Hasher for City hash implementation of the 32-bit hashing algorithm.
 #[derive(Default)]
 pub struct CityHasher32 {
     buffer: Vec<u8>,
+    seed: u32,
+}
+
+impl CityHasher32 {
+    /// Create a hasher which uses the given seed.
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            buffer: Vec::new(),
+            seed,
+        }
+    }
 }
 
 impl Hasher for CityHasher32 {
@@ -12,22 +25,37 @@ impl Hasher for CityHasher32 {
     }
 
     fn finish(&self) -> u64 {
-        hash32(&self.buffer) as u64
+        hash32_with_seed(&self.buffer, self.seed) as u64
     }
 }
 
 /// Hash builder for City hash implementation of the 32-bit hashing algorithm.
-#[derive(Default)]
-pub struct CityHash32 {}
+#[derive(Clone, Copy, Default)]
+pub struct CityHash32 {
+    seed: u32,
+}
+
+impl CityHash32 {
+    /// Create a hash builder whose hashers use the given seed.
+    pub fn with_seed(seed: u32) -> Self {
+        Self { seed }
+    }
+}
 
 impl BuildHasher for CityHash32 {
     type Hasher = CityHasher32;
 
     fn build_hasher(&self) -> Self::Hasher {
-        Self::Hasher::default()
+        Self::Hasher::with_seed(self.seed)
     }
 }
 
+crate::random::random_state! {
+    /// Randomly seeded hash builder for City hash implementation of the 32-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct CityRandomState32(CityHash32::with_seed(seed: u32));
+}
+
 // The code below is adapted from C++ code with the following license
 //-----------------------------------------------------------------------------
 // Copyright (c) 2011 Google, Inc.
@@ -63,6 +91,23 @@ const C2: u32 = 0x1b873593;
 
 const D0: u32 = 0xe6546b64;
 
+/// City hash implementation of the 32-bit hashing algorithm.
+pub struct City32;
+
+impl HashAlgorithm for City32 {
+    const NAME: &'static str = "city32";
+    type Seed = u32;
+    type Output = u32;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hash32(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hash32_with_seed(v, seed)
+    }
+}
+
 /// City hash implementation of the 32-bit hashing algorithm.
 /// This version allows you to specify a seed.
 pub fn hash32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
@@ -122,7 +167,7 @@ pub fn hash32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
     f = f.wrapping_mul(5).wrapping_add(D0);
 
     for i in 0..(data.len() - 1) / 20 {
-        a0 = fetch32(data, (i * 20) + 0)
+        a0 = fetch32(data, i * 20)
             .wrapping_mul(C1)
             .rotate_right(17)
             .wrapping_mul(C2);
@@ -207,7 +252,7 @@ fn fmix32(h: u32) -> u32 {
     input ^= input >> 16;
     input = input.wrapping_mul(0x85ebca6b);
     input ^= input >> 13;
-    input = input.wrapping_mul(0xC2b2ae35);
+    input = input.wrapping_mul(0xc2b2ae35);
     input ^= input >> 16;
     input
 }
@@ -234,8 +279,8 @@ fn fetch32(data: &[u8], i: usize) -> u32 {
 fn hash32_len_0_to_4(data: &[u8], seed: u32) -> u32 {
     let mut b = seed;
     let mut c = 9;
-    for i in 0..data.len() {
-        b = b.wrapping_mul(C1).wrapping_add(data[i] as u32);
+    for &byte in data {
+        b = b.wrapping_mul(C1).wrapping_add(byte as u32);
         c ^= b;
     }
     fmix32(mur_combine(b, mur_combine(data.len() as u32, c)))
@@ -284,6 +329,14 @@ mod test {
         assert_eq!(crate::city::city_32::hash32_with_seed("abc", 0), 795041479);
     }
 
+    #[test]
+    fn seeded_builder_test() {
+        use std::hash::{BuildHasher, Hasher};
+        let mut hasher = crate::city::CityHash32::with_seed(4919).build_hasher();
+        hasher.write(b"abc");
+        assert_eq!(hasher.finish(), crate::city::city_32::hash32_with_seed("abc", 4919) as u64);
+    }
+
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
     #[test]
     fn fasthash_interop_test() {
@@ -1,9 +1,31 @@
 use std::hash::{BuildHasher, Hasher};
 
+use crate::HashAlgorithm;
+
 /// Hasher for City hash implementation of the 64-bit hashing algorithm.
+///
+/// The input is buffered until `finish()` is called.
+/// CityHash64 seeds its main loop from the last 64 bytes of the input, so unlike `CityHasher128`
+/// it cannot be computed incrementally.
 #[derive(Default)]
 pub struct CityHasher64 {
     buffer: Vec<u8>,
+    seeds: Option<(u64, u64)>,
+}
+
+impl CityHasher64 {
+    /// Create a hasher which uses the given seed, like `hash64_with_seed()`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_seeds(K2, seed)
+    }
+
+    /// Create a hasher which uses the given two seeds, like `hash64_with_seeds()`.
+    pub fn with_seeds(seed0: u64, seed1: u64) -> Self {
+        Self {
+            buffer: Vec::new(),
+            seeds: Some((seed0, seed1)),
+        }
+    }
 }
 
 impl Hasher for CityHasher64 {
@@ -12,22 +34,50 @@ impl Hasher for CityHasher64 {
     }
 
     fn finish(&self) -> u64 {
-        hash64(&self.buffer)
+        match self.seeds {
+            Some((seed0, seed1)) => hash64_with_seeds(&self.buffer, seed0, seed1),
+            None => hash64(&self.buffer),
+        }
     }
 }
 
 /// Hash builder for City hash implementation of the 64-bit hashing algorithm.
-#[derive(Default)]
-pub struct CityHash64 {}
+#[derive(Clone, Copy, Default)]
+pub struct CityHash64 {
+    seeds: Option<(u64, u64)>,
+}
+
+impl CityHash64 {
+    /// Create a hash builder whose hashers use the given seed, like `hash64_with_seed()`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_seeds(K2, seed)
+    }
+
+    /// Create a hash builder whose hashers use the given two seeds, like `hash64_with_seeds()`.
+    pub fn with_seeds(seed0: u64, seed1: u64) -> Self {
+        Self {
+            seeds: Some((seed0, seed1)),
+        }
+    }
+}
 
 impl BuildHasher for CityHash64 {
     type Hasher = CityHasher64;
 
     fn build_hasher(&self) -> Self::Hasher {
-        Self::Hasher::default()
+        match self.seeds {
+            Some((seed0, seed1)) => Self::Hasher::with_seeds(seed0, seed1),
+            None => Self::Hasher::default(),
+        }
     }
 }
 
+crate::random::random_state! {
+    /// Randomly seeded hash builder for City hash implementation of the 64-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct CityRandomState64(CityHash64::with_seeds(seed0: u64, seed1: u64));
+}
+
 // The code below is adapted from C++ code with the following license
 //-----------------------------------------------------------------------------
 // Copyright (c) 2011 Google, Inc.
@@ -59,6 +109,24 @@ const K3: u64 = 0xc949d7c7509e6557;
 
 const K_MUL: u64 = 0x9ddfea08eb382d69;
 
+/// City hash implementation of the 64-bit hashing algorithm.
+/// The seed is the pair of seeds taken by `hash64_with_seeds()`.
+pub struct City64;
+
+impl HashAlgorithm for City64 {
+    const NAME: &'static str = "city64";
+    type Seed = (u64, u64);
+    type Output = u64;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hash64(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hash64_with_seeds(v, seed.0, seed.1)
+    }
+}
+
 /// City hash implementation of the 64-bit hashing algorithm.
 /// This version allows you to specify one seed.
 pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
@@ -116,7 +184,7 @@ pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
 }
 
 #[inline(always)]
-fn weak_hash_len_32_with_seeds(data: &[u8], mut a: u64, mut b: u64, index: usize) -> (u64, u64) {
+pub(crate) fn weak_hash_len_32_with_seeds(data: &[u8], mut a: u64, mut b: u64, index: usize) -> (u64, u64) {
     let w = fetch64(data, index);
     let x = fetch64(data, index + 8);
     let y = fetch64(data, index + 16);
@@ -144,12 +212,12 @@ fn fetch32(data: &[u8], i: usize) -> u32 {
 }
 
 #[inline(always)]
-fn shift_mix(val: u64) -> u64 {
+pub(crate) fn shift_mix(val: u64) -> u64 {
     val ^ (val >> 47)
 }
 
 #[inline(always)]
-fn hash_len_16(u: u64, v: u64) -> u64 {
+pub(crate) fn hash_len_16(u: u64, v: u64) -> u64 {
     //let x = (v as u128) << 64 | (u as u128);
     let mut a = (v ^ u).wrapping_mul(K_MUL);
     a ^= a >> 47;
@@ -170,7 +238,7 @@ fn hash64_len_0_to_16(data: &[u8]) -> u64 {
         let a = fetch32(data, 0) as u64;
         return hash_len_16((data.len() as u64).wrapping_add(a << 3), fetch32(data, data.len() - 4) as u64);
     }
-    if data.len() > 0 {
+    if !data.is_empty() {
         let a = data[0];
         let b = data[data.len() >> 1];
         let c = data[data.len() - 1];
@@ -223,6 +291,27 @@ mod test {
         assert_eq!(crate::city::city_64::hash64("abc"), 4220206313085259313);
     }
 
+    #[test]
+    fn hash_algorithm_test() {
+        use crate::city::City64;
+        use crate::HashAlgorithm;
+        assert_eq!(City64::NAME, "city64");
+        assert_eq!(City64::hash("abc"), 4220206313085259313);
+        assert_eq!(City64::hash_with_seed("abc", (1, 2)), crate::city::city_64::hash64_with_seeds("abc", 1, 2));
+    }
+
+    #[test]
+    fn seeded_builder_test() {
+        use std::hash::{BuildHasher, Hasher};
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let mut hasher = crate::city::CityHash64::with_seeds(1, 2).build_hasher();
+        hasher.write(input.as_bytes());
+        assert_eq!(hasher.finish(), crate::city::city_64::hash64_with_seeds(input, 1, 2));
+        let mut hasher = crate::city::CityHash64::with_seed(3).build_hasher();
+        hasher.write(input.as_bytes());
+        assert_eq!(hasher.finish(), crate::city::city_64::hash64_with_seed(input, 3));
+    }
+
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
     #[test]
     fn fasthash_interop_test() {
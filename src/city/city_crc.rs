@@ -0,0 +1,447 @@
+use std::hash::{BuildHasher, Hasher};
+
+use super::{hash128, hash128_with_seed, hash_len_16, shift_mix};
+use crate::{HashAlgorithm, Hasher128};
+
+/// Hasher for City hash implementation of the 128-bit CRC-based hashing algorithm.
+///
+/// The input is buffered until `finish()` is called.
+#[derive(Default)]
+pub struct CityCrcHasher128 {
+    buffer: Vec<u8>,
+    seed: Option<u128>,
+}
+
+impl CityCrcHasher128 {
+    /// Create a hasher which uses the given seed, like `hash_crc128_with_seed()`.
+    pub fn with_seed(seed: u128) -> Self {
+        Self {
+            buffer: Vec::new(),
+            seed: Some(seed),
+        }
+    }
+}
+
+impl Hasher for CityCrcHasher128 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.buffer.extend(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        self.finish128() as u64
+    }
+}
+
+impl Hasher128 for CityCrcHasher128 {
+    fn finish128(&self) -> u128 {
+        match self.seed {
+            Some(seed) => hash_crc128_with_seed(&self.buffer, seed),
+            None => hash_crc128(&self.buffer),
+        }
+    }
+}
+
+/// Hash builder for City hash implementation of the 128-bit CRC-based hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct CityCrcHash128 {
+    seed: Option<u128>,
+}
+
+impl CityCrcHash128 {
+    /// Create a hash builder whose hashers use the given seed, like `hash_crc128_with_seed()`.
+    pub fn with_seed(seed: u128) -> Self {
+        Self { seed: Some(seed) }
+    }
+}
+
+impl BuildHasher for CityCrcHash128 {
+    type Hasher = CityCrcHasher128;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        match self.seed {
+            Some(seed) => Self::Hasher::with_seed(seed),
+            None => Self::Hasher::default(),
+        }
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for City hash implementation of the 128-bit CRC-based hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct CityCrcRandomState128(CityCrcHash128::with_seed(seed: u128));
+}
+
+// The code below is adapted from C++ code with the following license
+//-----------------------------------------------------------------------------
+// Copyright (c) 2011 Google, Inc.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+const K0: u64 = 0xc3a5c85c97cb3127;
+
+// reflected Castagnoli polynomial, as used by the SSE4.2 crc32 instruction
+const CRC32C_POLY: u32 = 0x82f63b78;
+
+const CRC32C_TABLE: [u32; 256] = crc32c_table();
+
+/// City hash implementation of the 128-bit CRC-based hashing algorithm.
+pub struct CityCrc128;
+
+impl HashAlgorithm for CityCrc128 {
+    const NAME: &'static str = "city_crc128";
+    type Seed = u128;
+    type Output = u128;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hash_crc128(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hash_crc128_with_seed(v, seed)
+    }
+}
+
+/// City hash implementation of the 128-bit CRC-based hashing algorithm (`CityHashCrc128WithSeed`).
+/// This version allows you to specify a seed.
+///
+/// Inputs of up to 900 bytes hash the same as `hash128_with_seed()`.
+pub fn hash_crc128_with_seed<T: AsRef<[u8]>>(v: T, seed: u128) -> u128 {
+    let data = v.as_ref();
+    if data.len() <= 900 {
+        return hash128_with_seed(data, seed);
+    }
+    let result = hash_crc256(data);
+    let u = ((seed >> 64) as u64).wrapping_add(result[0]);
+    let v = (seed as u64).wrapping_add(result[1]);
+    let low = hash_len_16(u, v.wrapping_add(result[2]));
+    let high = hash_len_16(
+        v.rotate_right(32),
+        u.wrapping_mul(K0).wrapping_add(result[3]),
+    );
+    (high as u128) << 64 | low as u128
+}
+
+/// City hash implementation of the 128-bit CRC-based hashing algorithm (`CityHashCrc128`).
+/// This version generates a seed from the start of the data.
+///
+/// Inputs of up to 900 bytes hash the same as `hash128()`.
+pub fn hash_crc128<T: AsRef<[u8]>>(v: T) -> u128 {
+    let data = v.as_ref();
+    if data.len() <= 900 {
+        return hash128(data);
+    }
+    let result = hash_crc256(data);
+    (result[3] as u128) << 64 | result[2] as u128
+}
+
+/// City hash implementation of the 256-bit CRC-based hashing algorithm (`CityHashCrc256`).
+///
+/// The hash is returned as four 64-bit words, in the order of the reference implementation.
+pub fn hash_crc256<T: AsRef<[u8]>>(v: T) -> [u64; 4] {
+    let data = v.as_ref();
+    if data.len() >= 240 {
+        hash_crc256_long(data, 0)
+    } else {
+        let mut buf = [0; 240];
+        buf[..data.len()].copy_from_slice(data);
+        hash_crc256_long(&buf, !(data.len() as u32))
+    }
+}
+
+// requires data.len() >= 240
+fn hash_crc256_long(data: &[u8], seed: u32) -> [u64; 4] {
+    let mut len = data.len();
+    let mut result = [0; 4];
+    let mut a = fetch64(data, 56).wrapping_add(K0);
+    let mut b = fetch64(data, 96).wrapping_add(K0);
+    let mut c = hash_len_16(b, len as u64);
+    result[0] = c;
+    let mut d = fetch64(data, 120).wrapping_mul(K0).wrapping_add(len as u64);
+    result[1] = d;
+    let mut e = fetch64(data, 184).wrapping_add(seed as u64);
+    let mut f = seed as u64;
+    let mut g = 0;
+    let mut h = 0;
+    let mut i = 0;
+    let mut j = 0;
+    let mut t = c.wrapping_add(d);
+    let mut s = 0; // data index
+
+    let mut chunk = |s: usize, multiplier: u64, z: u32| {
+        let old_a = a;
+        a = b
+            .rotate_right(41 ^ z)
+            .wrapping_mul(multiplier)
+            .wrapping_add(fetch64(data, s));
+        b = c
+            .rotate_right(27 ^ z)
+            .wrapping_mul(multiplier)
+            .wrapping_add(fetch64(data, s + 8));
+        c = d
+            .rotate_right(41 ^ z)
+            .wrapping_mul(multiplier)
+            .wrapping_add(fetch64(data, s + 16));
+        d = e
+            .rotate_right(33 ^ z)
+            .wrapping_mul(multiplier)
+            .wrapping_add(fetch64(data, s + 24));
+        e = t
+            .rotate_right(25 ^ z)
+            .wrapping_mul(multiplier)
+            .wrapping_add(fetch64(data, s + 32));
+        t = old_a;
+        f = crc32c_u64(f, a);
+        g = crc32c_u64(g, b);
+        h = crc32c_u64(h, c);
+        i = crc32c_u64(i, d);
+        j = crc32c_u64(j, e);
+    };
+
+    // 240 bytes of input per iteration
+    let iters = len / 240;
+    len -= iters * 240;
+    for _ in 0..iters {
+        for _ in 0..3 {
+            chunk(s, 1, 1);
+            chunk(s + 40, K0, 0);
+            s += 80;
+        }
+    }
+    while len >= 40 {
+        chunk(s, K0, 0);
+        s += 40;
+        len -= 40;
+    }
+    if len > 0 {
+        chunk(s + len - 40, K0, 0);
+    }
+
+    j = j.wrapping_add(i << 32);
+    a = hash_len_16(a, j);
+    h = h.wrapping_add(g << 32);
+    b = b.wrapping_add(h);
+    c = hash_len_16(c, f).wrapping_add(i);
+    d = hash_len_16(d, e.wrapping_add(result[0]));
+    j = j.wrapping_add(e);
+    i = i.wrapping_add(hash_len_16(h, t));
+    e = hash_len_16(a, d).wrapping_add(j);
+    f = hash_len_16(b, c).wrapping_add(a);
+    g = hash_len_16(j, i).wrapping_add(c);
+    result[0] = e.wrapping_add(f).wrapping_add(g).wrapping_add(h);
+    a = shift_mix(a.wrapping_add(g).wrapping_mul(K0))
+        .wrapping_mul(K0)
+        .wrapping_add(b);
+    result[1] = result[1].wrapping_add(a).wrapping_add(result[0]);
+    a = shift_mix(a.wrapping_mul(K0))
+        .wrapping_mul(K0)
+        .wrapping_add(c);
+    result[2] = a.wrapping_add(result[1]);
+    a = shift_mix(a.wrapping_add(e).wrapping_mul(K0)).wrapping_mul(K0);
+    result[3] = a.wrapping_add(result[2]);
+    result
+}
+
+// software version of the `_mm_crc32_u64()` intrinsic:
+// the CRC32C of `v` in little-endian order, continuing from the lower 32 bits of `crc`
+#[inline(always)]
+fn crc32c_u64(crc: u64, v: u64) -> u64 {
+    let mut crc = crc as u32;
+    for byte in v.to_le_bytes() {
+        crc = CRC32C_TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8);
+    }
+    crc as u64
+}
+
+const fn crc32c_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut crc = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ CRC32C_POLY
+            } else {
+                crc >> 1
+            };
+            k += 1;
+        }
+        table[n] = crc;
+        n += 1;
+    }
+    table
+}
+
+#[inline(always)]
+fn fetch64(data: &[u8], i: usize) -> u64 {
+    let buf = [
+        data[i],
+        data[i + 1],
+        data[i + 2],
+        data[i + 3],
+        data[i + 4],
+        data[i + 5],
+        data[i + 6],
+        data[i + 7],
+    ];
+    u64::from_le_bytes(buf)
+}
+
+#[cfg(test)]
+mod test {
+    // reference values from CityHashCrc256 compiled with SSE4.2
+    const CRC256: [(usize, [u64; 4]); 8] = [
+        (
+            0,
+            [
+                0x889f555a0f5b2dc0,
+                0x7767800902c8a8ce,
+                0xbcd2a808f4cb4a44,
+                0xe9024dba8f94f2f3,
+            ],
+        ),
+        (
+            17,
+            [
+                0x6819a18d758e09a9,
+                0x6cc4fe69bad86e87,
+                0x2ca412e9f67b23e2,
+                0xeaa8c3d1b6376ad3,
+            ],
+        ),
+        (
+            239,
+            [
+                0xae5e16318ff5bf6f,
+                0x2d476ff008907d2c,
+                0xbd24eec7ab72a00d,
+                0x1c67b8da32fbf693,
+            ],
+        ),
+        (
+            240,
+            [
+                0x58ad0d1fd50977cd,
+                0x56d786d7e7c51420,
+                0x7a89fa6745f24be4,
+                0x38d6eba87736d89b,
+            ],
+        ),
+        (
+            241,
+            [
+                0xa56b5308e5f47ca3,
+                0xf7d970858bb1bf7b,
+                0xe57be4dfc6391aa8,
+                0xf729e93585585ecd,
+            ],
+        ),
+        (
+            280,
+            [
+                0xeec0a7d72271941f,
+                0x4f33f2b42211b558,
+                0xb543163b6d6f591f,
+                0xbd691fbee4e2cb0f,
+            ],
+        ),
+        (
+            901,
+            [
+                0x80d48386375657f9,
+                0x4a86644e634313ad,
+                0x13260b28ab261ab2,
+                0x172b4081442c7711,
+            ],
+        ),
+        (
+            2047,
+            [
+                0x15d233f538445ce8,
+                0x1c8bcea42bced2b2,
+                0x08b3d40bbefc3c27,
+                0x7dcf57306e70747e,
+            ],
+        ),
+    ];
+
+    fn input() -> Vec<u8> {
+        (0..3000u32)
+            .map(|i| (i.wrapping_mul(2654435761) >> 13) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn compliance_test() {
+        let input = input();
+        for (len, expected) in CRC256 {
+            assert_eq!(crate::city::hash_crc256(&input[..len]), expected, "{}", len);
+        }
+        assert_eq!(
+            crate::city::hash_crc128("hello"),
+            0xf1881d5ba3f4b5ecbbae8265cd136bef
+        );
+        assert_eq!(
+            crate::city::hash_crc128(&input[..1000]),
+            0xfd353f2ad97171f88f90d370b1b6b898
+        );
+        assert_eq!(
+            crate::city::hash_crc128(&input[..3000]),
+            0xe39d89aba97ab9e6e2a8c8e2f8a85f42
+        );
+        let seed = 0xfedcba9876543210_0123456789abcdef;
+        assert_eq!(
+            crate::city::hash_crc128_with_seed(&input[..900], seed),
+            0xa48627878b8a49766957838baa91f207
+        );
+        assert_eq!(
+            crate::city::hash_crc128_with_seed(&input[..901], seed),
+            0xd37628a69a65337ef36bf6944945d4b7
+        );
+        assert_eq!(
+            crate::city::hash_crc128_with_seed(&input[..1024], seed),
+            0x67ad2b03d898c98d8d3cda9b5add1887
+        );
+    }
+
+    #[test]
+    fn short_input_test() {
+        // up to 900 bytes, CityHashCrc128 is CityHash128
+        let input = input();
+        for len in [0, 16, 100, 900] {
+            assert_eq!(
+                crate::city::hash_crc128(&input[..len]),
+                crate::city::hash128(&input[..len])
+            );
+        }
+    }
+
+    #[test]
+    fn seeded_builder_test() {
+        use crate::Hasher128;
+        use std::hash::{BuildHasher, Hasher};
+        let input = input();
+        let mut hasher = crate::city::CityCrcHash128::with_seed(3).build_hasher();
+        hasher.write(&input[..1200]);
+        assert_eq!(
+            hasher.finish128(),
+            crate::city::hash_crc128_with_seed(&input[..1200], 3)
+        );
+    }
+}
@@ -2,7 +2,19 @@
 mod city_128;
 mod city_32;
 mod city_64;
+mod city_crc;
 
-pub use city_32::{hash32, hash32_with_seed, CityHash32, CityHasher32};
-pub use city_64::{hash64, hash64_with_seed, hash64_with_seeds, CityHash64, CityHasher64};
-pub use city_128::{hash128, hash128_with_seed, CityHash128, CityHasher128};
+pub(crate) use city_64::{hash_len_16, shift_mix, weak_hash_len_32_with_seeds};
+
+pub use city_32::{hash32, hash32_with_seed, City32, CityHash32, CityHasher32, CityRandomState32};
+pub use city_64::{
+    hash64, hash64_with_seed, hash64_with_seeds, City64, CityHash64, CityHasher64,
+    CityRandomState64,
+};
+pub use city_128::{
+    hash128, hash128_with_seed, City128, CityHash128, CityHasher128, CityRandomState128,
+};
+pub use city_crc::{
+    hash_crc128, hash_crc128_with_seed, hash_crc256, CityCrc128, CityCrcHash128, CityCrcHasher128,
+    CityCrcRandomState128,
+};
@@ -0,0 +1,126 @@
+//! Cassandra's `Murmur3Partitioner` tokens, to route requests to the replicas owning a partition key.
+//!
+//! ```
+//! use highhash::compat::cassandra;
+//!
+//! assert_eq!(cassandra::token("123"), -7468325962851647638);
+//!
+//! // PRIMARY KEY ((name text, id int), ...)
+//! let components = [b"user".as_ref(), &42i32.to_be_bytes()];
+//! let key = cassandra::composite_key(&components);
+//! assert_eq!(cassandra::composite_token(&components), cassandra::token(key));
+//! ```
+use std::convert::TryFrom;
+
+use crate::murmur;
+
+/// The smallest token, which Cassandra assigns to empty partition keys.
+pub const MINIMUM_TOKEN: i64 = i64::MIN;
+
+/// The token `Murmur3Partitioner.getToken()` assigns to a serialized partition key.
+///
+/// This is the first 64 bits of `murmur::hash128_x64_with_seed()` with seed 0, as a Java `long`,
+/// except that Cassandra's port sign-extends the tail bytes of the input,
+/// and that `i64::MIN` is reserved for `MINIMUM_TOKEN` and replaced with `i64::MAX`.
+pub fn token<T: AsRef<[u8]>>(key: T) -> i64 {
+    let key = key.as_ref();
+    if key.is_empty() {
+        return MINIMUM_TOKEN;
+    }
+    let h1 = (murmur::hash128_x64_sign_extended_with_seed(key, 0) >> 64) as i64;
+    if h1 == i64::MIN {
+        i64::MAX
+    } else {
+        h1
+    }
+}
+
+/// Serialize the components of a composite partition key the way Cassandra's `CompositeType` does.
+///
+/// Every component is written as its length as a 2-byte big-endian number, its bytes, and an
+/// end-of-component byte of 0.
+///
+/// # Panics
+///
+/// Panics if a component is longer than 65535 bytes.
+pub fn composite_key<T: AsRef<[u8]>>(components: &[T]) -> Vec<u8> {
+    let mut key = Vec::new();
+    for component in components {
+        let component = component.as_ref();
+        let len = u16::try_from(component.len()).expect("component is at most 65535 bytes long");
+        key.extend(len.to_be_bytes());
+        key.extend(component);
+        key.push(0);
+    }
+    key
+}
+
+/// The token of a partition key, given the serialized values of its components in order.
+///
+/// Cassandra only serializes partition keys with `CompositeType` when they have several
+/// components, so a single component is hashed as is, like `token()`.
+///
+/// # Panics
+///
+/// Panics if a component is longer than 65535 bytes.
+pub fn composite_token<T: AsRef<[u8]>>(components: &[T]) -> i64 {
+    match components {
+        [component] => token(component),
+        _ => token(composite_key(components)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn token_test() {
+        // from the Python driver's murmur3 tests, which check against Cassandra
+        use crate::compat::cassandra::token;
+        assert_eq!(token("123"), -7468325962851647638);
+        assert_eq!(
+            token(b"\x00\xff\x10\xfa\x99".repeat(10)),
+            5837342703291459765
+        );
+        assert_eq!(token([0xfe; 8]), -8927430733708461935);
+        assert_eq!(token([0x10; 8]), 1446172840243228796);
+        assert_eq!(token(i64::MAX.to_string()), 7162290910810015547);
+    }
+
+    #[test]
+    fn sign_extension_test() {
+        // without bytes >= 0x80 in the tail, the token is the standard hash
+        use crate::compat::cassandra::token;
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        for len in 1..input.len() {
+            let h1 = (crate::murmur::hash128_x64(&input[..len]) >> 64) as i64;
+            assert_eq!(token(&input[..len]), h1);
+        }
+    }
+
+    #[test]
+    fn minimum_token_test() {
+        use crate::compat::cassandra::{token, MINIMUM_TOKEN};
+        assert_eq!(token(""), MINIMUM_TOKEN);
+    }
+
+    #[test]
+    fn composite_key_test() {
+        use crate::compat::cassandra::composite_key;
+        assert_eq!(
+            composite_key(&[b"ab".as_ref(), b"", &7i32.to_be_bytes()]),
+            b"\x00\x02ab\x00\x00\x00\x00\x00\x04\x00\x00\x00\x07\x00"
+        );
+        assert!(composite_key::<&[u8]>(&[]).is_empty());
+    }
+
+    #[test]
+    fn single_component_test() {
+        // a partition key with one column is not a composite key
+        use crate::compat::cassandra::{composite_token, token};
+        assert_eq!(composite_token(&["123"]), -7468325962851647638);
+        assert_eq!(
+            composite_token(&[42i32.to_be_bytes()]),
+            token(42i32.to_be_bytes())
+        );
+    }
+}
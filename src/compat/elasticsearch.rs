@@ -0,0 +1,225 @@
+//! Elasticsearch's document routing, to find the shard a document is stored in.
+//!
+//! ```
+//! use highhash::compat::elasticsearch::IndexRouting;
+//!
+//! // an index created with 5 primary shards on Elasticsearch 7.0 or later
+//! let routing = IndexRouting::new(5);
+//! assert_eq!(routing.routing_num_shards(), 640);
+//! let shard = routing.shard_id("document-id", None);
+//! # assert!(shard < 5);
+//! ```
+use crate::murmur;
+
+/// Elasticsearch's `Murmur3HashFunction.hash(String)`: MurmurHash3 x86 32-bit with seed 0 of the UTF-16
+/// code units of the string, like a Java `String`, in little-endian order.
+pub fn hash(routing: &str) -> i32 {
+    let bytes: Vec<u8> = routing.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    murmur::hash32_with_seed(bytes, 0) as i32
+}
+
+/// The default `index.number_of_routing_shards` of an index created on Elasticsearch 7.0 or later:
+/// the number of shards doubled as many times as possible up to 1024 shards, and at least once.
+///
+/// # Panics
+///
+/// Panics if `num_shards` is 0.
+pub fn default_routing_num_shards(num_shards: u32) -> u32 {
+    assert!(num_shards > 0, "an index has at least one shard");
+    let log2_num_shards = 32 - (num_shards - 1).leading_zeros();
+    let num_splits = 10u32.saturating_sub(log2_num_shards).max(1);
+    num_shards << num_splits
+}
+
+/// The routing settings of an index, which determine the shard of its documents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IndexRouting {
+    num_shards: u32,
+    routing_num_shards: u32,
+    routing_partition_size: u32,
+}
+
+impl IndexRouting {
+    /// Routing of an index with `num_shards` primary shards, created on Elasticsearch 7.0 or later with the
+    /// default `index.number_of_routing_shards` and `index.routing_partition_size`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_shards` is 0.
+    pub fn new(num_shards: u32) -> Self {
+        Self {
+            num_shards,
+            routing_num_shards: default_routing_num_shards(num_shards),
+            routing_partition_size: 1,
+        }
+    }
+
+    /// Use the given `index.number_of_routing_shards`.
+    /// Indices created before Elasticsearch 7.0, or shrunk from another index, need this to be set to the
+    /// number of routing shards of the original index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `routing_num_shards` is not a multiple of the number of shards.
+    pub fn with_routing_num_shards(self, routing_num_shards: u32) -> Self {
+        assert!(
+            routing_num_shards > 0 && routing_num_shards % self.num_shards == 0,
+            "the number of routing shards {} must be a multiple of the number of shards {}",
+            routing_num_shards,
+            self.num_shards
+        );
+        Self {
+            routing_num_shards,
+            ..self
+        }
+    }
+
+    /// Use the given `index.routing_partition_size`, which spreads the documents with the same routing
+    /// value over that many shards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `routing_partition_size` is 0.
+    pub fn with_routing_partition_size(self, routing_partition_size: u32) -> Self {
+        assert!(
+            routing_partition_size > 0,
+            "the routing partition size is at least 1"
+        );
+        Self {
+            routing_partition_size,
+            ..self
+        }
+    }
+
+    /// The number of primary shards.
+    pub fn num_shards(&self) -> u32 {
+        self.num_shards
+    }
+
+    /// The number of routing shards, which the hash of the routing value is reduced to first.
+    pub fn routing_num_shards(&self) -> u32 {
+        self.routing_num_shards
+    }
+
+    /// The routing partition size.
+    pub fn routing_partition_size(&self) -> u32 {
+        self.routing_partition_size
+    }
+
+    /// The shard of a document with the given id and custom routing value, like
+    /// `OperationRouting.generateShardId()`.
+    /// Without a custom routing value, the id is used for routing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index has a routing partition size larger than 1 and `routing` is `None`,
+    /// as Elasticsearch requires a routing value for such indices.
+    pub fn shard_id(&self, id: &str, routing: Option<&str>) -> u32 {
+        let partition_offset = if self.routing_partition_size == 1 {
+            0
+        } else {
+            assert!(
+                routing.is_some(),
+                "a routing value is required for an index with a routing partition size"
+            );
+            floor_mod(hash(id), self.routing_partition_size) as i32
+        };
+        let hash = hash(routing.unwrap_or(id)).wrapping_add(partition_offset);
+        floor_mod(hash, self.routing_num_shards) / (self.routing_num_shards / self.num_shards)
+    }
+}
+
+// Java's `Math.floorMod()` with a positive divisor
+fn floor_mod(x: i32, y: u32) -> u32 {
+    (x as i64).rem_euclid(y as i64) as u32
+}
+
+#[cfg(test)]
+mod test {
+    use crate::compat::elasticsearch::IndexRouting;
+
+    #[test]
+    fn hash_test() {
+        // from Elasticsearch's Murmur3HashFunctionTests
+        use crate::compat::elasticsearch::hash;
+        assert_eq!(hash("hell"), 0x5a0cb7c3);
+        assert_eq!(hash("hello"), 0xd7c31989u32 as i32);
+        assert_eq!(hash("hello w"), 0x22ab2984);
+        assert_eq!(hash("hello wo"), 0xdf0ca123u32 as i32);
+        assert_eq!(hash("hello wor"), 0xe7744d61u32 as i32);
+        assert_eq!(
+            hash("The quick brown fox jumps over the lazy dog"),
+            0xe07db09cu32 as i32
+        );
+        assert_eq!(
+            hash("The quick brown fox jumps over the lazy cog"),
+            0x4e63d2ad
+        );
+    }
+
+    #[test]
+    fn default_routing_num_shards_test() {
+        use crate::compat::elasticsearch::default_routing_num_shards;
+        assert_eq!(default_routing_num_shards(1), 1024);
+        assert_eq!(default_routing_num_shards(2), 1024);
+        assert_eq!(default_routing_num_shards(3), 768);
+        assert_eq!(default_routing_num_shards(5), 640);
+        assert_eq!(default_routing_num_shards(8), 1024);
+        assert_eq!(default_routing_num_shards(1024), 2048);
+        assert_eq!(default_routing_num_shards(2000), 4000);
+    }
+
+    #[test]
+    fn shard_id_test() {
+        // from Elasticsearch's OperationRoutingTests.testBWC(), with 8 shards and 8 routing shards
+        let routing = IndexRouting::new(8).with_routing_num_shards(8);
+        let cases = [
+            ("sEERfFzPSI", 1),
+            ("cNRiIrjzYd", 7),
+            ("BgfLBXUyWT", 5),
+            ("cnepjZhQnb", 3),
+            ("OKCmuYkeCK", 6),
+            ("OutXGRQUja", 5),
+            ("yCdyocKWou", 1),
+            ("KXuNWWNgVj", 2),
+            ("DGJOYrpESx", 4),
+            ("upLDybdTGs", 5),
+        ];
+        for (key, shard) in cases {
+            assert_eq!(routing.shard_id(key, None), shard, "{}", key);
+            assert_eq!(routing.shard_id("foobar", Some(key)), shard, "{}", key);
+        }
+    }
+
+    #[test]
+    fn split_test() {
+        // splitting an index keeps the documents of a shard together
+        let before = IndexRouting::new(2).with_routing_num_shards(16);
+        let after = IndexRouting::new(8).with_routing_num_shards(16);
+        for i in 0..100 {
+            let id = i.to_string();
+            assert_eq!(after.shard_id(&id, None) / 4, before.shard_id(&id, None));
+        }
+    }
+
+    #[test]
+    fn partitioned_test() {
+        let routing = IndexRouting::new(8)
+            .with_routing_num_shards(8)
+            .with_routing_partition_size(3);
+        let mut shards: Vec<u32> = (0..100)
+            .map(|i| routing.shard_id(&i.to_string(), Some("user")))
+            .collect();
+        shards.sort_unstable();
+        shards.dedup();
+        assert_eq!(shards.len(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn partitioned_without_routing_test() {
+        IndexRouting::new(8)
+            .with_routing_partition_size(3)
+            .shard_id("1", None);
+    }
+}
@@ -0,0 +1,256 @@
+//! Guava's `Hashing.murmur3_32()` and `Hashing.murmur3_128()`, to agree with JVM services on hashed keys.
+//!
+//! The 128-bit functions return hashes in the layout of `murmur::hash128_x64()`, `(h1 << 64) | h2`;
+//! use `hash_code_bytes()`, `as_long()` and `as_int()` to read them the way Guava's `HashCode` does.
+//! 32-bit hashes are returned as `HashCode.asInt()`, whose bytes are its little-endian representation.
+//!
+//! ```
+//! use highhash::compat::guava;
+//!
+//! assert_eq!(guava::murmur3_32_hash_unencoded_chars("hello", 0), -675079799);
+//! let hash = guava::murmur3_128_hash_bytes("hell", 0);
+//! assert_eq!(guava::as_long(hash), 0x629942693e10f867);
+//! ```
+use crate::murmur;
+
+/// Guava's `HashCode.asBytes()` for a 128-bit hash: h1 then h2, both in little-endian order.
+pub fn hash_code_bytes(hash: u128) -> [u8; 16] {
+    let mut bytes = [0; 16];
+    bytes[..8].copy_from_slice(&((hash >> 64) as u64).to_le_bytes());
+    bytes[8..].copy_from_slice(&(hash as u64).to_le_bytes());
+    bytes
+}
+
+/// Guava's `HashCode.asLong()` for a 128-bit hash: the first 8 bytes, which are h1.
+pub fn as_long(hash: u128) -> i64 {
+    (hash >> 64) as i64
+}
+
+/// Guava's `HashCode.asInt()` for a 128-bit hash: the first 4 bytes, which are the low half of h1.
+pub fn as_int(hash: u128) -> i32 {
+    (hash >> 64) as i32
+}
+
+/// Guava's `Hashing.murmur3_32(seed).hashBytes()`, as `HashCode.asInt()`.
+pub fn murmur3_32_hash_bytes<T: AsRef<[u8]>>(v: T, seed: i32) -> i32 {
+    murmur::hash32_with_seed(v, seed as u32) as i32
+}
+
+/// Guava's `Hashing.murmur3_32(seed).hashInt()`, as `HashCode.asInt()`.
+pub fn murmur3_32_hash_int(input: i32, seed: i32) -> i32 {
+    murmur3_32_hash_bytes(input.to_le_bytes(), seed)
+}
+
+/// Guava's `Hashing.murmur3_32(seed).hashLong()`, as `HashCode.asInt()`.
+pub fn murmur3_32_hash_long(input: i64, seed: i32) -> i32 {
+    murmur3_32_hash_bytes(input.to_le_bytes(), seed)
+}
+
+/// Guava's `Hashing.murmur3_32(seed).hashUnencodedChars()`, as `HashCode.asInt()`.
+///
+/// This hashes the UTF-16 code units of the string, like a Java `String`, in little-endian order.
+pub fn murmur3_32_hash_unencoded_chars(input: &str, seed: i32) -> i32 {
+    murmur3_32_hash_bytes(utf16_le(input), seed)
+}
+
+/// Guava's `Hashing.murmur3_128(seed).hashBytes()`.
+///
+/// Guava sign-extends its `int` seed, so negative seeds give different results than
+/// `murmur::hash128_x64_with_seed()`.
+pub fn murmur3_128_hash_bytes<T: AsRef<[u8]>>(v: T, seed: i32) -> u128 {
+    murmur::hash128_x64_with_seed64(v.as_ref(), seed as i64 as u64)
+}
+
+/// Guava's `Hashing.murmur3_128(seed).hashInt()`.
+pub fn murmur3_128_hash_int(input: i32, seed: i32) -> u128 {
+    murmur3_128_hash_bytes(input.to_le_bytes(), seed)
+}
+
+/// Guava's `Hashing.murmur3_128(seed).hashLong()`.
+pub fn murmur3_128_hash_long(input: i64, seed: i32) -> u128 {
+    murmur3_128_hash_bytes(input.to_le_bytes(), seed)
+}
+
+/// Guava's `Hashing.murmur3_128(seed).hashUnencodedChars()`.
+///
+/// This hashes the UTF-16 code units of the string, like a Java `String`, in little-endian order.
+pub fn murmur3_128_hash_unencoded_chars(input: &str, seed: i32) -> u128 {
+    murmur3_128_hash_bytes(utf16_le(input), seed)
+}
+
+fn utf16_le(input: &str) -> Vec<u8> {
+    input.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+#[cfg(test)]
+mod test {
+    // expected values from Guava's Murmur3Hash32Test and Murmur3Hash128Test
+
+    #[test]
+    fn murmur3_32_test() {
+        use crate::compat::guava::{murmur3_32_hash_int, murmur3_32_hash_long};
+        assert_eq!(murmur3_32_hash_int(0, 0), 593689054);
+        assert_eq!(murmur3_32_hash_int(-42, 0), -189366624);
+        assert_eq!(murmur3_32_hash_int(42, 0), -1134849565);
+        assert_eq!(murmur3_32_hash_int(i32::MIN, 0), -1718298732);
+        assert_eq!(murmur3_32_hash_int(i32::MAX, 0), -1653689534);
+        assert_eq!(murmur3_32_hash_long(0, 0), 1669671676);
+        assert_eq!(murmur3_32_hash_long(-42, 0), -846261623);
+        assert_eq!(murmur3_32_hash_long(42, 0), 1871679806);
+        assert_eq!(murmur3_32_hash_long(i64::MIN, 0), 1366273829);
+        assert_eq!(murmur3_32_hash_long(i64::MAX, 0), -2106506049);
+    }
+
+    #[test]
+    fn unencoded_chars_test() {
+        use crate::compat::guava::murmur3_32_hash_unencoded_chars;
+        assert_eq!(murmur3_32_hash_unencoded_chars("", 0), 0);
+        assert_eq!(murmur3_32_hash_unencoded_chars("k", 0), 679745764);
+        assert_eq!(murmur3_32_hash_unencoded_chars("hell", 0), 1510782915);
+        assert_eq!(murmur3_32_hash_unencoded_chars("hello", 0), -675079799);
+        assert_eq!(
+            murmur3_32_hash_unencoded_chars("http://www.google.com/", 0),
+            1935035788
+        );
+        assert_eq!(
+            murmur3_32_hash_unencoded_chars("The quick brown fox jumps over the lazy dog", 0),
+            -528633700
+        );
+    }
+
+    #[test]
+    fn murmur3_128_test() {
+        use crate::compat::guava::{as_long, murmur3_128_hash_bytes};
+        let hash = murmur3_128_hash_bytes("hell", 0);
+        assert_eq!(hash, 0x629942693e10f867_92db0b82baeb5347);
+        assert_eq!(as_long(hash), 0x629942693e10f867);
+        assert_eq!(
+            murmur3_128_hash_bytes("hello", 1),
+            0xa78ddff5adae8d10_128900ef20900135
+        );
+        assert_eq!(
+            murmur3_128_hash_bytes("The quick brown fox jumps over the lazy dog", 0),
+            0xe34bbc7bbc071b6c_7a433ca9c49a9347
+        );
+        // non-negative seeds are the standard algorithm
+        assert_eq!(
+            murmur3_128_hash_bytes("hello", 1),
+            crate::murmur::hash128_x64_with_seed("hello", 1)
+        );
+    }
+
+    #[test]
+    fn murmur3_128_int_test() {
+        // computed with Guava 33.4.0
+        use crate::compat::guava::{as_long, hash_code_bytes, murmur3_128_hash_int};
+        let hash = murmur3_128_hash_int(0, 0);
+        assert_eq!(hash, 0xcfa0f7ddd84c76bc_589623161cf526f1);
+        assert_eq!(as_long(hash), -3485513579396041028);
+        assert_eq!(
+            hash_code_bytes(hash),
+            [
+                0xbc, 0x76, 0x4c, 0xd8, 0xdd, 0xf7, 0xa0, 0xcf, 0xf1, 0x26, 0xf5, 0x1c, 0x16, 0x23,
+                0x96, 0x58
+            ]
+        );
+        assert_eq!(
+            murmur3_128_hash_int(-42, 0),
+            0x5b892edfd3d2f851_092411718d010d5d
+        );
+        assert_eq!(
+            murmur3_128_hash_int(42, 0),
+            0x286f48e61c6e34cf_e2d23d6a2bbcb816
+        );
+        assert_eq!(
+            murmur3_128_hash_int(i32::MIN, 0),
+            0x6efda854479dbf85_ef7013b96a72ecf0
+        );
+        assert_eq!(
+            murmur3_128_hash_int(i32::MAX, 0),
+            0x7744c65bde2c7f9e_4346a347ffddd000
+        );
+        assert_eq!(
+            murmur3_128_hash_int(0, 7),
+            0x79d54dd1bf713748_0af5e7f1b766291d
+        );
+        assert_eq!(
+            murmur3_128_hash_int(-42, 7),
+            0x008fa333269a1ee4_9c9fa1a14e29b1d0
+        );
+        assert_eq!(
+            murmur3_128_hash_int(i32::MAX, 7),
+            0x8ff890b3c66cea63_0109c48f83bfa820
+        );
+    }
+
+    #[test]
+    fn murmur3_128_long_test() {
+        // computed with Guava 33.4.0
+        use crate::compat::guava::{as_long, murmur3_128_hash_long};
+        let hash = murmur3_128_hash_long(0, 0);
+        assert_eq!(hash, 0x28df63b7cc57c3cb_f2557dfcc4e8fe52);
+        assert_eq!(as_long(hash), 2945182322382062539);
+        assert_eq!(
+            murmur3_128_hash_long(-42, 0),
+            0xc19bd738efd199db_11ca1c75e92f3ee5
+        );
+        assert_eq!(
+            murmur3_128_hash_long(42, 0),
+            0xb6acc39989d27df8_24b917fb96f22f80
+        );
+        assert_eq!(
+            murmur3_128_hash_long(i64::MIN, 0),
+            0x01159dfeb4593227_8bdef8b0ec4fe0b6
+        );
+        assert_eq!(
+            murmur3_128_hash_long(i64::MAX, 0),
+            0x6c76ebcbdad669d4_7dfb92c0a9003d9e
+        );
+        assert_eq!(
+            murmur3_128_hash_long(0, 7),
+            0x6ce113b115a56871_195953c2230f8db2
+        );
+        assert_eq!(
+            murmur3_128_hash_long(-42, 7),
+            0x8181256ac0e21ecf_9194b8d98b5048cd
+        );
+        assert_eq!(
+            murmur3_128_hash_long(i64::MAX, 7),
+            0x241315eea83e4f34_d5f76360124f011e
+        );
+    }
+
+    #[test]
+    fn negative_seed_test() {
+        // Murmur3_128Hasher initializes its `long` state from the `int` seed;
+        // expected values computed with Guava 33.4.0
+        use crate::compat::guava::{as_long, murmur3_128_hash_bytes};
+        assert_ne!(
+            murmur3_128_hash_bytes("hello", -1),
+            crate::murmur::hash128_x64_with_seed("hello", u32::MAX)
+        );
+        let hash = murmur3_128_hash_bytes("", -1);
+        assert_eq!(hash, 0xe4c18f38c65bd6ed_8efd8ae920620daf);
+        assert_eq!(as_long(hash), -1963130488547977491);
+        assert_eq!(
+            murmur3_128_hash_bytes("hello", -1),
+            0xc0eb3c1697185396_dbb7ade0823f4087
+        );
+        assert_eq!(
+            murmur3_128_hash_bytes("The quick brown fox jumps over the lazy dog", -1),
+            0x7ddc2cae3c8061ce_b3aa26abb775cf00
+        );
+    }
+
+    #[test]
+    fn hash_code_test() {
+        use crate::compat::guava::{as_int, as_long, hash_code_bytes};
+        let hash = 0x0807060504030201_100f0e0d0c0b0a09;
+        assert_eq!(
+            hash_code_bytes(hash),
+            [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]
+        );
+        assert_eq!(as_long(hash), 0x0807060504030201);
+        assert_eq!(as_int(hash), 0x04030201);
+    }
+}
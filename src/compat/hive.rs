@@ -0,0 +1,182 @@
+//! Hive's bucketing hashes, to find the bucket file of a row in a bucketed table.
+//!
+//! Tables with `bucketing_version` 1 use Java-style hash codes, while tables with `bucketing_version` 2
+//! (the default since Hive 3) use Murmur3. In both cases, the hashes of the bucketing columns are
+//! combined with `combine()` and the bucket is `bucket()` of the result.
+//!
+//! ```
+//! use highhash::compat::hive;
+//!
+//! // CLUSTERED BY (id, name) INTO 16 BUCKETS
+//! let hash = hive::combine(&[hive::hash_int_v2(42), hive::hash_string_v2("alice")]);
+//! let bucket = hive::bucket(hash, 16);
+//! # assert!(bucket < 16);
+//! ```
+use crate::murmur;
+
+/// Seed of Hive's `Murmur3.hash32()`, used for `bucketing_version` 2.
+pub const SEED: i32 = 104729;
+
+/// The version 1 hash of an `int`, `smallint` or `tinyint` value, which is the value itself.
+pub fn hash_int_v1(input: i32) -> i32 {
+    input
+}
+
+/// The version 1 hash of a `bigint` value, like Java's `Long.hashCode()`.
+pub fn hash_long_v1(input: i64) -> i32 {
+    (input ^ (input >> 32)) as i32
+}
+
+/// The version 1 hash of a `string` value, given its UTF-8 bytes.
+///
+/// This is `String.hashCode()` for ASCII strings, but other characters are hashed as signed UTF-8 bytes.
+pub fn hash_string_v1<T: AsRef<[u8]>>(v: T) -> i32 {
+    v.as_ref().iter().fold(0i32, |r, &byte| {
+        r.wrapping_mul(31).wrapping_add(byte as i8 as i32)
+    })
+}
+
+/// The version 2 hash of an `int` value: Hive's `Murmur3.hash32()` of its big-endian bytes.
+pub fn hash_int_v2(input: i32) -> i32 {
+    murmur::hash32_with_seed(input.to_be_bytes(), SEED as u32) as i32
+}
+
+/// The version 2 hash of a `smallint` value: Hive's `Murmur3.hash32()` of its big-endian bytes.
+///
+/// Values shorter than 4 bytes only go through the tail of the hash, so like string tails, their
+/// bytes are sign-extended.
+pub fn hash_short_v2(input: i16) -> i32 {
+    murmur::hash32_sign_extended_with_seed(&input.to_be_bytes(), SEED as u32) as i32
+}
+
+/// The version 2 hash of a `tinyint` value: Hive's `Murmur3.hash32()` of its single byte.
+///
+/// Like in `hash_short_v2()`, the byte is sign-extended.
+pub fn hash_byte_v2(input: i8) -> i32 {
+    murmur::hash32_sign_extended_with_seed(&[input as u8], SEED as u32) as i32
+}
+
+/// The version 2 hash of a `bigint` value: Hive's `Murmur3.hash32()` of its big-endian bytes.
+pub fn hash_long_v2(input: i64) -> i32 {
+    murmur::hash32_with_seed(input.to_be_bytes(), SEED as u32) as i32
+}
+
+/// The version 2 hash of a `string` value: Hive's `Murmur3.hash32()` of its UTF-8 bytes.
+///
+/// Hive's port of MurmurHash3 sign-extends the bytes after the last full 4-byte block, so the result
+/// differs from `murmur::hash32_with_seed()` when one of them is not ASCII.
+pub fn hash_string_v2<T: AsRef<[u8]>>(v: T) -> i32 {
+    murmur::hash32_sign_extended_with_seed(v.as_ref(), SEED as u32) as i32
+}
+
+/// Combine the hashes of the bucketing columns, in order, like `ObjectInspectorUtils.getBucketHashCode()`.
+pub fn combine(hashes: &[i32]) -> i32 {
+    hashes.iter().fold(0i32, |hash, &field| {
+        hash.wrapping_mul(31).wrapping_add(field)
+    })
+}
+
+/// The bucket of a row with the given combined hash, like `ObjectInspectorUtils.getBucketNumber()`.
+///
+/// # Panics
+///
+/// Panics if `num_buckets` is 0.
+pub fn bucket(hash: i32, num_buckets: u32) -> u32 {
+    assert!(num_buckets > 0, "there is at least one bucket");
+    (hash & i32::MAX) as u32 % num_buckets
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn v1_test() {
+        use crate::compat::hive::{hash_long_v1, hash_string_v1};
+        // the same as String.hashCode() for ASCII
+        assert_eq!(hash_string_v1(""), 0);
+        assert_eq!(hash_string_v1("hello"), 99162322);
+        // "é" is 0xc3 0xa9 in UTF-8
+        assert_eq!(hash_string_v1("é"), -61 * 31 - 87);
+        assert_eq!(hash_long_v1(1), 1);
+        assert_eq!(hash_long_v1(-1), 0);
+        assert_eq!(hash_long_v1(1 << 32), 1);
+    }
+
+    #[test]
+    fn v2_test() {
+        // computed with Apache Commons Codec's MurmurHash3.hash32(byte[], int, int, int), its
+        // port of Hive's Murmur3.hash32() which keeps the sign extension, using Hive's seed and
+        // the big-endian and UTF-8 bytes ObjectInspectorUtils.hashCodeMurmur() hashes
+        use crate::compat::hive::{
+            hash_byte_v2, hash_int_v2, hash_long_v2, hash_short_v2, hash_string_v2,
+        };
+        let ints: [(i32, i32); 6] = [
+            (0, 1362653161),
+            (1, 1321152925),
+            (-1, 1626716813),
+            (42, 1960085659),
+            (i32::MIN, 1194881028),
+            (i32::MAX, 1133859967),
+        ];
+        for &(input, hash) in ints.iter() {
+            assert_eq!(hash_int_v2(input), hash, "{}", input);
+        }
+        let longs: [(i64, i32); 6] = [
+            (0, 1754797035),
+            (1, -913662660),
+            (-1, -237789309),
+            (42, -1236600470),
+            (i64::MIN, 1728983947),
+            (i64::MAX, -536577852),
+        ];
+        for &(input, hash) in longs.iter() {
+            assert_eq!(hash_long_v2(input), hash, "{}", input);
+        }
+        let shorts: [(i16, i32); 6] = [
+            (0, 134891328),
+            (1, 1484957954),
+            (-1, -1220617012),
+            (42, 1876706815),
+            (i16::MIN, 1342976838),
+            (i16::MAX, -684075052),
+        ];
+        for &(input, hash) in shorts.iter() {
+            assert_eq!(hash_short_v2(input), hash, "{}", input);
+        }
+        let bytes: [(i8, i32); 6] = [
+            (0, 500407381),
+            (1, -1089647197),
+            (-1, -1036465288),
+            (42, 1229808197),
+            (i8::MIN, 1104592682),
+            (i8::MAX, 1106843505),
+        ];
+        for &(input, hash) in bytes.iter() {
+            assert_eq!(hash_byte_v2(input), hash, "{}", input);
+        }
+        // "hellé" and "日本語" end in bytes >= 0x80 after the last full block
+        let strings = [
+            ("", -965378730),
+            ("a", 1086686554),
+            ("hello", 1321743225),
+            ("hellé", 464203643),
+            ("héllo", 1190235422),
+            ("naïve café", 134704803),
+            ("日本語", 1359263722),
+            ("alice", -1783678941),
+        ];
+        for &(input, hash) in strings.iter() {
+            assert_eq!(hash_string_v2(input), hash, "{}", input);
+        }
+    }
+
+    #[test]
+    fn bucket_test() {
+        use crate::compat::hive::{bucket, combine};
+        assert_eq!(combine(&[]), 0);
+        assert_eq!(combine(&[5]), 5);
+        assert_eq!(combine(&[1, 2]), 33);
+        assert_eq!(bucket(33, 16), 1);
+        assert_eq!(bucket(-1, 16), 15);
+        assert_eq!(bucket(i32::MIN, 16), 0);
+    }
+}
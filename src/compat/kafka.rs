@@ -0,0 +1,83 @@
+//! Kafka's murmur2 partitioning, as used by the Java client's `DefaultPartitioner` for records with a key.
+//!
+//! ```
+//! use highhash::compat::kafka;
+//!
+//! assert_eq!(kafka::murmur2("foobar"), -790332482);
+//! assert_eq!(kafka::partition("foobar", 12), 6);
+//! ```
+use crate::murmur;
+
+/// Seed used by `org.apache.kafka.common.utils.Utils.murmur2()`.
+pub const SEED: u32 = 0x9747b28c;
+
+/// Kafka's `Utils.murmur2()`: MurmurHash2 with Kafka's seed, as a Java `int`.
+pub fn murmur2<T: AsRef<[u8]>>(key: T) -> i32 {
+    murmur::murmur2_32_with_seed(key, SEED) as i32
+}
+
+/// Kafka's `Utils.toPositive()`: clears the sign bit.
+///
+/// This is not the absolute value: negative numbers map to `number + 2^31`, and `i32::MIN` maps to 0.
+pub fn to_positive(number: i32) -> i32 {
+    number & 0x7fffffff
+}
+
+/// The partition the Java client's `DefaultPartitioner` picks for a record with the given serialized key.
+///
+/// # Panics
+///
+/// Panics if `num_partitions` is 0.
+pub fn partition<T: AsRef<[u8]>>(key: T, num_partitions: u32) -> u32 {
+    assert!(num_partitions > 0, "a topic has at least one partition");
+    to_positive(murmur2(key)) as u32 % num_partitions
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn murmur2_test() {
+        // from Kafka's UtilsTest.testMurmur2()
+        let cases: [(&[u8], i32); 6] = [
+            (b"21", -973932308),
+            (b"foobar", -790332482),
+            (b"a-little-bit-long-string", -985981536),
+            (b"a-little-bit-longer-string", -1486304829),
+            (
+                b"lkjh234lh9fiuh90y23oiuhsafujhadof229phr9h19h89h8",
+                -58897971,
+            ),
+            (b"abc", 479470107),
+        ];
+        for (key, expected) in cases {
+            assert_eq!(crate::compat::kafka::murmur2(key), expected);
+        }
+    }
+
+    #[test]
+    fn to_positive_test() {
+        use crate::compat::kafka::to_positive;
+        assert_eq!(to_positive(0), 0);
+        assert_eq!(to_positive(1), 1);
+        assert_eq!(to_positive(-1), i32::MAX);
+        assert_eq!(to_positive(i32::MAX), i32::MAX);
+        assert_eq!(to_positive(i32::MIN), 0);
+    }
+
+    #[test]
+    fn partition_test() {
+        use crate::compat::kafka::partition;
+        // toPositive(-790332482) = 1357151166
+        assert_eq!(partition("foobar", 1), 0);
+        assert_eq!(partition("foobar", 12), 1357151166 % 12);
+        assert_eq!(partition("foobar", 1000), 166);
+        // toPositive(479470107) = 479470107
+        assert_eq!(partition("abc", 7), 479470107 % 7);
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_partitions_test() {
+        crate::compat::kafka::partition("foobar", 0);
+    }
+}
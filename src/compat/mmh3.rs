@@ -0,0 +1,132 @@
+//! The functions of Python's `mmh3` package, with its signedness and byte order.
+//!
+//! Python strings are hashed as their UTF-8 bytes, so `&str` keys give the same results as in Python.
+//! The `x64arch` parameters select between `murmur::hash128_x64_with_seed()` (`true`, mmh3's default)
+//! and `murmur::hash128_with_seed()` (`false`).
+//!
+//! ```
+//! use highhash::compat::mmh3;
+//!
+//! // mmh3.hash("foo", 42)
+//! assert_eq!(mmh3::hash("foo", 42), -1322301282);
+//! // mmh3.hash128("foo", 42)
+//! assert_eq!(mmh3::hash128("foo", 42, true), 215966891540331383248189432718888555506);
+//! ```
+use crate::murmur;
+
+/// `mmh3.hash(key, seed)`: MurmurHash3 x86 32-bit as a signed number.
+pub fn hash<T: AsRef<[u8]>>(key: T, seed: u32) -> i32 {
+    hash_unsigned(key, seed) as i32
+}
+
+/// `mmh3.hash(key, seed, signed=False)`: MurmurHash3 x86 32-bit as an unsigned number.
+pub fn hash_unsigned<T: AsRef<[u8]>>(key: T, seed: u32) -> u32 {
+    murmur::hash32_with_seed(key, seed)
+}
+
+/// `mmh3.hash_bytes(key, seed, x64arch)`: the 128-bit hash as the bytes the reference implementation
+/// writes to memory on little-endian machines.
+pub fn hash_bytes<T: AsRef<[u8]>>(key: T, seed: u32, x64arch: bool) -> [u8; 16] {
+    let mut bytes = [0; 16];
+    if x64arch {
+        let hash = murmur::hash128_x64_with_seed(key, seed);
+        for (i, chunk) in bytes.chunks_exact_mut(8).enumerate() {
+            chunk.copy_from_slice(&((hash >> (64 - 64 * i)) as u64).to_le_bytes());
+        }
+    } else {
+        let hash = murmur::hash128_with_seed(key, seed);
+        for (i, chunk) in bytes.chunks_exact_mut(4).enumerate() {
+            chunk.copy_from_slice(&((hash >> (96 - 32 * i)) as u32).to_le_bytes());
+        }
+    }
+    bytes
+}
+
+/// `mmh3.hash128(key, seed, x64arch)`: the bytes of `hash_bytes()` as an unsigned little-endian number.
+pub fn hash128<T: AsRef<[u8]>>(key: T, seed: u32, x64arch: bool) -> u128 {
+    u128::from_le_bytes(hash_bytes(key, seed, x64arch))
+}
+
+/// `mmh3.hash128(key, seed, x64arch, signed=True)`: the bytes of `hash_bytes()` as a signed
+/// little-endian number.
+pub fn hash128_signed<T: AsRef<[u8]>>(key: T, seed: u32, x64arch: bool) -> i128 {
+    hash128(key, seed, x64arch) as i128
+}
+
+/// `mmh3.hash64(key, seed, x64arch)`: the two halves of `hash_bytes()` as signed little-endian numbers.
+pub fn hash64<T: AsRef<[u8]>>(key: T, seed: u32, x64arch: bool) -> (i64, i64) {
+    let (first, second) = hash64_unsigned(key, seed, x64arch);
+    (first as i64, second as i64)
+}
+
+/// `mmh3.hash64(key, seed, x64arch, signed=False)`: the two halves of `hash_bytes()` as unsigned
+/// little-endian numbers.
+pub fn hash64_unsigned<T: AsRef<[u8]>>(key: T, seed: u32, x64arch: bool) -> (u64, u64) {
+    let hash = hash128(key, seed, x64arch);
+    (hash as u64, (hash >> 64) as u64)
+}
+
+#[cfg(test)]
+mod test {
+    // expected values from the mmh3 documentation
+
+    #[test]
+    fn hash_test() {
+        use crate::compat::mmh3::{hash, hash_unsigned};
+        assert_eq!(hash("foo", 0), -156908512);
+        assert_eq!(hash("foo", 42), -1322301282);
+        assert_eq!(hash_unsigned("foo", 0), 4138058784);
+    }
+
+    #[test]
+    fn hash64_test() {
+        use crate::compat::mmh3::{hash64, hash64_unsigned};
+        assert_eq!(
+            hash64("foo", 0, true),
+            (-2129773440516405919, 9128664383759220103)
+        );
+        assert_eq!(
+            hash64_unsigned("foo", 0, true),
+            (-2129773440516405919i64 as u64, 9128664383759220103)
+        );
+    }
+
+    #[test]
+    fn hash128_test() {
+        use crate::compat::mmh3::{hash128, hash128_signed};
+        assert_eq!(
+            hash128("foo", 42, true),
+            215966891540331383248189432718888555506
+        );
+        assert_eq!(
+            hash128_signed("foo", 42, true),
+            215966891540331383248189432718888555506u128 as i128
+        );
+    }
+
+    #[test]
+    fn hash_bytes_test() {
+        use crate::compat::mmh3::hash_bytes;
+        assert_eq!(
+            &hash_bytes("foo", 0, true),
+            b"aE\xf5\x01W\x86q\xe2\x87}\xba+\xe4\x87\xaf~"
+        );
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn fasthash_interop_test() {
+        // fasthash returns the output of the reference implementation as a little-endian number, like mmh3
+        use fasthash::FastHash;
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let seed = 4919;
+        assert_eq!(
+            crate::compat::mmh3::hash128(input, seed, true),
+            fasthash::murmur3::Hash128_x64::hash_with_seed(input, seed)
+        );
+        assert_eq!(
+            crate::compat::mmh3::hash128(input, seed, false),
+            fasthash::murmur3::Hash128_x86::hash_with_seed(input, seed)
+        );
+    }
+}
@@ -0,0 +1,12 @@
+//! Hash functions reproducing the exact output of other systems built on the hashing algorithms of this crate.
+//!
+//! These exist to interoperate with data keyed, partitioned or routed by those systems,
+//! including any quirks they have relative to the reference algorithms.
+pub mod cassandra;
+pub mod elasticsearch;
+pub mod guava;
+pub mod hive;
+pub mod kafka;
+pub mod mmh3;
+pub mod postgres;
+pub mod spark;
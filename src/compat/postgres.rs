@@ -0,0 +1,274 @@
+//! PostgreSQL's `hash_any()` family, as used by hash indexes, hash joins and hash partitioning.
+//!
+//! PostgreSQL hashes with a variant of lookup3 that uses a different initial value and packs the
+//! last bytes differently, and its results depend on the byte order of the server.
+//! These functions reproduce the results of little-endian servers, which covers x86 and ARM.
+//!
+//! ```
+//! use highhash::compat::postgres;
+//!
+//! // the standard hash is the low half of the extended hash without a seed
+//! assert_eq!(
+//!     postgres::hash_any("foobar"),
+//!     postgres::hash_bytes_extended("foobar", 0) as u32
+//! );
+//! assert_eq!(postgres::hash_uint32(42), postgres::hash_any(42u32.to_le_bytes()));
+//! ```
+use crate::jenkins::{fetch32_le, final_mix, mix};
+
+/// Seed PostgreSQL passes to the extended hash functions of the partition key columns
+/// (`HASH_PARTITION_SEED`).
+pub const PARTITION_SEED: u64 = 0x7a5b22367996dcfd;
+
+/// PostgreSQL's `hash_any()`, also known as `hash_bytes()`.
+///
+/// This is `hashtext()` for text in a deterministic collation, and `hashvarlena()` for `bytea`.
+pub fn hash_any<T: AsRef<[u8]>>(key: T) -> u32 {
+    hash_bytes(key.as_ref(), 0).1
+}
+
+/// PostgreSQL's `hash_bytes_extended()`, also known as `hash_any_extended()`.
+///
+/// With a seed of 0, the low 32 bits are the same as `hash_any()`.
+pub fn hash_bytes_extended<T: AsRef<[u8]>>(key: T, seed: u64) -> u64 {
+    let (b, c) = hash_bytes(key.as_ref(), seed);
+    (b as u64) << 32 | c as u64
+}
+
+/// PostgreSQL's `hash_uint32()`, which hashes 4-byte integers like `hashint4()`.
+pub fn hash_uint32(k: u32) -> u32 {
+    hash_uint32_state(k, 0).1
+}
+
+/// PostgreSQL's `hash_uint32_extended()`.
+///
+/// With a seed of 0, the low 32 bits are the same as `hash_uint32()`.
+pub fn hash_uint32_extended(k: u32, seed: u64) -> u64 {
+    let (b, c) = hash_uint32_state(k, seed);
+    (b as u64) << 32 | c as u64
+}
+
+// the initial value of the internal state, for an input of `len` bytes
+fn init(len: usize, seed: u64) -> (u32, u32, u32) {
+    let a = 0x9e3779b9_u32
+        .wrapping_add(len as u32)
+        .wrapping_add(3923095);
+    if seed == 0 {
+        (a, a, a)
+    } else {
+        // the seed is treated as a 12-byte chunk of data, padded with zeroes
+        mix(
+            a.wrapping_add((seed >> 32) as u32),
+            a.wrapping_add(seed as u32),
+            a,
+        )
+    }
+}
+
+// returns the final values of b and c
+fn hash_bytes(data: &[u8], seed: u64) -> (u32, u32) {
+    let (mut a, mut b, mut c) = init(data.len(), seed);
+
+    let mut rest = data;
+    while rest.len() >= 12 {
+        a = a.wrapping_add(fetch32_le(rest, 0));
+        b = b.wrapping_add(fetch32_le(rest, 4));
+        c = c.wrapping_add(fetch32_le(rest, 8));
+        let mixed = mix(a, b, c);
+        a = mixed.0;
+        b = mixed.1;
+        c = mixed.2;
+        rest = &rest[12..];
+    }
+
+    // the last 0 to 11 bytes, zero-padded, with the lowest byte of c left out like in lookup2
+    let mut block = [0; 12];
+    block[..rest.len()].copy_from_slice(rest);
+    let (_, b, c) = final_mix(
+        a.wrapping_add(fetch32_le(&block, 0)),
+        b.wrapping_add(fetch32_le(&block, 4)),
+        c.wrapping_add(fetch32_le(&block, 8) << 8),
+    );
+    (b, c)
+}
+
+// returns the final values of b and c
+fn hash_uint32_state(k: u32, seed: u64) -> (u32, u32) {
+    let (a, b, c) = init(4, seed);
+    let (_, b, c) = final_mix(a.wrapping_add(k), b, c);
+    (b, c)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::compat::postgres::{
+        hash_any, hash_bytes_extended, hash_uint32, hash_uint32_extended, PARTITION_SEED,
+    };
+
+    // lookup3's initial value is 0xdeadbeef + length + seed
+    const LOOKUP3_SEED: u32 = 0x9e3779b9_u32
+        .wrapping_add(3923095)
+        .wrapping_sub(0xdeadbeef);
+
+    // the expected values below are the results of a little-endian PostgreSQL 15 server,
+    // with PARTITION_SEED written as 8816678312871386365
+
+    #[test]
+    fn hashtext_test() {
+        let expected = [
+            ("", -1477818771),
+            ("a", 1075015857),
+            ("abc", -785388649),
+            ("abcd", -393934804),
+            ("abcdefgh", -1960928205),
+            ("abcdefghi", -92131489),
+            ("abcdefghij", 1948051852),
+            ("abcdefghijk", -1483803693),
+            ("abcdefghijkl", -1586087212),
+            ("abcdefghijklm", 405849808),
+            ("Four score and seven years ago", -235275101),
+            ("abcdefghijklmnopqrstuvwx", 1251586959),
+        ];
+        for &(s, hash) in expected.iter() {
+            assert_eq!(hash_any(s) as i32, hash, "{}", s);
+        }
+    }
+
+    #[test]
+    fn hashtextextended_test() {
+        let expected = [
+            ("", 0, -6939563903564495251),
+            ("", 1, -150071773020461347),
+            ("", PARTITION_SEED, -5700645584453517373),
+            ("a", 0, 3591986179850072241),
+            ("a", 1, 3325245193489759299),
+            ("a", PARTITION_SEED, -6705225459120232837),
+            ("abc", 0, -6747756470228489321),
+            ("abc", 1, 2125675926272891485),
+            ("abc", PARTITION_SEED, 3628778498291917250),
+            ("abcd", 0, -1038429303365892052),
+            ("abcd", 1, -9042268383291675265),
+            ("abcd", PARTITION_SEED, 4029384952263848046),
+            ("abcdefgh", 0, -8882191227074733005),
+            ("abcdefgh", 1, -996835456672934620),
+            ("abcdefgh", PARTITION_SEED, -7754745184861941070),
+            ("abcdefghi", 0, -3278210106038014113),
+            ("abcdefghi", 1, 5773402991589158274),
+            ("abcdefghi", PARTITION_SEED, -7860738947571725408),
+            ("abcdefghij", 0, -3183301415634409076),
+            ("abcdefghij", 1, 3861069368272743232),
+            ("abcdefghij", PARTITION_SEED, -8306284229451770207),
+            ("abcdefghijk", 0, 3112909444737856467),
+            ("abcdefghijk", 1, 8196382510784792041),
+            ("abcdefghijk", PARTITION_SEED, -2046435308545367432),
+            ("abcdefghijkl", 0, -7556637188122330412),
+            ("abcdefghijkl", 1, -6429754808531195817),
+            ("abcdefghijkl", PARTITION_SEED, -7876791541613377658),
+            ("abcdefghijklm", 0, 8871624171452417744),
+            ("abcdefghijklm", 1, 3808842878744369845),
+            ("abcdefghijklm", PARTITION_SEED, -6785754114725280077),
+            ("Four score and seven years ago", 0, -3250576024605688669),
+            ("Four score and seven years ago", 1, -4731987929771913973),
+            (
+                "Four score and seven years ago",
+                PARTITION_SEED,
+                -2434625085857358110,
+            ),
+            ("abcdefghijklmnopqrstuvwx", 0, -1933194731779935345),
+            ("abcdefghijklmnopqrstuvwx", 1, -731882798451170301),
+            (
+                "abcdefghijklmnopqrstuvwx",
+                PARTITION_SEED,
+                -10959101940276370,
+            ),
+        ];
+        for &(s, seed, hash) in expected.iter() {
+            assert_eq!(hash_bytes_extended(s, seed) as i64, hash, "{} {}", s, seed);
+        }
+    }
+
+    #[test]
+    fn hashint4_test() {
+        let expected = [
+            (0_i32, -272711505),
+            (1, -1905060026),
+            (42, 1509752520),
+            (-1, 385747274),
+            (i32::MAX, -96758253),
+        ];
+        for &(k, hash) in expected.iter() {
+            assert_eq!(hash_uint32(k as u32) as i32, hash, "{}", k);
+        }
+    }
+
+    #[test]
+    fn hashint4extended_test() {
+        let expected = [
+            (0_i32, 0, 4154612158245552303),
+            (0, 1, 434346878670890809),
+            (0, PARTITION_SEED, -4403592609991167795),
+            (1, 0, -3670598878359251130),
+            (1, 1, -7620678293940249823),
+            (1, PARTITION_SEED, 5968994663651403477),
+            (42, 0, 8010225493015854792),
+            (42, 1, 3812564155960986560),
+            (42, PARTITION_SEED, 7363975540656877951),
+            (-1, 0, -1888257769727981238),
+            (-1, 1, 3987656643895367148),
+            (-1, PARTITION_SEED, -5017072347659237694),
+            (i32::MAX, 0, -9109110466470504941),
+            (i32::MAX, 1, 2560836655869490746),
+            (i32::MAX, PARTITION_SEED, -6050265599104649060),
+        ];
+        for &(k, seed, hash) in expected.iter() {
+            assert_eq!(
+                hash_uint32_extended(k as u32, seed) as i64,
+                hash,
+                "{} {}",
+                k,
+                seed
+            );
+        }
+    }
+
+    #[test]
+    fn lookup3_test() {
+        // hash_any only differs from hashlittle in the initial value, in the packing of the last
+        // 9 to 11 bytes, and when the input is a non-zero multiple of 12 bytes
+        let input: Vec<u8> = (0..64u32).map(|i| (i * 7 + 3) as u8).collect();
+        for len in 0..input.len() {
+            let tail = len % 12;
+            let expected = crate::jenkins::hashlittle(&input[..len], LOOKUP3_SEED);
+            if (1..=8).contains(&tail) {
+                assert_eq!(hash_any(&input[..len]), expected, "{}", len);
+            } else if len != 0 {
+                assert_ne!(hash_any(&input[..len]), expected, "{}", len);
+            }
+        }
+    }
+
+    #[test]
+    fn extended_test() {
+        let input: Vec<u8> = (0..64u32).map(|i| (i * 7 + 3) as u8).collect();
+        for len in 0..input.len() {
+            let hash = hash_bytes_extended(&input[..len], 0);
+            assert_eq!(hash as u32, hash_any(&input[..len]));
+            assert_ne!(hash_bytes_extended(&input[..len], PARTITION_SEED), hash);
+        }
+    }
+
+    #[test]
+    fn hash_uint32_test() {
+        for &k in [0, 1, 42, 0xdeadbeef, u32::MAX].iter() {
+            assert_eq!(hash_uint32(k), hash_any(k.to_le_bytes()));
+            assert_eq!(
+                hash_uint32_extended(k, 0),
+                hash_bytes_extended(k.to_le_bytes(), 0)
+            );
+            assert_eq!(
+                hash_uint32_extended(k, 77),
+                hash_bytes_extended(k.to_le_bytes(), 77)
+            );
+        }
+    }
+}
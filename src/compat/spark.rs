@@ -0,0 +1,131 @@
+//! Spark's Murmur3 hashing, as used by the SQL `hash()` function and to assign rows to buckets.
+//!
+//! Spark hashes the columns of a row in order, using the hash of the previous column as the seed
+//! for the next one, starting with `SEED`. Null values leave the hash unchanged, and the elements of
+//! arrays are hashed like consecutive columns.
+//!
+//! ```
+//! use highhash::compat::spark;
+//!
+//! // SELECT hash('Spark', array(123), 2)
+//! let mut hash = spark::SEED;
+//! hash = spark::hash_unsafe_bytes("Spark", hash);
+//! hash = spark::hash_int(123, hash);
+//! hash = spark::hash_int(2, hash);
+//! assert_eq!(hash, -1321691492);
+//!
+//! // the bucket of the row in a table with 8 buckets
+//! assert_eq!(spark::pmod(hash, 8), 4);
+//! ```
+use crate::murmur;
+
+/// Seed of the first column hashed by Spark's `Murmur3Hash` expression.
+pub const SEED: i32 = 42;
+
+/// Spark's `Murmur3_x86_32.hashInt()`, used for `int`, `short`, `byte`, `boolean` (1 or 0) and `date` values.
+pub fn hash_int(input: i32, seed: i32) -> i32 {
+    murmur::hash32_with_seed(input.to_le_bytes(), seed as u32) as i32
+}
+
+/// Spark's `Murmur3_x86_32.hashLong()`, used for `bigint` and `timestamp` values.
+pub fn hash_long(input: i64, seed: i32) -> i32 {
+    murmur::hash32_with_seed(input.to_le_bytes(), seed as u32) as i32
+}
+
+/// The hash of a `float` value: `hashInt()` of its bits, where -0.0 is hashed like 0.0 and all NaNs
+/// are hashed like Java's canonical NaN.
+pub fn hash_float(input: f32, seed: i32) -> i32 {
+    hash_int(float_to_int_bits(input), seed)
+}
+
+/// The hash of a `double` value: `hashLong()` of its bits, where -0.0 is hashed like 0.0 and all NaNs
+/// are hashed like Java's canonical NaN.
+pub fn hash_double(input: f64, seed: i32) -> i32 {
+    hash_long(double_to_long_bits(input), seed)
+}
+
+/// Spark's `Murmur3_x86_32.hashUnsafeBytes()`, used for `string` (UTF-8) and `binary` values.
+///
+/// Unlike the reference MurmurHash3, every byte after the last full 4-byte block is sign-extended and
+/// mixed in on its own, so the result differs from `murmur::hash32_with_seed()` unless the length of
+/// the input is a multiple of 4.
+pub fn hash_unsafe_bytes<T: AsRef<[u8]>>(v: T, seed: i32) -> i32 {
+    murmur::hash32_bytewise_tail_with_seed(v.as_ref(), seed as u32) as i32
+}
+
+/// Spark's `pmod()`: the non-negative remainder of the hash, which is the bucket or partition of a row.
+///
+/// # Panics
+///
+/// Panics if `num_buckets` is 0.
+pub fn pmod(hash: i32, num_buckets: u32) -> u32 {
+    assert!(num_buckets > 0, "there is at least one bucket");
+    (hash as i64).rem_euclid(num_buckets as i64) as u32
+}
+
+fn float_to_int_bits(input: f32) -> i32 {
+    if input.is_nan() {
+        0x7fc00000
+    } else if input == 0.0 {
+        0
+    } else {
+        input.to_bits() as i32
+    }
+}
+
+fn double_to_long_bits(input: f64) -> i64 {
+    if input.is_nan() {
+        0x7ff8000000000000
+    } else if input == 0.0 {
+        0
+    } else {
+        input.to_bits() as i64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn row_hash_test() {
+        use crate::compat::spark::{hash_int, hash_unsafe_bytes, SEED};
+        // SELECT hash('Spark', array(123), 2), from the Spark SQL function documentation
+        let hash = hash_int(2, hash_int(123, hash_unsafe_bytes("Spark", SEED)));
+        assert_eq!(hash, -1321691492);
+    }
+
+    #[test]
+    fn hash_unsafe_bytes_test() {
+        use crate::compat::spark::hash_unsafe_bytes;
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        for len in (0..input.len()).step_by(4) {
+            assert_eq!(
+                hash_unsafe_bytes(&input[..len], 42),
+                crate::murmur::hash32_with_seed(&input[..len], 42) as i32
+            );
+        }
+        assert_ne!(
+            hash_unsafe_bytes("Spark", 42),
+            crate::murmur::hash32_with_seed("Spark", 42) as i32
+        );
+    }
+
+    #[test]
+    fn floating_point_test() {
+        use crate::compat::spark::{hash_double, hash_float, hash_int, hash_long};
+        assert_eq!(hash_float(-0.0, 42), hash_int(0, 42));
+        assert_eq!(hash_float(1.5, 42), hash_int(0x3fc00000, 42));
+        assert_eq!(hash_float(-f32::NAN, 42), hash_float(f32::NAN, 42));
+        assert_eq!(hash_double(-0.0, 42), hash_long(0, 42));
+        assert_eq!(hash_double(1.5, 42), hash_long(0x3ff8000000000000, 42));
+        assert_eq!(hash_double(-f64::NAN, 42), hash_double(f64::NAN, 42));
+    }
+
+    #[test]
+    fn pmod_test() {
+        use crate::compat::spark::pmod;
+        assert_eq!(pmod(7, 4), 3);
+        assert_eq!(pmod(-7, 4), 1);
+        assert_eq!(pmod(i32::MIN, 3), 1);
+        assert_eq!(pmod(i32::MIN, u32::MAX), 2147483647);
+    }
+}
@@ -0,0 +1,420 @@
+//! `farmhashcc`: the 32-bit and 128-bit variants equivalent to CityHash v1.1.1.
+//!
+//! The 128-bit hash is the basis of `Fingerprint128`.
+use super::mk::{self, combine, finalize, scramble, C1};
+use super::na::{fetch64, hash_len_0_to_16, K0, K1};
+use crate::city::{hash_len_16, shift_mix, weak_hash_len_32_with_seeds};
+
+// The code below is adapted from C++ code with the following license
+//-----------------------------------------------------------------------------
+// Copyright (c) 2014 Google, Inc.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+/// `farmhashcc` 32-bit hashing algorithm.
+/// This version allows you to specify a seed, and is only partly equivalent to CityHash32.
+pub fn hash32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
+    let data = v.as_ref();
+    if data.len() <= 24 {
+        return mk::hash32_with_seed(data, seed);
+    }
+    let h = mk::hash32_len_13_to_24(&data[..24], seed ^ data.len() as u32);
+    mk::mur(hash32(&data[24..]).wrapping_add(seed), h)
+}
+
+/// `farmhashcc` 32-bit hashing algorithm.
+/// This version has no seed.
+pub fn hash32<T: AsRef<[u8]>>(v: T) -> u32 {
+    let data = v.as_ref();
+    let len = data.len();
+    if len <= 24 {
+        if len <= 12 {
+            if len <= 4 {
+                return mk::hash32_len_0_to_4(data, 0);
+            }
+            return mk::hash32_len_5_to_12(data, 0);
+        }
+        return hash32_len_13_to_24(data);
+    }
+
+    // len > 24
+    let mut h = len as u32;
+    let mut g = C1.wrapping_mul(len as u32);
+    let mut f = g;
+    let a0 = scramble(fetch32(data, len - 4));
+    let a1 = scramble(fetch32(data, len - 8));
+    let a2 = scramble(fetch32(data, len - 16));
+    let a3 = scramble(fetch32(data, len - 12));
+    let a4 = scramble(fetch32(data, len - 20));
+    h = combine(h ^ a0);
+    h = combine(h ^ a2);
+    g = combine(g ^ a1);
+    g = combine(g ^ a3);
+    f = combine(f.wrapping_add(a4));
+    for i in 0..(len - 1) / 20 {
+        let s = i * 20;
+        let a0 = scramble(fetch32(data, s));
+        let a1 = fetch32(data, s + 4);
+        let a2 = scramble(fetch32(data, s + 8));
+        let a3 = scramble(fetch32(data, s + 12));
+        let a4 = fetch32(data, s + 16);
+        h ^= a0;
+        h = h.rotate_right(18).wrapping_mul(5).wrapping_add(0xe6546b64);
+        f = f.wrapping_add(a1).rotate_right(19).wrapping_mul(C1);
+        g = g
+            .wrapping_add(a2)
+            .rotate_right(18)
+            .wrapping_mul(5)
+            .wrapping_add(0xe6546b64);
+        h = combine(h ^ a3.wrapping_add(a1));
+        g = (g ^ a4).swap_bytes().wrapping_mul(5);
+        h = h.wrapping_add(a4.wrapping_mul(5)).swap_bytes();
+        f = f.wrapping_add(a0);
+        // PERMUTE3(f, h, g)
+        std::mem::swap(&mut f, &mut h);
+        std::mem::swap(&mut f, &mut g);
+    }
+    finalize(h, g, f)
+}
+
+/// `farmhashcc` 128-bit hashing algorithm, equivalent to CityHash128WithSeed v1.1.1.
+/// The lower 64 bits of the seed and of the result are the first half of the C++ `uint128_t`.
+pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u128) -> u128 {
+    let data = v.as_ref();
+    if data.len() < 128 {
+        return city_murmur(data, seed);
+    }
+
+    // Keep 56 bytes of state: v, w, x, y, and z.
+    let mut x = seed as u64;
+    let mut y = (seed >> 64) as u64;
+    let mut z = (data.len() as u64).wrapping_mul(K1);
+    let mut v = (0, 0);
+    v.0 = (y ^ K1)
+        .rotate_right(49)
+        .wrapping_mul(K1)
+        .wrapping_add(fetch64(data, 0));
+    v.1 =
+        v.0.rotate_right(42)
+            .wrapping_mul(K1)
+            .wrapping_add(fetch64(data, 8));
+    let mut w = (
+        y.wrapping_add(z)
+            .rotate_right(35)
+            .wrapping_mul(K1)
+            .wrapping_add(x),
+        x.wrapping_add(fetch64(data, 88))
+            .rotate_right(53)
+            .wrapping_mul(K1),
+    );
+
+    // This is the same inner loop as CityHash64(), manually unrolled.
+    let mut s = 0; // data index
+    let mut len = data.len();
+    loop {
+        for _ in 0..2 {
+            x = x
+                .wrapping_add(y)
+                .wrapping_add(v.0)
+                .wrapping_add(fetch64(data, s + 8))
+                .rotate_right(37)
+                .wrapping_mul(K1);
+            y = y
+                .wrapping_add(v.1)
+                .wrapping_add(fetch64(data, s + 48))
+                .rotate_right(42)
+                .wrapping_mul(K1);
+            x ^= w.1;
+            y = y.wrapping_add(v.0).wrapping_add(fetch64(data, s + 40));
+            z = z.wrapping_add(w.0).rotate_right(33).wrapping_mul(K1);
+            v = weak_hash_len_32_with_seeds(data, v.1.wrapping_mul(K1), x.wrapping_add(w.0), s);
+            w = weak_hash_len_32_with_seeds(
+                data,
+                z.wrapping_add(w.1),
+                y.wrapping_add(fetch64(data, s + 16)),
+                s + 32,
+            );
+            std::mem::swap(&mut z, &mut x);
+            s += 64;
+        }
+        len -= 128;
+        if len < 128 {
+            break;
+        }
+    }
+    x = x.wrapping_add(v.0.wrapping_add(z).rotate_right(49).wrapping_mul(K0));
+    y = y.wrapping_mul(K0).wrapping_add(w.1.rotate_right(37));
+    z = z.wrapping_mul(K0).wrapping_add(w.0.rotate_right(27));
+    w.0 = w.0.wrapping_mul(9);
+    v.0 = v.0.wrapping_mul(K0);
+    // If 0 < len < 128, hash up to 4 chunks of 32 bytes each from the end of the input.
+    let mut tail_done = 0;
+    while tail_done < len {
+        tail_done += 32;
+        let t = s + len - tail_done;
+        y = x
+            .wrapping_add(y)
+            .rotate_right(42)
+            .wrapping_mul(K0)
+            .wrapping_add(v.1);
+        w.0 = w.0.wrapping_add(fetch64(data, t + 16));
+        x = x.wrapping_mul(K0).wrapping_add(w.0);
+        z = z.wrapping_add(w.1).wrapping_add(fetch64(data, t));
+        w.1 = w.1.wrapping_add(v.0);
+        v = weak_hash_len_32_with_seeds(data, v.0.wrapping_add(z), v.1, t);
+        v.0 = v.0.wrapping_mul(K0);
+    }
+    // At this point our 56 bytes of state should contain more than enough information
+    // for a strong 128-bit hash. We use two different 56-byte-to-8-byte hashes to get
+    // a 16-byte final result.
+    x = hash_len_16(x, v.0);
+    y = hash_len_16(y.wrapping_add(z), w.0);
+    to_u128(
+        hash_len_16(x.wrapping_add(v.1), w.1).wrapping_add(y),
+        hash_len_16(x.wrapping_add(w.1), y.wrapping_add(v.1)),
+    )
+}
+
+/// `farmhashcc` 128-bit hashing algorithm, equivalent to CityHash128 v1.1.1.
+/// This version generates a seed from the start of the data.
+pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
+    let data = v.as_ref();
+    if data.len() >= 16 {
+        let seed = to_u128(fetch64(data, 0), fetch64(data, 8).wrapping_add(K0));
+        hash128_with_seed(&data[16..], seed)
+    } else {
+        hash128_with_seed(data, to_u128(K0, K1))
+    }
+}
+
+#[inline(always)]
+fn to_u128(low: u64, high: u64) -> u128 {
+    (high as u128) << 64 | (low as u128)
+}
+
+// A subroutine for hash128_with_seed(). Returns a decent 128-bit hash for inputs
+// shorter than 128 bytes. Based on City and Murmur.
+#[inline(always)]
+fn city_murmur(data: &[u8], seed: u128) -> u128 {
+    let len = data.len();
+    let mut a = seed as u64;
+    let mut b = (seed >> 64) as u64;
+    let mut c;
+    let mut d;
+    if len <= 16 {
+        a = shift_mix(a.wrapping_mul(K1)).wrapping_mul(K1);
+        c = b.wrapping_mul(K1).wrapping_add(hash_len_0_to_16(data));
+        d = shift_mix(a.wrapping_add(if len >= 8 { fetch64(data, 0) } else { c }));
+    } else {
+        c = hash_len_16(fetch64(data, len - 8).wrapping_add(K1), a);
+        d = hash_len_16(
+            b.wrapping_add(len as u64),
+            c.wrapping_add(fetch64(data, len - 16)),
+        );
+        a = a.wrapping_add(d);
+        let mut s = 0; // data index
+        loop {
+            a ^= shift_mix(fetch64(data, s).wrapping_mul(K1)).wrapping_mul(K1);
+            a = a.wrapping_mul(K1);
+            b ^= a;
+            c ^= shift_mix(fetch64(data, s + 8).wrapping_mul(K1)).wrapping_mul(K1);
+            c = c.wrapping_mul(K1);
+            d ^= c;
+            s += 16;
+            if s + 16 >= len {
+                break;
+            }
+        }
+    }
+    a = hash_len_16(a, c);
+    b = hash_len_16(d, b);
+    to_u128(a ^ b, hash_len_16(b, a))
+}
+
+#[inline(always)]
+fn hash32_len_13_to_24(data: &[u8]) -> u32 {
+    let len = data.len();
+    let a = fetch32(data, (len >> 1) - 4);
+    let b = fetch32(data, 4);
+    let c = fetch32(data, len - 8);
+    let d = fetch32(data, len >> 1);
+    let e = fetch32(data, 0);
+    let f = fetch32(data, len - 4);
+    let h = len as u32;
+    let mur = mk::mur;
+    mk::fmix(mur(f, mur(e, mur(d, mur(c, mur(b, mur(a, h)))))))
+}
+
+#[inline(always)]
+fn fetch32(data: &[u8], i: usize) -> u32 {
+    let buf = [data[i], data[i + 1], data[i + 2], data[i + 3]];
+    u32::from_le_bytes(buf)
+}
+
+#[cfg(test)]
+mod test {
+    fn input() -> Vec<u8> {
+        (0..1024u32)
+            .map(|i| (i.wrapping_mul(2654435761) >> 13) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn compliance_test() {
+        let input = input();
+        // (length, no seed, seeded with 0x9747b28c)
+        let expected = [
+            (0, 0xdc56d17a, 0xa0d32d6a),
+            (3, 0x572a068e, 0xec7e667d),
+            (4, 0x83785b97, 0x5e4780cc),
+            (8, 0xeff3aa27, 0xb470cebe),
+            (9, 0xbefabdc8, 0xc8d11fc3),
+            (16, 0xcc5ffb42, 0xf021aa0e),
+            (17, 0x984fa0f7, 0x4218d6a7),
+            (24, 0x2f55746a, 0xd3910f11),
+            (25, 0x0adefbfa, 0xf62b3eea),
+            (32, 0xf1ce1ec0, 0x15b0f2ff),
+            (33, 0x44b03b22, 0x4a84f334),
+            (64, 0x30c0edcd, 0x45faff23),
+            (65, 0xfc45cf9e, 0x77fbbf0a),
+            (96, 0x7e6c82b9, 0x0c060b0d),
+            (97, 0xd6b79545, 0xb28b48aa),
+            (128, 0x82f2b6ed, 0x7187d6f3),
+            (256, 0x9da53d40, 0xb18f8eef),
+            (257, 0x3386c609, 0xf7ce64d4),
+            (1000, 0xc38612b9, 0x7f166633),
+        ];
+        for &(len, unseeded, seeded) in expected.iter() {
+            let data = &input[..len];
+            assert_eq!(super::hash32(data), unseeded);
+            assert_eq!(super::hash32_with_seed(data, 0x9747b28c), seeded);
+        }
+    }
+
+    #[test]
+    fn compliance_128_test() {
+        let input = input();
+        // (length, no seed, seeded with 0xfedcba9876543210_0123456789abcdef)
+        let expected = [
+            (
+                0,
+                0x3cb540c392e51e293df09dfc64c09a2b,
+                0x896d1395ac698decc5ee24e9a7f8e832,
+            ),
+            (
+                3,
+                0xf3b86800e6a1d85580cbfad572127215,
+                0xc8b831c40e2201786059a3a972e33f63,
+            ),
+            (
+                4,
+                0xc7751798e730b06dd4f730810730966b,
+                0xd67de83d2f3d89d3e8aa23a24b89090a,
+            ),
+            (
+                8,
+                0x807b92e4de1b68e77c8030355c9fe310,
+                0x46b5687aa1adc829291ff39cf82fd800,
+            ),
+            (
+                9,
+                0x70b1cf8702311155fbfa61d5946f7140,
+                0xa4b0b3841ba9bbcf836a77909e582c0e,
+            ),
+            (
+                16,
+                0x029c02dea9e8ce0ebbdbfd39142840a4,
+                0x590ab298fa046111fbedb1837da8131b,
+            ),
+            (
+                17,
+                0xe891e5dea5e3965f918ee9dcfed807ad,
+                0x4364b37080633e7e904a2d775d6a81ad,
+            ),
+            (
+                24,
+                0x4a954cf0fe4629b46f03491ce89bd571,
+                0x4a8bd94352294a5835307b9fef1793be,
+            ),
+            (
+                25,
+                0x625e53c89f3772b1a3c9e4224dc6de0d,
+                0x14a0d91a69ffdba49a71b871f03ae6da,
+            ),
+            (
+                32,
+                0xa09d386be37d5d6ab0977bd20db2be60,
+                0x92ceb1e9d8ef39a19616e0f04b1eb5d3,
+            ),
+            (
+                33,
+                0x807f36af22eac4e7b8e038349ff1e8f6,
+                0x93690e7fef5bc503720bbcf019bdd890,
+            ),
+            (
+                64,
+                0x9387fe1e2d3a6f4b3a07b1d5acf87d56,
+                0x482d087770033fb7d0bd765cade1b2c0,
+            ),
+            (
+                65,
+                0xce67347c7ea65a353bc0acfdbe099e8d,
+                0x8cc62c3e3799283253c4789f4f59ba13,
+            ),
+            (
+                96,
+                0xd5d9740bd5b4a6807f12c85996f9b967,
+                0xa292f1bcd18d70b39c0778dd433a38ed,
+            ),
+            (
+                97,
+                0xd2a59ebacbb98be51bbce6790408f942,
+                0xa6c574233c04d7ee6416de9372d2e6c8,
+            ),
+            (
+                128,
+                0xe12065aabfb614974248b4ef99abd2a7,
+                0xe39844ed28bfd71af77def07660b620c,
+            ),
+            (
+                256,
+                0xf0acf8f2d05edfbff16e31aca23b4537,
+                0x603a79c71edc6b3b60c4337e4b9054b1,
+            ),
+            (
+                257,
+                0x2b2690b09960a21d628bb8a9980b9fc1,
+                0xb0f03a23994d89f9084da395d9d89c06,
+            ),
+            (
+                1000,
+                0x2e60839ea5151f39da9f03ab626f63b7,
+                0x1e38b8c84759254f472ed96097a14381,
+            ),
+        ];
+        for &(len, unseeded, seeded) in expected.iter() {
+            let data = &input[..len];
+            assert_eq!(super::hash128(data), unseeded);
+            assert_eq!(
+                super::hash128_with_seed(data, 0xfedcba9876543210_0123456789abcdef),
+                seeded
+            );
+        }
+    }
+}
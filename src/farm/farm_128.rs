@@ -0,0 +1,141 @@
+use std::hash::{BuildHasher, Hasher};
+
+use super::cc;
+use crate::{HashAlgorithm, Hasher128};
+
+/// Hasher for Farm hash implementation of the 128-bit hashing algorithm.
+///
+/// The input is buffered until `finish()` is called.
+#[derive(Default)]
+pub struct FarmHasher128 {
+    buffer: Vec<u8>,
+    seed: Option<u128>,
+}
+
+impl FarmHasher128 {
+    /// Create a hasher which uses the given seed, like `hash128_with_seed()`.
+    pub fn with_seed(seed: u128) -> Self {
+        Self {
+            buffer: Vec::new(),
+            seed: Some(seed),
+        }
+    }
+}
+
+impl Hasher for FarmHasher128 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.buffer.extend(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        self.finish128() as u64
+    }
+}
+
+impl Hasher128 for FarmHasher128 {
+    fn finish128(&self) -> u128 {
+        match self.seed {
+            Some(seed) => hash128_with_seed(&self.buffer, seed),
+            None => hash128(&self.buffer),
+        }
+    }
+}
+
+/// Hash builder for Farm hash implementation of the 128-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct FarmHash128 {
+    seed: Option<u128>,
+}
+
+impl FarmHash128 {
+    /// Create a hash builder whose hashers use the given seed, like `hash128_with_seed()`.
+    pub fn with_seed(seed: u128) -> Self {
+        Self { seed: Some(seed) }
+    }
+}
+
+impl BuildHasher for FarmHash128 {
+    type Hasher = FarmHasher128;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        match self.seed {
+            Some(seed) => Self::Hasher::with_seed(seed),
+            None => Self::Hasher::default(),
+        }
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for Farm hash implementation of the 128-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct FarmRandomState128(FarmHash128::with_seed(seed: u128));
+}
+
+/// Farm hash implementation of the 128-bit hashing algorithm.
+pub struct Farm128;
+
+impl HashAlgorithm for Farm128 {
+    const NAME: &'static str = "farm128";
+    type Seed = u128;
+    type Output = u128;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hash128(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hash128_with_seed(v, seed)
+    }
+}
+
+/// Farm hash implementation of the 128-bit hashing algorithm.
+/// This version allows you to specify a seed.
+///
+/// This is the `farmhashcc` variant.
+pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u128) -> u128 {
+    cc::hash128_with_seed(v, seed)
+}
+
+/// Farm hash implementation of the 128-bit hashing algorithm.
+/// This version generates a seed from the start of the data.
+///
+/// This is the `farmhashcc` variant.
+pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
+    cc::hash128(v)
+}
+
+/// Farm 128-bit fingerprint, which never changes across platforms and versions.
+pub fn fingerprint128<T: AsRef<[u8]>>(v: T) -> u128 {
+    cc::hash128(v)
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn compliance_test() {
+        assert_eq!(
+            crate::farm::fingerprint128("hello word"),
+            73675844590621301084713386800078304440
+        );
+    }
+
+    #[test]
+    fn seeded_builder_test() {
+        use crate::Hasher128;
+        use std::hash::{BuildHasher, Hasher};
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let mut hasher = crate::farm::FarmHash128::with_seed(3).build_hasher();
+        hasher.write(input.as_bytes());
+        assert_eq!(hasher.finish128(), crate::farm::hash128_with_seed(input, 3));
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn fasthash_interop_test() {
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        assert_eq!(
+            crate::farm::fingerprint128(input),
+            fasthash::farm::fingerprint128(input)
+        );
+    }
+}
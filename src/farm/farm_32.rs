@@ -0,0 +1,143 @@
+use std::hash::{BuildHasher, Hasher};
+
+use super::mk;
+use crate::HashAlgorithm;
+
+/// Hasher for Farm hash implementation of the 32-bit hashing algorithm.
+///
+/// The input is buffered until `finish()` is called.
+#[derive(Default)]
+pub struct FarmHasher32 {
+    buffer: Vec<u8>,
+    seed: Option<u32>,
+}
+
+impl FarmHasher32 {
+    /// Create a hasher which uses the given seed, like `hash32_with_seed()`.
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            buffer: Vec::new(),
+            seed: Some(seed),
+        }
+    }
+}
+
+impl Hasher for FarmHasher32 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.buffer.extend(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        match self.seed {
+            Some(seed) => hash32_with_seed(&self.buffer, seed) as u64,
+            None => hash32(&self.buffer) as u64,
+        }
+    }
+}
+
+/// Hash builder for Farm hash implementation of the 32-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct FarmHash32 {
+    seed: Option<u32>,
+}
+
+impl FarmHash32 {
+    /// Create a hash builder whose hashers use the given seed, like `hash32_with_seed()`.
+    pub fn with_seed(seed: u32) -> Self {
+        Self { seed: Some(seed) }
+    }
+}
+
+impl BuildHasher for FarmHash32 {
+    type Hasher = FarmHasher32;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        match self.seed {
+            Some(seed) => Self::Hasher::with_seed(seed),
+            None => Self::Hasher::default(),
+        }
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for Farm hash implementation of the 32-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct FarmRandomState32(FarmHash32::with_seed(seed: u32));
+}
+
+/// Farm hash implementation of the 32-bit hashing algorithm.
+pub struct Farm32;
+
+impl HashAlgorithm for Farm32 {
+    const NAME: &'static str = "farm32";
+    type Seed = u32;
+    type Output = u32;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hash32(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hash32_with_seed(v, seed)
+    }
+}
+
+/// Farm hash implementation of the 32-bit hashing algorithm.
+/// This version allows you to specify a seed.
+///
+/// This is the portable `farmhashmk` variant, which the reference implementation uses when
+/// no SSE4 instructions are available.
+pub fn hash32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
+    mk::hash32_with_seed(v, seed)
+}
+
+/// Farm hash implementation of the 32-bit hashing algorithm.
+/// This version has no seed.
+///
+/// This is the portable `farmhashmk` variant, which the reference implementation uses when
+/// no SSE4 instructions are available.
+pub fn hash32<T: AsRef<[u8]>>(v: T) -> u32 {
+    mk::hash32(v)
+}
+
+/// Farm 32-bit fingerprint, which never changes across platforms and versions.
+pub fn fingerprint32<T: AsRef<[u8]>>(v: T) -> u32 {
+    mk::hash32(v)
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn compliance_test() {
+        assert_eq!(crate::farm::fingerprint32("hello word"), 4146030890);
+        assert_eq!(
+            crate::farm::hash32("hello word"),
+            crate::farm::fingerprint32("hello word")
+        );
+    }
+
+    #[test]
+    fn seeded_builder_test() {
+        use std::hash::{BuildHasher, Hasher};
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let mut hasher = crate::farm::FarmHash32::default().build_hasher();
+        hasher.write(input.as_bytes());
+        assert_eq!(hasher.finish(), crate::farm::hash32(input) as u64);
+        let mut hasher = crate::farm::FarmHash32::with_seed(3).build_hasher();
+        hasher.write(input.as_bytes());
+        assert_eq!(
+            hasher.finish(),
+            crate::farm::hash32_with_seed(input, 3) as u64
+        );
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn fasthash_interop_test() {
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        assert_eq!(
+            crate::farm::fingerprint32(input),
+            fasthash::farm::fingerprint32(input)
+        );
+    }
+}
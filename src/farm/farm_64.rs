@@ -0,0 +1,171 @@
+use std::hash::{BuildHasher, Hasher};
+
+use super::{na, xo};
+use crate::HashAlgorithm;
+
+/// Hasher for Farm hash implementation of the 64-bit hashing algorithm.
+///
+/// The input is buffered until `finish()` is called.
+#[derive(Default)]
+pub struct FarmHasher64 {
+    buffer: Vec<u8>,
+    seeds: Option<(u64, u64)>,
+}
+
+impl FarmHasher64 {
+    /// Create a hasher which uses the given seed, like `hash64_with_seed()`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_seeds(na::K2, seed)
+    }
+
+    /// Create a hasher which uses the given two seeds, like `hash64_with_seeds()`.
+    pub fn with_seeds(seed0: u64, seed1: u64) -> Self {
+        Self {
+            buffer: Vec::new(),
+            seeds: Some((seed0, seed1)),
+        }
+    }
+}
+
+impl Hasher for FarmHasher64 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.buffer.extend(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        match self.seeds {
+            Some((seed0, seed1)) => hash64_with_seeds(&self.buffer, seed0, seed1),
+            None => hash64(&self.buffer),
+        }
+    }
+}
+
+/// Hash builder for Farm hash implementation of the 64-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct FarmHash64 {
+    seeds: Option<(u64, u64)>,
+}
+
+impl FarmHash64 {
+    /// Create a hash builder whose hashers use the given seed, like `hash64_with_seed()`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_seeds(na::K2, seed)
+    }
+
+    /// Create a hash builder whose hashers use the given two seeds, like `hash64_with_seeds()`.
+    pub fn with_seeds(seed0: u64, seed1: u64) -> Self {
+        Self {
+            seeds: Some((seed0, seed1)),
+        }
+    }
+}
+
+impl BuildHasher for FarmHash64 {
+    type Hasher = FarmHasher64;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        match self.seeds {
+            Some((seed0, seed1)) => Self::Hasher::with_seeds(seed0, seed1),
+            None => Self::Hasher::default(),
+        }
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for Farm hash implementation of the 64-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct FarmRandomState64(FarmHash64::with_seeds(seed0: u64, seed1: u64));
+}
+
+/// Farm hash implementation of the 64-bit hashing algorithm.
+/// The seed is the pair of seeds taken by `hash64_with_seeds()`.
+pub struct Farm64;
+
+impl HashAlgorithm for Farm64 {
+    const NAME: &'static str = "farm64";
+    type Seed = (u64, u64);
+    type Output = u64;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hash64(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hash64_with_seeds(v, seed.0, seed.1)
+    }
+}
+
+/// Farm hash implementation of the 64-bit hashing algorithm.
+/// This version allows you to specify one seed.
+///
+/// Like the reference implementation, this uses the `farmhashna` variant.
+pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
+    na::hash64_with_seed(v, seed)
+}
+
+/// Farm hash implementation of the 64-bit hashing algorithm.
+/// This version allows you to specify two seeds.
+///
+/// Like the reference implementation, this uses the `farmhashna` variant.
+pub fn hash64_with_seeds<T: AsRef<[u8]>>(v: T, seed0: u64, seed1: u64) -> u64 {
+    na::hash64_with_seeds(v, seed0, seed1)
+}
+
+/// Farm hash implementation of the 64-bit hashing algorithm.
+/// This version has no seed.
+///
+/// This is the portable `farmhashxo` variant, which the reference implementation uses when
+/// no SSE4.2 instructions are available.
+pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
+    xo::hash64(v)
+}
+
+/// Farm 64-bit fingerprint, which never changes across platforms and versions.
+pub fn fingerprint64<T: AsRef<[u8]>>(v: T) -> u64 {
+    na::hash64(v)
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn compliance_test() {
+        assert_eq!(
+            crate::farm::fingerprint64("hello word"),
+            2862784602449412590
+        );
+    }
+
+    #[test]
+    fn hash_algorithm_test() {
+        use crate::farm::Farm64;
+        use crate::HashAlgorithm;
+        assert_eq!(Farm64::NAME, "farm64");
+        assert_eq!(Farm64::hash("abc"), crate::farm::xo::hash64("abc"));
+        assert_eq!(
+            Farm64::hash_with_seed("abc", (1, 2)),
+            crate::farm::na::hash64_with_seeds("abc", 1, 2)
+        );
+    }
+
+    #[test]
+    fn seeded_builder_test() {
+        use std::hash::{BuildHasher, Hasher};
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let mut hasher = crate::farm::FarmHash64::with_seeds(1, 2).build_hasher();
+        hasher.write(input.as_bytes());
+        assert_eq!(hasher.finish(), crate::farm::hash64_with_seeds(input, 1, 2));
+        let mut hasher = crate::farm::FarmHash64::with_seed(3).build_hasher();
+        hasher.write(input.as_bytes());
+        assert_eq!(hasher.finish(), crate::farm::hash64_with_seed(input, 3));
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn fasthash_interop_test() {
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        assert_eq!(
+            crate::farm::fingerprint64(input),
+            fasthash::farm::fingerprint64(input)
+        );
+    }
+}
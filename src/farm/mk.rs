@@ -0,0 +1,226 @@
+//! `farmhashmk`: the 32-bit variant behind `Fingerprint32`.
+
+// The code below is adapted from C++ code with the following license
+//-----------------------------------------------------------------------------
+// Copyright (c) 2014 Google, Inc.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+// Magic numbers for 32-bit hashing. Copied from Murmur3.
+pub(super) const C1: u32 = 0xcc9e2d51;
+pub(super) const C2: u32 = 0x1b873593;
+
+/// `farmhashmk` 32-bit hashing algorithm.
+/// This version allows you to specify a seed.
+pub fn hash32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
+    let data = v.as_ref();
+    let len = data.len();
+    if len <= 24 {
+        if len >= 13 {
+            return hash32_len_13_to_24(data, seed.wrapping_mul(C1));
+        } else if len >= 5 {
+            return hash32_len_5_to_12(data, seed);
+        }
+        return hash32_len_0_to_4(data, seed);
+    }
+    let h = hash32_len_13_to_24(&data[..24], seed ^ len as u32);
+    mur(hash32(&data[24..]).wrapping_add(seed), h)
+}
+
+/// `farmhashmk` 32-bit hashing algorithm.
+/// This version has no seed.
+pub fn hash32<T: AsRef<[u8]>>(v: T) -> u32 {
+    let data = v.as_ref();
+    let len = data.len();
+    if len <= 24 {
+        if len <= 12 {
+            if len <= 4 {
+                return hash32_len_0_to_4(data, 0);
+            }
+            return hash32_len_5_to_12(data, 0);
+        }
+        return hash32_len_13_to_24(data, 0);
+    }
+
+    // len > 24
+    let mut h = len as u32;
+    let mut g = C1.wrapping_mul(len as u32);
+    let mut f = g;
+    let a0 = scramble(fetch32(data, len - 4));
+    let a1 = scramble(fetch32(data, len - 8));
+    let a2 = scramble(fetch32(data, len - 16));
+    let a3 = scramble(fetch32(data, len - 12));
+    let a4 = scramble(fetch32(data, len - 20));
+    h = combine(h ^ a0);
+    h = combine(h ^ a2);
+    g = combine(g ^ a1);
+    g = combine(g ^ a3);
+    f = f.wrapping_add(a4).rotate_right(19).wrapping_add(113);
+    for i in 0..(len - 1) / 20 {
+        let s = i * 20;
+        let a = fetch32(data, s);
+        let b = fetch32(data, s + 4);
+        let c = fetch32(data, s + 8);
+        let d = fetch32(data, s + 12);
+        let e = fetch32(data, s + 16);
+        h = h.wrapping_add(a);
+        g = g.wrapping_add(b);
+        f = f.wrapping_add(c);
+        h = mur(d, h).wrapping_add(e);
+        g = mur(c, g).wrapping_add(a);
+        f = mur(b.wrapping_add(e.wrapping_mul(C1)), f).wrapping_add(d);
+        f = f.wrapping_add(g);
+        g = g.wrapping_add(f);
+    }
+    finalize(h, g, f)
+}
+
+// shared by the `farmhashmk` and `farmhashcc` loops
+#[inline(always)]
+pub(super) fn finalize(mut h: u32, mut g: u32, mut f: u32) -> u32 {
+    g = g.rotate_right(11).wrapping_mul(C1);
+    g = g.rotate_right(17).wrapping_mul(C1);
+    f = f.rotate_right(11).wrapping_mul(C1);
+    f = f.rotate_right(17).wrapping_mul(C1);
+    h = combine(h.wrapping_add(g));
+    h = h.rotate_right(17).wrapping_mul(C1);
+    h = combine(h.wrapping_add(f));
+    h.rotate_right(17).wrapping_mul(C1)
+}
+
+#[inline(always)]
+pub(super) fn scramble(a: u32) -> u32 {
+    a.wrapping_mul(C1).rotate_right(17).wrapping_mul(C2)
+}
+
+#[inline(always)]
+pub(super) fn combine(h: u32) -> u32 {
+    h.rotate_right(19).wrapping_mul(5).wrapping_add(0xe6546b64)
+}
+
+// Helper from Murmur3 for combining two 32-bit values.
+#[inline(always)]
+pub(super) fn mur(a: u32, h: u32) -> u32 {
+    combine(h ^ scramble(a))
+}
+
+// A 32-bit to 32-bit integer hash copied from Murmur3.
+#[inline(always)]
+pub(super) fn fmix(mut h: u32) -> u32 {
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x85ebca6b);
+    h ^= h >> 13;
+    h = h.wrapping_mul(0xc2b2ae35);
+    h ^= h >> 16;
+    h
+}
+
+#[inline(always)]
+pub(super) fn hash32_len_13_to_24(data: &[u8], seed: u32) -> u32 {
+    let len = data.len();
+    let mut a = fetch32(data, (len >> 1) - 4);
+    let b = fetch32(data, 4);
+    let c = fetch32(data, len - 8);
+    let d = fetch32(data, len >> 1);
+    let e = fetch32(data, 0);
+    let f = fetch32(data, len - 4);
+    let mut h = d
+        .wrapping_mul(C1)
+        .wrapping_add(len as u32)
+        .wrapping_add(seed);
+    a = a.rotate_right(12).wrapping_add(f);
+    h = mur(c, h).wrapping_add(a);
+    a = a.rotate_right(3).wrapping_add(c);
+    h = mur(e, h).wrapping_add(a);
+    a = a.wrapping_add(f).rotate_right(12).wrapping_add(d);
+    h = mur(b ^ seed, h).wrapping_add(a);
+    fmix(h)
+}
+
+#[inline(always)]
+pub(super) fn hash32_len_0_to_4(data: &[u8], seed: u32) -> u32 {
+    let mut b = seed;
+    let mut c: u32 = 9;
+    for &byte in data {
+        // bytes are signed in the reference implementation
+        b = b.wrapping_mul(C1).wrapping_add(byte as i8 as u32);
+        c ^= b;
+    }
+    fmix(mur(b, mur(data.len() as u32, c)))
+}
+
+#[inline(always)]
+pub(super) fn hash32_len_5_to_12(data: &[u8], seed: u32) -> u32 {
+    let len = data.len();
+    let mut a = len as u32;
+    let mut b = len as u32 * 5;
+    let mut c: u32 = 9;
+    let d = b.wrapping_add(seed);
+    a = a.wrapping_add(fetch32(data, 0));
+    b = b.wrapping_add(fetch32(data, len - 4));
+    c = c.wrapping_add(fetch32(data, (len >> 1) & 4));
+    fmix(seed ^ mur(c, mur(b, mur(a, d))))
+}
+
+#[inline(always)]
+fn fetch32(data: &[u8], i: usize) -> u32 {
+    let buf = [data[i], data[i + 1], data[i + 2], data[i + 3]];
+    u32::from_le_bytes(buf)
+}
+
+#[cfg(test)]
+mod test {
+    fn input() -> Vec<u8> {
+        (0..1024u32)
+            .map(|i| (i.wrapping_mul(2654435761) >> 13) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn compliance_test() {
+        let input = input();
+        // (length, no seed, seeded with 0x9747b28c)
+        let expected = [
+            (0, 0xdc56d17a, 0xa0d32d6a),
+            (3, 0x572a068e, 0xec7e667d),
+            (4, 0x83785b97, 0x5e4780cc),
+            (8, 0xeff3aa27, 0xb470cebe),
+            (9, 0xbefabdc8, 0xc8d11fc3),
+            (16, 0x5c0cc6dc, 0xf021aa0e),
+            (17, 0x50d6c592, 0x4218d6a7),
+            (24, 0x303c25ae, 0xd3910f11),
+            (25, 0xd0690138, 0xf62b3eea),
+            (32, 0x0159cc12, 0x15b0f2ff),
+            (33, 0x455a10cd, 0x4a84f334),
+            (64, 0xc112f14c, 0xdbc3ceda),
+            (65, 0xb6d2dca3, 0x3b27766c),
+            (96, 0x9dd631b7, 0xe9b36c94),
+            (97, 0x5cc63cec, 0xa9ea77e0),
+            (128, 0x8ccebfcf, 0xd3ea8feb),
+            (256, 0x5ad3ffbe, 0x38841144),
+            (257, 0x30b1c7f0, 0x56363c01),
+            (1000, 0xcdb56f41, 0x75b6eac6),
+        ];
+        for &(len, unseeded, seeded) in expected.iter() {
+            let data = &input[..len];
+            assert_eq!(super::hash32(data), unseeded);
+            assert_eq!(super::hash32_with_seed(data, 0x9747b28c), seeded);
+        }
+    }
+}
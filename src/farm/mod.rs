@@ -0,0 +1,26 @@
+//! Farm string-hashing algorithm by Geoff Pike, the successor of City hash.
+//!
+//! The top-level functions pick the portable variants the reference implementation falls back to
+//! without SSE4, so they give the same results on every platform.
+//! The `fingerprint*()` functions are guaranteed to never change.
+//! Each variant is also available on its own, in the module named after its C++ namespace.
+pub mod cc;
+mod farm_128;
+mod farm_32;
+mod farm_64;
+pub mod mk;
+pub mod na;
+pub mod uo;
+pub mod xo;
+
+pub use farm_128::{
+    fingerprint128, hash128, hash128_with_seed, Farm128, FarmHash128, FarmHasher128,
+    FarmRandomState128,
+};
+pub use farm_32::{
+    fingerprint32, hash32, hash32_with_seed, Farm32, FarmHash32, FarmHasher32, FarmRandomState32,
+};
+pub use farm_64::{
+    fingerprint64, hash64, hash64_with_seed, hash64_with_seeds, Farm64, FarmHash64, FarmHasher64,
+    FarmRandomState64,
+};
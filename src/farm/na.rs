@@ -0,0 +1,393 @@
+//! `farmhashna`: the 64-bit variant behind `Fingerprint64`.
+//!
+//! Inputs of up to 64 bytes are also hashed this way by `farmhashuo` and `farmhashxo`.
+use crate::city::{hash_len_16, shift_mix, weak_hash_len_32_with_seeds};
+
+// The code below is adapted from C++ code with the following license
+//-----------------------------------------------------------------------------
+// Copyright (c) 2014 Google, Inc.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+// Some primes between 2^63 and 2^64 for various uses.
+pub(super) const K0: u64 = 0xc3a5c85c97cb3127;
+pub(super) const K1: u64 = 0xb492b66fbe98f273;
+pub(super) const K2: u64 = 0x9ae16a3b2f90404f;
+
+/// `farmhashna` 64-bit hashing algorithm.
+/// This version allows you to specify one seed.
+pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
+    hash64_with_seeds(v, K2, seed)
+}
+
+/// `farmhashna` 64-bit hashing algorithm.
+/// This version allows you to specify two seeds.
+pub fn hash64_with_seeds<T: AsRef<[u8]>>(v: T, seed0: u64, seed1: u64) -> u64 {
+    hash_len_16(hash64(v).wrapping_sub(seed0), seed1)
+}
+
+/// `farmhashna` 64-bit hashing algorithm.
+/// This version has no seed.
+pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
+    let data = v.as_ref();
+    let len = data.len();
+    if len <= 32 {
+        if len <= 16 {
+            return hash_len_0_to_16(data);
+        }
+        return hash_len_17_to_32(data);
+    } else if len <= 64 {
+        return hash_len_33_to_64(data);
+    }
+
+    // For inputs over 64 bytes we loop.
+    // Internal state consists of 56 bytes: v, w, x, y, and z.
+    let seed: u64 = 81;
+    let mut x = seed;
+    let mut y = seed.wrapping_mul(K1).wrapping_add(113);
+    let mut z = shift_mix(y.wrapping_mul(K2).wrapping_add(113)).wrapping_mul(K2);
+    let mut v = (0, 0);
+    let mut w = (0, 0);
+    x = x.wrapping_mul(K2).wrapping_add(fetch64(data, 0));
+
+    // Set end so that after the loop we have 1 to 64 bytes left to process.
+    let end = ((len - 1) / 64) * 64;
+    let mut s = 0; // data index
+    loop {
+        x = x
+            .wrapping_add(y)
+            .wrapping_add(v.0)
+            .wrapping_add(fetch64(data, s + 8))
+            .rotate_right(37)
+            .wrapping_mul(K1);
+        y = y
+            .wrapping_add(v.1)
+            .wrapping_add(fetch64(data, s + 48))
+            .rotate_right(42)
+            .wrapping_mul(K1);
+        x ^= w.1;
+        y = y.wrapping_add(v.0).wrapping_add(fetch64(data, s + 40));
+        z = z.wrapping_add(w.0).rotate_right(33).wrapping_mul(K1);
+        v = weak_hash_len_32_with_seeds(data, v.1.wrapping_mul(K1), x.wrapping_add(w.0), s);
+        w = weak_hash_len_32_with_seeds(
+            data,
+            z.wrapping_add(w.1),
+            y.wrapping_add(fetch64(data, s + 16)),
+            s + 32,
+        );
+        std::mem::swap(&mut z, &mut x);
+        s += 64;
+        if s == end {
+            break;
+        }
+    }
+    let mul = K1.wrapping_add((z & 0xff) << 1);
+    // process the last 64 bytes of the input
+    s = len - 64;
+    w.0 = w.0.wrapping_add(((len - 1) & 63) as u64);
+    v.0 = v.0.wrapping_add(w.0);
+    w.0 = w.0.wrapping_add(v.0);
+    x = x
+        .wrapping_add(y)
+        .wrapping_add(v.0)
+        .wrapping_add(fetch64(data, s + 8))
+        .rotate_right(37)
+        .wrapping_mul(mul);
+    y = y
+        .wrapping_add(v.1)
+        .wrapping_add(fetch64(data, s + 48))
+        .rotate_right(42)
+        .wrapping_mul(mul);
+    x ^= w.1.wrapping_mul(9);
+    y = y
+        .wrapping_add(v.0.wrapping_mul(9))
+        .wrapping_add(fetch64(data, s + 40));
+    z = z.wrapping_add(w.0).rotate_right(33).wrapping_mul(mul);
+    v = weak_hash_len_32_with_seeds(data, v.1.wrapping_mul(mul), x.wrapping_add(w.0), s);
+    w = weak_hash_len_32_with_seeds(
+        data,
+        z.wrapping_add(w.1),
+        y.wrapping_add(fetch64(data, s + 16)),
+        s + 32,
+    );
+    std::mem::swap(&mut z, &mut x);
+    hash_len_16_mul(
+        hash_len_16_mul(v.0, w.0, mul)
+            .wrapping_add(shift_mix(y).wrapping_mul(K0))
+            .wrapping_add(z),
+        hash_len_16_mul(v.1, w.1, mul).wrapping_add(x),
+        mul,
+    )
+}
+
+// Murmur-inspired hashing.
+#[inline(always)]
+pub(super) fn hash_len_16_mul(u: u64, v: u64, mul: u64) -> u64 {
+    let mut a = (u ^ v).wrapping_mul(mul);
+    a ^= a >> 47;
+    let mut b = (v ^ a).wrapping_mul(mul);
+    b ^= b >> 47;
+    b.wrapping_mul(mul)
+}
+
+#[inline(always)]
+pub(super) fn hash_len_0_to_16(data: &[u8]) -> u64 {
+    let len = data.len();
+    if len >= 8 {
+        let mul = K2.wrapping_add(len as u64 * 2);
+        let a = fetch64(data, 0).wrapping_add(K2);
+        let b = fetch64(data, len - 8);
+        let c = b.rotate_right(37).wrapping_mul(mul).wrapping_add(a);
+        let d = a.rotate_right(25).wrapping_add(b).wrapping_mul(mul);
+        return hash_len_16_mul(c, d, mul);
+    }
+    if len >= 4 {
+        let mul = K2.wrapping_add(len as u64 * 2);
+        let a = fetch32(data, 0) as u64;
+        return hash_len_16_mul(
+            (len as u64).wrapping_add(a << 3),
+            fetch32(data, len - 4) as u64,
+            mul,
+        );
+    }
+    if len > 0 {
+        let a = data[0];
+        let b = data[len >> 1];
+        let c = data[len - 1];
+        let y = (a as u32).wrapping_add((b as u32) << 8);
+        let z = (len as u32).wrapping_add((c as u32) << 2);
+        return shift_mix((y as u64).wrapping_mul(K2) ^ (z as u64).wrapping_mul(K0))
+            .wrapping_mul(K2);
+    }
+    K2
+}
+
+#[inline(always)]
+pub(super) fn hash_len_17_to_32(data: &[u8]) -> u64 {
+    let len = data.len();
+    let mul = K2.wrapping_add(len as u64 * 2);
+    let a = fetch64(data, 0).wrapping_mul(K1);
+    let b = fetch64(data, 8);
+    let c = fetch64(data, len - 8).wrapping_mul(mul);
+    let d = fetch64(data, len - 16).wrapping_mul(K2);
+    hash_len_16_mul(
+        a.wrapping_add(b)
+            .rotate_right(43)
+            .wrapping_add(c.rotate_right(30))
+            .wrapping_add(d),
+        a.wrapping_add(b.wrapping_add(K2).rotate_right(18))
+            .wrapping_add(c),
+        mul,
+    )
+}
+
+#[inline(always)]
+fn hash_len_33_to_64(data: &[u8]) -> u64 {
+    let len = data.len();
+    let mul = K2.wrapping_add(len as u64 * 2);
+    let a = fetch64(data, 0).wrapping_mul(K2);
+    let b = fetch64(data, 8);
+    let c = fetch64(data, len - 8).wrapping_mul(mul);
+    let d = fetch64(data, len - 16).wrapping_mul(K2);
+    let y = a
+        .wrapping_add(b)
+        .rotate_right(43)
+        .wrapping_add(c.rotate_right(30))
+        .wrapping_add(d);
+    let z = hash_len_16_mul(
+        y,
+        a.wrapping_add(b.wrapping_add(K2).rotate_right(18))
+            .wrapping_add(c),
+        mul,
+    );
+    let e = fetch64(data, 16).wrapping_mul(mul);
+    let f = fetch64(data, 24);
+    let g = y.wrapping_add(fetch64(data, len - 32)).wrapping_mul(mul);
+    let h = z.wrapping_add(fetch64(data, len - 24)).wrapping_mul(mul);
+    hash_len_16_mul(
+        e.wrapping_add(f)
+            .rotate_right(43)
+            .wrapping_add(g.rotate_right(30))
+            .wrapping_add(h),
+        e.wrapping_add(f.wrapping_add(a).rotate_right(18))
+            .wrapping_add(g),
+        mul,
+    )
+}
+
+#[inline(always)]
+pub(super) fn fetch64(data: &[u8], i: usize) -> u64 {
+    let buf = [
+        data[i],
+        data[i + 1],
+        data[i + 2],
+        data[i + 3],
+        data[i + 4],
+        data[i + 5],
+        data[i + 6],
+        data[i + 7],
+    ];
+    u64::from_le_bytes(buf)
+}
+
+#[inline(always)]
+fn fetch32(data: &[u8], i: usize) -> u32 {
+    let buf = [data[i], data[i + 1], data[i + 2], data[i + 3]];
+    u32::from_le_bytes(buf)
+}
+
+#[cfg(test)]
+mod test {
+    fn input() -> Vec<u8> {
+        (0..1024u32)
+            .map(|i| (i.wrapping_mul(2654435761) >> 13) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn compliance_test() {
+        let input = input();
+        // (length, no seed, seeded with 0x0123456789abcdef, seeded with that and 0xfedcba9876543210)
+        let expected = [
+            (
+                0,
+                0x9ae16a3b2f90404f,
+                0x9f48347ecc763bba,
+                0xcb0b0ef713007cf7,
+            ),
+            (
+                3,
+                0x88cdb7a066b28ed8,
+                0x101917acd8d997e8,
+                0x346adcb0038b6c7f,
+            ),
+            (
+                4,
+                0xd1656aa65fa15bcc,
+                0xe003202d8182d19a,
+                0x3eef822f1bcafdd5,
+            ),
+            (
+                8,
+                0x5c2467c03969bd6a,
+                0x5d38eeccb7151d74,
+                0xc9563666bfa7c565,
+            ),
+            (
+                9,
+                0xef4f9dbe9e445c40,
+                0x63b575ffff63c1dd,
+                0x16fd0fb6216db42b,
+            ),
+            (
+                16,
+                0x7519e05b26735db1,
+                0xbbfbb8bd80e6dcb7,
+                0x019cc5067f8a2da8,
+            ),
+            (
+                17,
+                0x170d6ccd9f1ecfa3,
+                0xfb881e31f9e02b14,
+                0xd2aca05884bf3715,
+            ),
+            (
+                24,
+                0x5443f2220d56ff84,
+                0x085e06a18de39cbe,
+                0x9cdb4fd24a13c005,
+            ),
+            (
+                25,
+                0x4b352cbcf48880c5,
+                0x76544f6acc51ae8d,
+                0x76563eaa8789f3c8,
+            ),
+            (
+                32,
+                0x0f4c1ec69dd9ab73,
+                0x18463828e4dacf21,
+                0xb6c90fd1b1e08a50,
+            ),
+            (
+                33,
+                0x6c651c27c0788ea7,
+                0x6e7bb77b5dec8e88,
+                0x48a0a5167ae7e45b,
+            ),
+            (
+                64,
+                0x111d69e00e5702c7,
+                0x6936a5ce62550ed2,
+                0xd7e0e3b9c603e9d0,
+            ),
+            (
+                65,
+                0x6e17f37ec210a00d,
+                0xaa2b5c51f746786c,
+                0x6ad9f01f0d812abb,
+            ),
+            (
+                96,
+                0x6469c8c5e36b9c70,
+                0xb5427717723b598e,
+                0x98456d990fa0db50,
+            ),
+            (
+                97,
+                0xe5ca29202cb14edd,
+                0xb0227823c2100426,
+                0xfe5ca4d0188dca90,
+            ),
+            (
+                128,
+                0xa70358e3189324c8,
+                0x506d7456b0183850,
+                0xb756c795b75f14d4,
+            ),
+            (
+                256,
+                0x1173f5c793ee7084,
+                0x2139a3358df76f90,
+                0x4760407da6c2a492,
+            ),
+            (
+                257,
+                0x382d06f448a11dcf,
+                0x2243497917d29d93,
+                0x37cf82ffa071b522,
+            ),
+            (
+                1000,
+                0x5faefca08bf954a8,
+                0xd18ccf7da05c4bd2,
+                0x1cebff2007c1981a,
+            ),
+        ];
+        for &(len, unseeded, seeded, seeds) in expected.iter() {
+            let data = &input[..len];
+            assert_eq!(super::hash64(data), unseeded);
+            assert_eq!(super::hash64_with_seed(data, 0x0123456789abcdef), seeded);
+            assert_eq!(
+                super::hash64_with_seeds(data, 0x0123456789abcdef, 0xfedcba9876543210),
+                seeds
+            );
+        }
+    }
+}
@@ -0,0 +1,308 @@
+//! `farmhashuo`: a 64-bit variant with a faster main loop for inputs over 64 bytes.
+use super::na::{self, fetch64, hash_len_16_mul, K2};
+use crate::city::{shift_mix, weak_hash_len_32_with_seeds};
+
+// The code below is adapted from C++ code with the following license
+//-----------------------------------------------------------------------------
+// Copyright (c) 2014 Google, Inc.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+/// `farmhashuo` 64-bit hashing algorithm.
+/// This version allows you to specify one seed.
+pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
+    let data = v.as_ref();
+    if data.len() <= 64 {
+        na::hash64_with_seed(data, seed)
+    } else {
+        hash64_long(data, 0, seed)
+    }
+}
+
+/// `farmhashuo` 64-bit hashing algorithm.
+/// This version allows you to specify two seeds.
+pub fn hash64_with_seeds<T: AsRef<[u8]>>(v: T, seed0: u64, seed1: u64) -> u64 {
+    let data = v.as_ref();
+    if data.len() <= 64 {
+        na::hash64_with_seeds(data, seed0, seed1)
+    } else {
+        hash64_long(data, seed0, seed1)
+    }
+}
+
+/// `farmhashuo` 64-bit hashing algorithm.
+/// This version has no seed.
+pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
+    let data = v.as_ref();
+    if data.len() <= 64 {
+        na::hash64(data)
+    } else {
+        hash64_long(data, 81, 0)
+    }
+}
+
+#[inline(always)]
+fn h(x: u64, y: u64, mul: u64, r: u32) -> u64 {
+    let mut a = (x ^ y).wrapping_mul(mul);
+    a ^= a >> 47;
+    let b = (y ^ a).wrapping_mul(mul);
+    b.rotate_right(r).wrapping_mul(mul)
+}
+
+// hash an input longer than 64 bytes
+fn hash64_long(data: &[u8], seed0: u64, seed1: u64) -> u64 {
+    let len = data.len();
+    // Internal state consists of 64 bytes: u, v, w, x, y, and z.
+    let mut x = seed0;
+    let mut y = seed1.wrapping_mul(K2).wrapping_add(113);
+    let mut z = shift_mix(y.wrapping_mul(K2)).wrapping_mul(K2);
+    let mut v = (seed0, seed1);
+    let mut w: (u64, u64) = (0, 0);
+    let mut u = x.wrapping_sub(z);
+    x = x.wrapping_mul(K2);
+    let mul = K2.wrapping_add(u & 0x82);
+
+    // Set end so that after the loop we have 1 to 64 bytes left to process.
+    let end = ((len - 1) / 64) * 64;
+    let mut s = 0; // data index
+    loop {
+        let a0 = fetch64(data, s);
+        let a1 = fetch64(data, s + 8);
+        let a2 = fetch64(data, s + 16);
+        let a3 = fetch64(data, s + 24);
+        let a4 = fetch64(data, s + 32);
+        let a5 = fetch64(data, s + 40);
+        let a6 = fetch64(data, s + 48);
+        let a7 = fetch64(data, s + 56);
+        x = x.wrapping_add(a0).wrapping_add(a1);
+        y = y.wrapping_add(a2);
+        z = z.wrapping_add(a3);
+        v.0 = v.0.wrapping_add(a4);
+        v.1 = v.1.wrapping_add(a5).wrapping_add(a1);
+        w.0 = w.0.wrapping_add(a6);
+        w.1 = w.1.wrapping_add(a7);
+
+        x = x.rotate_right(26).wrapping_mul(9);
+        y = y.rotate_right(29);
+        z = z.wrapping_mul(mul);
+        v.0 = v.0.rotate_right(33);
+        v.1 = v.1.rotate_right(30);
+        w.0 = (w.0 ^ x).wrapping_mul(9);
+        z = z.rotate_right(32).wrapping_add(w.1);
+        w.1 = w.1.wrapping_add(z);
+        z = z.wrapping_mul(9);
+        std::mem::swap(&mut u, &mut y);
+
+        z = z.wrapping_add(a0).wrapping_add(a6);
+        v.0 = v.0.wrapping_add(a2);
+        v.1 = v.1.wrapping_add(a3);
+        w.0 = w.0.wrapping_add(a4);
+        w.1 = w.1.wrapping_add(a5).wrapping_add(a6);
+        x = x.wrapping_add(a1);
+        y = y.wrapping_add(a7);
+
+        y = y.wrapping_add(v.0);
+        v.0 = v.0.wrapping_add(x.wrapping_sub(y));
+        v.1 = v.1.wrapping_add(w.0);
+        w.0 = w.0.wrapping_add(v.1);
+        w.1 = w.1.wrapping_add(x.wrapping_sub(y));
+        x = x.wrapping_add(w.1);
+        w.1 = w.1.rotate_right(34);
+        std::mem::swap(&mut u, &mut z);
+        s += 64;
+        if s == end {
+            break;
+        }
+    }
+    // process the last 64 bytes of the input
+    s = len - 64;
+    u = u.wrapping_mul(9);
+    v.1 = v.1.rotate_right(28);
+    v.0 = v.0.rotate_right(20);
+    w.0 = w.0.wrapping_add(((len - 1) & 63) as u64);
+    u = u.wrapping_add(y);
+    y = y.wrapping_add(u);
+    x = y
+        .wrapping_sub(x)
+        .wrapping_add(v.0)
+        .wrapping_add(fetch64(data, s + 8))
+        .rotate_right(37)
+        .wrapping_mul(mul);
+    y = (y ^ v.1 ^ fetch64(data, s + 48))
+        .rotate_right(42)
+        .wrapping_mul(mul);
+    x ^= w.1.wrapping_mul(9);
+    y = y.wrapping_add(v.0).wrapping_add(fetch64(data, s + 40));
+    z = z.wrapping_add(w.0).rotate_right(33).wrapping_mul(mul);
+    v = weak_hash_len_32_with_seeds(data, v.1.wrapping_mul(mul), x.wrapping_add(w.0), s);
+    w = weak_hash_len_32_with_seeds(
+        data,
+        z.wrapping_add(w.1),
+        y.wrapping_add(fetch64(data, s + 16)),
+        s + 32,
+    );
+    h(
+        hash_len_16_mul(v.0.wrapping_add(x), w.0 ^ y, mul)
+            .wrapping_add(z)
+            .wrapping_sub(u),
+        h(v.1.wrapping_add(y), w.1.wrapping_add(z), K2, 30) ^ x,
+        K2,
+        31,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    fn input() -> Vec<u8> {
+        (0..1024u32)
+            .map(|i| (i.wrapping_mul(2654435761) >> 13) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn compliance_test() {
+        let input = input();
+        // (length, no seed, seeded with 0x0123456789abcdef, seeded with that and 0xfedcba9876543210)
+        let expected = [
+            (
+                0,
+                0x9ae16a3b2f90404f,
+                0x9f48347ecc763bba,
+                0xcb0b0ef713007cf7,
+            ),
+            (
+                3,
+                0x88cdb7a066b28ed8,
+                0x101917acd8d997e8,
+                0x346adcb0038b6c7f,
+            ),
+            (
+                4,
+                0xd1656aa65fa15bcc,
+                0xe003202d8182d19a,
+                0x3eef822f1bcafdd5,
+            ),
+            (
+                8,
+                0x5c2467c03969bd6a,
+                0x5d38eeccb7151d74,
+                0xc9563666bfa7c565,
+            ),
+            (
+                9,
+                0xef4f9dbe9e445c40,
+                0x63b575ffff63c1dd,
+                0x16fd0fb6216db42b,
+            ),
+            (
+                16,
+                0x7519e05b26735db1,
+                0xbbfbb8bd80e6dcb7,
+                0x019cc5067f8a2da8,
+            ),
+            (
+                17,
+                0x170d6ccd9f1ecfa3,
+                0xfb881e31f9e02b14,
+                0xd2aca05884bf3715,
+            ),
+            (
+                24,
+                0x5443f2220d56ff84,
+                0x085e06a18de39cbe,
+                0x9cdb4fd24a13c005,
+            ),
+            (
+                25,
+                0x4b352cbcf48880c5,
+                0x76544f6acc51ae8d,
+                0x76563eaa8789f3c8,
+            ),
+            (
+                32,
+                0x0f4c1ec69dd9ab73,
+                0x18463828e4dacf21,
+                0xb6c90fd1b1e08a50,
+            ),
+            (
+                33,
+                0x6c651c27c0788ea7,
+                0x6e7bb77b5dec8e88,
+                0x48a0a5167ae7e45b,
+            ),
+            (
+                64,
+                0x111d69e00e5702c7,
+                0x6936a5ce62550ed2,
+                0xd7e0e3b9c603e9d0,
+            ),
+            (
+                65,
+                0xc23bafd49e4fb917,
+                0x2be1ebf42311b5bc,
+                0x07924ce5009971a0,
+            ),
+            (
+                96,
+                0xb3e61b7153248a6a,
+                0x2e7246886fc6f5e3,
+                0xa2e8ef0126d3a572,
+            ),
+            (
+                97,
+                0x8e9ac9097855fb64,
+                0x842f66cae58055c6,
+                0xcc2ad70bc5640768,
+            ),
+            (
+                128,
+                0xdda74d7266053e50,
+                0x7e7087be734db9c8,
+                0xb61be01c102317fd,
+            ),
+            (
+                256,
+                0x5bd9f8bdb5eda1c3,
+                0x191016ecc903a648,
+                0x36f2b65a74434126,
+            ),
+            (
+                257,
+                0x7e8b3adb304d911f,
+                0x1dfa6eef7580f8e6,
+                0x5d176d459405b722,
+            ),
+            (
+                1000,
+                0x9ad748287a805b1e,
+                0xd6475d843b655d75,
+                0x94b17abb0cd72260,
+            ),
+        ];
+        for &(len, unseeded, seeded, seeds) in expected.iter() {
+            let data = &input[..len];
+            assert_eq!(super::hash64(data), unseeded);
+            assert_eq!(super::hash64_with_seed(data, 0x0123456789abcdef), seeded);
+            assert_eq!(
+                super::hash64_with_seeds(data, 0x0123456789abcdef, 0xfedcba9876543210),
+                seeds
+            );
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! `farmhashxo`: the 64-bit variant used by `hash64()` on platforms without SSE4.2.
+use super::na::{self, fetch64, K1, K2};
+use super::uo;
+use crate::city::shift_mix;
+
+// The code below is adapted from C++ code with the following license
+//-----------------------------------------------------------------------------
+// Copyright (c) 2014 Google, Inc.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+/// `farmhashxo` 64-bit hashing algorithm.
+/// This version allows you to specify one seed, and is the same as `uo::hash64_with_seed()`.
+pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
+    uo::hash64_with_seed(v, seed)
+}
+
+/// `farmhashxo` 64-bit hashing algorithm.
+/// This version allows you to specify two seeds, and is the same as `uo::hash64_with_seeds()`.
+pub fn hash64_with_seeds<T: AsRef<[u8]>>(v: T, seed0: u64, seed1: u64) -> u64 {
+    uo::hash64_with_seeds(v, seed0, seed1)
+}
+
+/// `farmhashxo` 64-bit hashing algorithm.
+/// This version has no seed.
+pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
+    let data = v.as_ref();
+    match data.len() {
+        0..=16 => na::hash_len_0_to_16(data),
+        17..=32 => na::hash_len_17_to_32(data),
+        33..=64 => hash_len_33_to_64(data),
+        65..=96 => hash_len_65_to_96(data),
+        97..=256 => na::hash64(data),
+        _ => uo::hash64(data),
+    }
+}
+
+// hash the 32 bytes at `s`
+#[inline(always)]
+fn h32(data: &[u8], s: usize, mul: u64, seed0: u64, seed1: u64) -> u64 {
+    let a = fetch64(data, s).wrapping_mul(K1);
+    let b = fetch64(data, s + 8);
+    let c = fetch64(data, s + 24).wrapping_mul(mul);
+    let d = fetch64(data, s + 16).wrapping_mul(K2);
+    let u = a
+        .wrapping_add(b)
+        .rotate_right(43)
+        .wrapping_add(c.rotate_right(30))
+        .wrapping_add(d)
+        .wrapping_add(seed0);
+    let v = a
+        .wrapping_add(b.wrapping_add(K2).rotate_right(18))
+        .wrapping_add(c)
+        .wrapping_add(seed1);
+    let a = shift_mix((u ^ v).wrapping_mul(mul));
+    shift_mix((v ^ a).wrapping_mul(mul))
+}
+
+#[inline(always)]
+fn hash_len_33_to_64(data: &[u8]) -> u64 {
+    let len = data.len() as u64;
+    let mul0 = K2.wrapping_sub(30);
+    let mul1 = K2.wrapping_sub(30).wrapping_add(2 * len);
+    let h0 = h32(data, 0, mul0, 0, 0);
+    let h1 = h32(data, data.len() - 32, mul1, 0, 0);
+    h1.wrapping_mul(mul1).wrapping_add(h0).wrapping_mul(mul1)
+}
+
+#[inline(always)]
+fn hash_len_65_to_96(data: &[u8]) -> u64 {
+    let len = data.len() as u64;
+    let mul0 = K2.wrapping_sub(114);
+    let mul1 = K2.wrapping_sub(114).wrapping_add(2 * len);
+    let h0 = h32(data, 0, mul0, 0, 0);
+    let h1 = h32(data, 32, mul1, 0, 0);
+    let h2 = h32(data, data.len() - 32, mul1, h0, h1);
+    h2.wrapping_mul(9)
+        .wrapping_add(h0 >> 17)
+        .wrapping_add(h1 >> 21)
+        .wrapping_mul(mul1)
+}
+
+#[cfg(test)]
+mod test {
+    fn input() -> Vec<u8> {
+        (0..1024u32)
+            .map(|i| (i.wrapping_mul(2654435761) >> 13) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn compliance_test() {
+        let input = input();
+        let expected = [
+            (0, 0x9ae16a3b2f90404f),
+            (3, 0x88cdb7a066b28ed8),
+            (4, 0xd1656aa65fa15bcc),
+            (8, 0x5c2467c03969bd6a),
+            (9, 0xef4f9dbe9e445c40),
+            (16, 0x7519e05b26735db1),
+            (17, 0x170d6ccd9f1ecfa3),
+            (24, 0x5443f2220d56ff84),
+            (25, 0x4b352cbcf48880c5),
+            (32, 0x0f4c1ec69dd9ab73),
+            (33, 0xcb998a4f905e5039),
+            (64, 0xf00d462bb8d74fd1),
+            (65, 0x8dac121b095e86da),
+            (96, 0x2fa0fbe5f6931494),
+            (97, 0xe5ca29202cb14edd),
+            (128, 0xa70358e3189324c8),
+            (256, 0x1173f5c793ee7084),
+            (257, 0x7e8b3adb304d911f),
+            (1000, 0x9ad748287a805b1e),
+        ];
+        for &(len, unseeded) in expected.iter() {
+            assert_eq!(super::hash64(&input[..len]), unseeded);
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use std::hash::{BuildHasher, Hasher};
+
+use crate::{HashAlgorithm, Hasher128};
+
+/// Hasher for FNV-1 implementation of the 128-bit hashing algorithm.
+#[derive(Clone, Copy)]
+pub struct Fnv1Hasher128 {
+    hash: u128,
+}
+
+impl Fnv1Hasher128 {
+    /// Create a hasher which starts from the given offset basis instead of the standard one,
+    /// like `fnv1_128_with_offset_basis()`.
+    pub fn with_offset_basis(offset_basis: u128) -> Self {
+        Self { hash: offset_basis }
+    }
+}
+
+impl Default for Fnv1Hasher128 {
+    fn default() -> Self {
+        Self::with_offset_basis(OFFSET_BASIS_128)
+    }
+}
+
+impl Hasher for Fnv1Hasher128 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.hash = fnv1_128_with_offset_basis(bytes, self.hash);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash as u64
+    }
+}
+
+impl Hasher128 for Fnv1Hasher128 {
+    fn finish128(&self) -> u128 {
+        self.hash
+    }
+}
+
+/// Hash builder for FNV-1 implementation of the 128-bit hashing algorithm.
+#[derive(Clone, Copy)]
+pub struct Fnv1Hash128 {
+    offset_basis: u128,
+}
+
+impl Fnv1Hash128 {
+    /// Create a hash builder whose hashers start from the given offset basis,
+    /// like `fnv1_128_with_offset_basis()`.
+    pub fn with_offset_basis(offset_basis: u128) -> Self {
+        Self { offset_basis }
+    }
+}
+
+impl Default for Fnv1Hash128 {
+    fn default() -> Self {
+        Self::with_offset_basis(OFFSET_BASIS_128)
+    }
+}
+
+impl BuildHasher for Fnv1Hash128 {
+    type Hasher = Fnv1Hasher128;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_offset_basis(self.offset_basis)
+    }
+}
+
+/// Hasher for FNV-1a implementation of the 128-bit hashing algorithm.
+#[derive(Clone, Copy)]
+pub struct Fnv1aHasher128 {
+    hash: u128,
+}
+
+impl Fnv1aHasher128 {
+    /// Create a hasher which starts from the given offset basis instead of the standard one,
+    /// like `fnv1a_128_with_offset_basis()`.
+    pub fn with_offset_basis(offset_basis: u128) -> Self {
+        Self { hash: offset_basis }
+    }
+}
+
+impl Default for Fnv1aHasher128 {
+    fn default() -> Self {
+        Self::with_offset_basis(OFFSET_BASIS_128)
+    }
+}
+
+impl Hasher for Fnv1aHasher128 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.hash = fnv1a_128_with_offset_basis(bytes, self.hash);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash as u64
+    }
+}
+
+impl Hasher128 for Fnv1aHasher128 {
+    fn finish128(&self) -> u128 {
+        self.hash
+    }
+}
+
+/// Hash builder for FNV-1a implementation of the 128-bit hashing algorithm.
+#[derive(Clone, Copy)]
+pub struct Fnv1aHash128 {
+    offset_basis: u128,
+}
+
+impl Fnv1aHash128 {
+    /// Create a hash builder whose hashers start from the given offset basis,
+    /// like `fnv1a_128_with_offset_basis()`.
+    pub fn with_offset_basis(offset_basis: u128) -> Self {
+        Self { offset_basis }
+    }
+}
+
+impl Default for Fnv1aHash128 {
+    fn default() -> Self {
+        Self::with_offset_basis(OFFSET_BASIS_128)
+    }
+}
+
+impl BuildHasher for Fnv1aHash128 {
+    type Hasher = Fnv1aHasher128;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_offset_basis(self.offset_basis)
+    }
+}
+
+// The code below is adapted from C code with the following disclaimer
+//-----------------------------------------------------------------------------
+// Please do not copyright this code.  This code is in the public domain.
+//
+// By:
+//      chongo <Landon Curt Noll> /\oo/\
+//      http://www.isthe.com/chongo/
+
+/// Standard offset basis of the 128-bit FNV hashes.
+pub const OFFSET_BASIS_128: u128 = 0x6c62272e07bb014262b821756295c58d;
+
+const PRIME: u128 = 0x0000000001000000000000000000013b;
+
+/// FNV-1 implementation of the 128-bit hashing algorithm.
+pub struct Fnv1_128;
+
+impl HashAlgorithm for Fnv1_128 {
+    const NAME: &'static str = "fnv1_128";
+    type Seed = u128;
+    type Output = u128;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        fnv1_128(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        fnv1_128_with_offset_basis(v, seed)
+    }
+}
+
+/// FNV-1 implementation of the 128-bit hashing algorithm.
+/// This version allows you to replace the standard offset basis, for example with 0 for FNV-0
+/// or with the hash of a prefix to continue hashing.
+pub fn fnv1_128_with_offset_basis<T: AsRef<[u8]>>(v: T, offset_basis: u128) -> u128 {
+    v.as_ref().iter().fold(offset_basis, |hash, &byte| {
+        hash.wrapping_mul(PRIME) ^ byte as u128
+    })
+}
+
+/// FNV-1 implementation of the 128-bit hashing algorithm.
+/// The standard offset basis is always used in this version.
+pub fn fnv1_128<T: AsRef<[u8]>>(v: T) -> u128 {
+    fnv1_128_with_offset_basis(v, OFFSET_BASIS_128)
+}
+
+/// FNV-1a implementation of the 128-bit hashing algorithm.
+pub struct Fnv1a128;
+
+impl HashAlgorithm for Fnv1a128 {
+    const NAME: &'static str = "fnv1a_128";
+    type Seed = u128;
+    type Output = u128;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        fnv1a_128(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        fnv1a_128_with_offset_basis(v, seed)
+    }
+}
+
+/// FNV-1a implementation of the 128-bit hashing algorithm.
+/// This version allows you to replace the standard offset basis, for example with 0 for FNV-0
+/// or with the hash of a prefix to continue hashing.
+pub fn fnv1a_128_with_offset_basis<T: AsRef<[u8]>>(v: T, offset_basis: u128) -> u128 {
+    v.as_ref().iter().fold(offset_basis, |hash, &byte| {
+        (hash ^ byte as u128).wrapping_mul(PRIME)
+    })
+}
+
+/// FNV-1a implementation of the 128-bit hashing algorithm.
+/// The standard offset basis is always used in this version.
+pub fn fnv1a_128<T: AsRef<[u8]>>(v: T) -> u128 {
+    fnv1a_128_with_offset_basis(v, OFFSET_BASIS_128)
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    #[test]
+    fn compliance_test() {
+        // test vectors from the FNV reference test suite
+        let expected = [
+            ("", 0x6c62272e07bb014262b821756295c58d),
+            ("a", 0xd228cb69101a8caf78912b704e4a141e),
+            ("foobar", 0x7896bfea9c3c64bf6dc58353d2c293aa),
+            ("chongo was here!\n", 0x40ab469af9cf0fe57236785215beee65),
+        ];
+        for &(input, hash) in expected.iter() {
+            assert_eq!(crate::fnv::fnv_128::fnv1_128(input), hash, "{}", input);
+        }
+        let expected = [
+            ("", 0x6c62272e07bb014262b821756295c58d),
+            ("a", 0xd228cb696f1a8caf78912b704e4a8964),
+            ("foobar", 0x343e1662793c64bf6f0d3597ba446f18),
+            ("chongo was here!\n", 0xd09f538fec03781a034e1e32bab19a75),
+        ];
+        for &(input, hash) in expected.iter() {
+            assert_eq!(crate::fnv::fnv_128::fnv1a_128(input), hash, "{}", input);
+        }
+    }
+
+    #[test]
+    fn streaming_test() {
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let mut hasher = crate::fnv::Fnv1aHasher128::default();
+        for chunk in input.as_bytes().chunks(7) {
+            hasher.write(chunk);
+        }
+        assert_eq!(
+            hasher.finish(),
+            crate::fnv::fnv_128::fnv1a_128(input) as u64
+        );
+    }
+
+    #[test]
+    fn offset_basis_test() {
+        use std::hash::BuildHasher;
+        // continuing from the hash of a prefix gives the hash of the whole input
+        let prefix = crate::fnv::fnv_128::fnv1_128("foo");
+        assert_eq!(
+            crate::fnv::fnv_128::fnv1_128_with_offset_basis("bar", prefix),
+            crate::fnv::fnv_128::fnv1_128("foobar")
+        );
+        let mut hasher = crate::fnv::Fnv1Hash128::with_offset_basis(prefix).build_hasher();
+        hasher.write(b"bar");
+        assert_eq!(
+            hasher.finish(),
+            crate::fnv::fnv_128::fnv1_128("foobar") as u64
+        );
+    }
+}
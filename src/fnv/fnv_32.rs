@@ -0,0 +1,251 @@
+use std::hash::{BuildHasher, Hasher};
+
+use crate::HashAlgorithm;
+
+/// Hasher for FNV-1 implementation of the 32-bit hashing algorithm.
+#[derive(Clone, Copy)]
+pub struct Fnv1Hasher32 {
+    hash: u32,
+}
+
+impl Fnv1Hasher32 {
+    /// Create a hasher which starts from the given offset basis instead of the standard one,
+    /// like `fnv1_32_with_offset_basis()`.
+    pub fn with_offset_basis(offset_basis: u32) -> Self {
+        Self { hash: offset_basis }
+    }
+}
+
+impl Default for Fnv1Hasher32 {
+    fn default() -> Self {
+        Self::with_offset_basis(OFFSET_BASIS_32)
+    }
+}
+
+impl Hasher for Fnv1Hasher32 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.hash = fnv1_32_with_offset_basis(bytes, self.hash);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash as u64
+    }
+}
+
+/// Hash builder for FNV-1 implementation of the 32-bit hashing algorithm.
+#[derive(Clone, Copy)]
+pub struct Fnv1Hash32 {
+    offset_basis: u32,
+}
+
+impl Fnv1Hash32 {
+    /// Create a hash builder whose hashers start from the given offset basis,
+    /// like `fnv1_32_with_offset_basis()`.
+    pub fn with_offset_basis(offset_basis: u32) -> Self {
+        Self { offset_basis }
+    }
+}
+
+impl Default for Fnv1Hash32 {
+    fn default() -> Self {
+        Self::with_offset_basis(OFFSET_BASIS_32)
+    }
+}
+
+impl BuildHasher for Fnv1Hash32 {
+    type Hasher = Fnv1Hasher32;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_offset_basis(self.offset_basis)
+    }
+}
+
+/// Hasher for FNV-1a implementation of the 32-bit hashing algorithm.
+#[derive(Clone, Copy)]
+pub struct Fnv1aHasher32 {
+    hash: u32,
+}
+
+impl Fnv1aHasher32 {
+    /// Create a hasher which starts from the given offset basis instead of the standard one,
+    /// like `fnv1a_32_with_offset_basis()`.
+    pub fn with_offset_basis(offset_basis: u32) -> Self {
+        Self { hash: offset_basis }
+    }
+}
+
+impl Default for Fnv1aHasher32 {
+    fn default() -> Self {
+        Self::with_offset_basis(OFFSET_BASIS_32)
+    }
+}
+
+impl Hasher for Fnv1aHasher32 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.hash = fnv1a_32_with_offset_basis(bytes, self.hash);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash as u64
+    }
+}
+
+/// Hash builder for FNV-1a implementation of the 32-bit hashing algorithm.
+#[derive(Clone, Copy)]
+pub struct Fnv1aHash32 {
+    offset_basis: u32,
+}
+
+impl Fnv1aHash32 {
+    /// Create a hash builder whose hashers start from the given offset basis,
+    /// like `fnv1a_32_with_offset_basis()`.
+    pub fn with_offset_basis(offset_basis: u32) -> Self {
+        Self { offset_basis }
+    }
+}
+
+impl Default for Fnv1aHash32 {
+    fn default() -> Self {
+        Self::with_offset_basis(OFFSET_BASIS_32)
+    }
+}
+
+impl BuildHasher for Fnv1aHash32 {
+    type Hasher = Fnv1aHasher32;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_offset_basis(self.offset_basis)
+    }
+}
+
+// The code below is adapted from C code with the following disclaimer
+//-----------------------------------------------------------------------------
+// Please do not copyright this code.  This code is in the public domain.
+//
+// By:
+//      chongo <Landon Curt Noll> /\oo/\
+//      http://www.isthe.com/chongo/
+
+/// Standard offset basis of the 32-bit FNV hashes.
+pub const OFFSET_BASIS_32: u32 = 0x811c9dc5;
+
+const PRIME: u32 = 0x01000193;
+
+/// FNV-1 implementation of the 32-bit hashing algorithm.
+pub struct Fnv1_32;
+
+impl HashAlgorithm for Fnv1_32 {
+    const NAME: &'static str = "fnv1_32";
+    type Seed = u32;
+    type Output = u32;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        fnv1_32(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        fnv1_32_with_offset_basis(v, seed)
+    }
+}
+
+/// FNV-1 implementation of the 32-bit hashing algorithm.
+/// This version allows you to replace the standard offset basis, for example with 0 for FNV-0
+/// or with the hash of a prefix to continue hashing.
+pub fn fnv1_32_with_offset_basis<T: AsRef<[u8]>>(v: T, offset_basis: u32) -> u32 {
+    v.as_ref().iter().fold(offset_basis, |hash, &byte| {
+        hash.wrapping_mul(PRIME) ^ byte as u32
+    })
+}
+
+/// FNV-1 implementation of the 32-bit hashing algorithm.
+/// The standard offset basis is always used in this version.
+pub fn fnv1_32<T: AsRef<[u8]>>(v: T) -> u32 {
+    fnv1_32_with_offset_basis(v, OFFSET_BASIS_32)
+}
+
+/// FNV-1a implementation of the 32-bit hashing algorithm.
+pub struct Fnv1a32;
+
+impl HashAlgorithm for Fnv1a32 {
+    const NAME: &'static str = "fnv1a_32";
+    type Seed = u32;
+    type Output = u32;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        fnv1a_32(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        fnv1a_32_with_offset_basis(v, seed)
+    }
+}
+
+/// FNV-1a implementation of the 32-bit hashing algorithm.
+/// This version allows you to replace the standard offset basis, for example with 0 for FNV-0
+/// or with the hash of a prefix to continue hashing.
+pub fn fnv1a_32_with_offset_basis<T: AsRef<[u8]>>(v: T, offset_basis: u32) -> u32 {
+    v.as_ref().iter().fold(offset_basis, |hash, &byte| {
+        (hash ^ byte as u32).wrapping_mul(PRIME)
+    })
+}
+
+/// FNV-1a implementation of the 32-bit hashing algorithm.
+/// The standard offset basis is always used in this version.
+pub fn fnv1a_32<T: AsRef<[u8]>>(v: T) -> u32 {
+    fnv1a_32_with_offset_basis(v, OFFSET_BASIS_32)
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    #[test]
+    fn compliance_test() {
+        // test vectors from the FNV reference test suite
+        let expected = [
+            ("", 0x811c9dc5),
+            ("a", 0x050c5d7e),
+            ("foobar", 0x31f0b262),
+            ("chongo was here!\n", 0xdd002f35),
+        ];
+        for &(input, hash) in expected.iter() {
+            assert_eq!(crate::fnv::fnv_32::fnv1_32(input), hash, "{}", input);
+        }
+        let expected = [
+            ("", 0x811c9dc5),
+            ("a", 0xe40c292c),
+            ("foobar", 0xbf9cf968),
+            ("chongo was here!\n", 0xd49930d5),
+        ];
+        for &(input, hash) in expected.iter() {
+            assert_eq!(crate::fnv::fnv_32::fnv1a_32(input), hash, "{}", input);
+        }
+    }
+
+    #[test]
+    fn streaming_test() {
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let mut hasher = crate::fnv::Fnv1aHasher32::default();
+        for chunk in input.as_bytes().chunks(7) {
+            hasher.write(chunk);
+        }
+        assert_eq!(hasher.finish(), crate::fnv::fnv_32::fnv1a_32(input) as u64);
+    }
+
+    #[test]
+    fn offset_basis_test() {
+        use std::hash::BuildHasher;
+        // continuing from the hash of a prefix gives the hash of the whole input
+        let prefix = crate::fnv::fnv_32::fnv1_32("foo");
+        assert_eq!(
+            crate::fnv::fnv_32::fnv1_32_with_offset_basis("bar", prefix),
+            crate::fnv::fnv_32::fnv1_32("foobar")
+        );
+        let mut hasher = crate::fnv::Fnv1Hash32::with_offset_basis(prefix).build_hasher();
+        hasher.write(b"bar");
+        assert_eq!(
+            hasher.finish(),
+            crate::fnv::fnv_32::fnv1_32("foobar") as u64
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use std::hash::{BuildHasher, Hasher};
+
+use crate::HashAlgorithm;
+
+/// Hasher for FNV-1 implementation of the 64-bit hashing algorithm.
+#[derive(Clone, Copy)]
+pub struct Fnv1Hasher64 {
+    hash: u64,
+}
+
+impl Fnv1Hasher64 {
+    /// Create a hasher which starts from the given offset basis instead of the standard one,
+    /// like `fnv1_64_with_offset_basis()`.
+    pub fn with_offset_basis(offset_basis: u64) -> Self {
+        Self { hash: offset_basis }
+    }
+}
+
+impl Default for Fnv1Hasher64 {
+    fn default() -> Self {
+        Self::with_offset_basis(OFFSET_BASIS_64)
+    }
+}
+
+impl Hasher for Fnv1Hasher64 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.hash = fnv1_64_with_offset_basis(bytes, self.hash);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+/// Hash builder for FNV-1 implementation of the 64-bit hashing algorithm.
+#[derive(Clone, Copy)]
+pub struct Fnv1Hash64 {
+    offset_basis: u64,
+}
+
+impl Fnv1Hash64 {
+    /// Create a hash builder whose hashers start from the given offset basis,
+    /// like `fnv1_64_with_offset_basis()`.
+    pub fn with_offset_basis(offset_basis: u64) -> Self {
+        Self { offset_basis }
+    }
+}
+
+impl Default for Fnv1Hash64 {
+    fn default() -> Self {
+        Self::with_offset_basis(OFFSET_BASIS_64)
+    }
+}
+
+impl BuildHasher for Fnv1Hash64 {
+    type Hasher = Fnv1Hasher64;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_offset_basis(self.offset_basis)
+    }
+}
+
+/// Hasher for FNV-1a implementation of the 64-bit hashing algorithm.
+#[derive(Clone, Copy)]
+pub struct Fnv1aHasher64 {
+    hash: u64,
+}
+
+impl Fnv1aHasher64 {
+    /// Create a hasher which starts from the given offset basis instead of the standard one,
+    /// like `fnv1a_64_with_offset_basis()`.
+    pub fn with_offset_basis(offset_basis: u64) -> Self {
+        Self { hash: offset_basis }
+    }
+}
+
+impl Default for Fnv1aHasher64 {
+    fn default() -> Self {
+        Self::with_offset_basis(OFFSET_BASIS_64)
+    }
+}
+
+impl Hasher for Fnv1aHasher64 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.hash = fnv1a_64_with_offset_basis(bytes, self.hash);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+/// Hash builder for FNV-1a implementation of the 64-bit hashing algorithm.
+#[derive(Clone, Copy)]
+pub struct Fnv1aHash64 {
+    offset_basis: u64,
+}
+
+impl Fnv1aHash64 {
+    /// Create a hash builder whose hashers start from the given offset basis,
+    /// like `fnv1a_64_with_offset_basis()`.
+    pub fn with_offset_basis(offset_basis: u64) -> Self {
+        Self { offset_basis }
+    }
+}
+
+impl Default for Fnv1aHash64 {
+    fn default() -> Self {
+        Self::with_offset_basis(OFFSET_BASIS_64)
+    }
+}
+
+impl BuildHasher for Fnv1aHash64 {
+    type Hasher = Fnv1aHasher64;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_offset_basis(self.offset_basis)
+    }
+}
+
+// The code below is adapted from C code with the following disclaimer
+//-----------------------------------------------------------------------------
+// Please do not copyright this code.  This code is in the public domain.
+//
+// By:
+//      chongo <Landon Curt Noll> /\oo/\
+//      http://www.isthe.com/chongo/
+
+/// Standard offset basis of the 64-bit FNV hashes.
+pub const OFFSET_BASIS_64: u64 = 0xcbf29ce484222325;
+
+const PRIME: u64 = 0x100000001b3;
+
+/// FNV-1 implementation of the 64-bit hashing algorithm.
+pub struct Fnv1_64;
+
+impl HashAlgorithm for Fnv1_64 {
+    const NAME: &'static str = "fnv1_64";
+    type Seed = u64;
+    type Output = u64;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        fnv1_64(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        fnv1_64_with_offset_basis(v, seed)
+    }
+}
+
+/// FNV-1 implementation of the 64-bit hashing algorithm.
+/// This version allows you to replace the standard offset basis, for example with 0 for FNV-0
+/// or with the hash of a prefix to continue hashing.
+pub fn fnv1_64_with_offset_basis<T: AsRef<[u8]>>(v: T, offset_basis: u64) -> u64 {
+    v.as_ref().iter().fold(offset_basis, |hash, &byte| {
+        hash.wrapping_mul(PRIME) ^ byte as u64
+    })
+}
+
+/// FNV-1 implementation of the 64-bit hashing algorithm.
+/// The standard offset basis is always used in this version.
+pub fn fnv1_64<T: AsRef<[u8]>>(v: T) -> u64 {
+    fnv1_64_with_offset_basis(v, OFFSET_BASIS_64)
+}
+
+/// FNV-1a implementation of the 64-bit hashing algorithm.
+pub struct Fnv1a64;
+
+impl HashAlgorithm for Fnv1a64 {
+    const NAME: &'static str = "fnv1a_64";
+    type Seed = u64;
+    type Output = u64;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        fnv1a_64(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        fnv1a_64_with_offset_basis(v, seed)
+    }
+}
+
+/// FNV-1a implementation of the 64-bit hashing algorithm.
+/// This version allows you to replace the standard offset basis, for example with 0 for FNV-0
+/// or with the hash of a prefix to continue hashing.
+pub fn fnv1a_64_with_offset_basis<T: AsRef<[u8]>>(v: T, offset_basis: u64) -> u64 {
+    v.as_ref().iter().fold(offset_basis, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(PRIME)
+    })
+}
+
+/// FNV-1a implementation of the 64-bit hashing algorithm.
+/// The standard offset basis is always used in this version.
+pub fn fnv1a_64<T: AsRef<[u8]>>(v: T) -> u64 {
+    fnv1a_64_with_offset_basis(v, OFFSET_BASIS_64)
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    #[test]
+    fn compliance_test() {
+        // test vectors from the FNV reference test suite
+        let expected = [
+            ("", 0xcbf29ce484222325),
+            ("a", 0xaf63bd4c8601b7be),
+            ("foobar", 0x340d8765a4dda9c2),
+            ("chongo was here!\n", 0xe0aca20b624e4235),
+        ];
+        for &(input, hash) in expected.iter() {
+            assert_eq!(crate::fnv::fnv_64::fnv1_64(input), hash, "{}", input);
+        }
+        let expected = [
+            ("", 0xcbf29ce484222325),
+            ("a", 0xaf63dc4c8601ec8c),
+            ("foobar", 0x85944171f73967e8),
+            ("chongo was here!\n", 0x46810940eff5f915),
+        ];
+        for &(input, hash) in expected.iter() {
+            assert_eq!(crate::fnv::fnv_64::fnv1a_64(input), hash, "{}", input);
+        }
+    }
+
+    #[test]
+    fn streaming_test() {
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let mut hasher = crate::fnv::Fnv1aHasher64::default();
+        for chunk in input.as_bytes().chunks(7) {
+            hasher.write(chunk);
+        }
+        assert_eq!(hasher.finish(), crate::fnv::fnv_64::fnv1a_64(input));
+    }
+
+    #[test]
+    fn offset_basis_test() {
+        use std::hash::BuildHasher;
+        // continuing from the hash of a prefix gives the hash of the whole input
+        let prefix = crate::fnv::fnv_64::fnv1_64("foo");
+        assert_eq!(
+            crate::fnv::fnv_64::fnv1_64_with_offset_basis("bar", prefix),
+            crate::fnv::fnv_64::fnv1_64("foobar")
+        );
+        let mut hasher = crate::fnv::Fnv1Hash64::with_offset_basis(prefix).build_hasher();
+        hasher.write(b"bar");
+        assert_eq!(hasher.finish(), crate::fnv::fnv_64::fnv1_64("foobar"));
+    }
+}
@@ -0,0 +1,20 @@
+//! FNV-1 and FNV-1a algorithms by Glenn Fowler, Landon Curt Noll and Kiem-Phong Vo.
+//!
+//! FNV has no seed and is trivial to attack, so there are no randomly seeded hash builders.
+//! Instead, the offset basis can be replaced, which also allows continuing from a previous hash.
+mod fnv_128;
+mod fnv_32;
+mod fnv_64;
+
+pub use fnv_128::{
+    fnv1_128, fnv1_128_with_offset_basis, fnv1a_128, fnv1a_128_with_offset_basis, Fnv1Hash128,
+    Fnv1Hasher128, Fnv1_128, Fnv1a128, Fnv1aHash128, Fnv1aHasher128, OFFSET_BASIS_128,
+};
+pub use fnv_32::{
+    fnv1_32, fnv1_32_with_offset_basis, fnv1a_32, fnv1a_32_with_offset_basis, Fnv1Hash32,
+    Fnv1Hasher32, Fnv1_32, Fnv1a32, Fnv1aHash32, Fnv1aHasher32, OFFSET_BASIS_32,
+};
+pub use fnv_64::{
+    fnv1_64, fnv1_64_with_offset_basis, fnv1a_64, fnv1a_64_with_offset_basis, Fnv1Hash64,
+    Fnv1Hasher64, Fnv1_64, Fnv1a64, Fnv1aHash64, Fnv1aHasher64, OFFSET_BASIS_64,
+};
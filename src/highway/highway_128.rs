@@ -0,0 +1,176 @@
+use std::hash::{BuildHasher, Hasher};
+
+use super::highwayhash::State;
+use crate::{HashAlgorithm, Hasher128};
+
+/// Hasher for HighwayHash implementation of the 128-bit hashing algorithm.
+#[derive(Clone, Copy)]
+pub struct HighwayHasher128 {
+    state: State,
+}
+
+impl HighwayHasher128 {
+    /// Create a hasher which uses the given 256-bit key, like `hash128_with_key()`.
+    pub fn with_key(key: [u64; 4]) -> Self {
+        Self {
+            state: State::new(key),
+        }
+    }
+}
+
+impl Default for HighwayHasher128 {
+    fn default() -> Self {
+        Self::with_key([0; 4])
+    }
+}
+
+impl Hasher for HighwayHasher128 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.state.write(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        self.finish128() as u64
+    }
+}
+
+impl Hasher128 for HighwayHasher128 {
+    fn finish128(&self) -> u128 {
+        let hash = self.state.finish128();
+        (hash[1] as u128) << 64 | hash[0] as u128
+    }
+}
+
+/// Hash builder for HighwayHash implementation of the 128-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct HighwayHash128 {
+    key: [u64; 4],
+}
+
+impl HighwayHash128 {
+    /// Create a hash builder whose hashers use the given 256-bit key, like `hash128_with_key()`.
+    pub fn with_key(key: [u64; 4]) -> Self {
+        Self { key }
+    }
+}
+
+impl BuildHasher for HighwayHash128 {
+    type Hasher = HighwayHasher128;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_key(self.key)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly keyed hash builder for HighwayHash implementation of the 128-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct HighwayRandomState128(HighwayHash128::with_key(key: [u64; 4]));
+}
+
+/// HighwayHash implementation of the 128-bit hashing algorithm.
+pub struct Highway128;
+
+impl HashAlgorithm for Highway128 {
+    const NAME: &'static str = "highwayhash128";
+    type Seed = [u64; 4];
+    type Output = u128;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hash128(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hash128_with_key(v, seed)
+    }
+}
+
+/// HighwayHash implementation of the 128-bit hashing algorithm.
+/// This version allows you to specify the 256-bit key.
+///
+/// The two 64-bit words of the reference implementation are returned as the low and high half.
+pub fn hash128_with_key<T: AsRef<[u8]>>(v: T, key: [u64; 4]) -> u128 {
+    let mut state = State::new(key);
+    state.write(v.as_ref());
+    let hash = state.finish128();
+    (hash[1] as u128) << 64 | hash[0] as u128
+}
+
+/// HighwayHash implementation of the 128-bit hashing algorithm.
+/// The key is always 0 in this version.
+pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
+    hash128_with_key(v, [0; 4])
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    use crate::Hasher128;
+
+    // the reference test key, with bytes {0, 1, ..., 31}
+    const KEY: [u64; 4] = [
+        0x0706050403020100,
+        0x0f0e0d0c0b0a0908,
+        0x1716151413121110,
+        0x1f1e1d1c1b1a1918,
+    ];
+
+    #[test]
+    fn compliance_test() {
+        // computed with the reference C implementation, hashing {0, 1, 2, ...}
+        let expected = [
+            (0, 0x33565e767f093e6f0fed268f9d8ffec7),
+            (1, 0xdc291df9eb9cdcb4d6b0a8893681e7a8),
+            (3, 0xbfe69a0fd9cedd790607621b295f0beb),
+            (4, 0x2e922ad03931920826399eb46dace49e),
+            (16, 0x029ea3d5019f18c8414460ffd5a401ad),
+            (31, 0x52415e3a07f5d44606e7b465e8a57c29),
+            (32, 0x16fc1958f9b3e4b91984df66c1434aaa),
+            (33, 0xf958b59de5a2849d111678afe0c6c36c),
+            (63, 0x3aea94a8ad5f4bcb74bd7018022f3ef0),
+            (64, 0xe0bc0571de918fc898bb1f7198d4c4f2),
+        ];
+        let input: Vec<u8> = (0..64).collect();
+        for &(len, hash) in expected.iter() {
+            assert_eq!(
+                crate::highway::highway_128::hash128_with_key(&input[..len], KEY),
+                hash,
+                "{}",
+                len
+            );
+        }
+    }
+
+    #[test]
+    fn streaming_test() {
+        let input: Vec<u8> = (0..100).collect();
+        for split in 0..input.len() {
+            let mut hasher = crate::highway::HighwayHasher128::with_key(KEY);
+            hasher.write(&input[..split]);
+            hasher.write(&input[split..]);
+            assert_eq!(
+                hasher.finish128(),
+                crate::highway::highway_128::hash128_with_key(&input, KEY)
+            );
+        }
+    }
+
+    #[test]
+    fn seeded_builder_test() {
+        use std::hash::BuildHasher;
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let mut hasher = crate::highway::HighwayHash128::with_key(KEY).build_hasher();
+        for chunk in input.as_bytes().chunks(7) {
+            hasher.write(chunk);
+        }
+        assert_eq!(
+            hasher.finish128(),
+            crate::highway::highway_128::hash128_with_key(input, KEY)
+        );
+        assert_eq!(
+            hasher.finish(),
+            crate::highway::highway_128::hash128_with_key(input, KEY) as u64
+        );
+    }
+}
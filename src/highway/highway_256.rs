@@ -0,0 +1,236 @@
+use std::hash::{BuildHasher, Hasher};
+
+use super::highwayhash::State;
+use crate::HashAlgorithm;
+
+/// Hasher for HighwayHash implementation of the 256-bit hashing algorithm.
+///
+/// `Hasher::finish()` returns the first word of the hash, `finish256()` returns all of it.
+#[derive(Clone, Copy)]
+pub struct HighwayHasher256 {
+    state: State,
+}
+
+impl HighwayHasher256 {
+    /// Create a hasher which uses the given 256-bit key, like `hash256_with_key()`.
+    pub fn with_key(key: [u64; 4]) -> Self {
+        Self {
+            state: State::new(key),
+        }
+    }
+
+    /// Returns the full 256-bit hash value for the values written, like `hash256_with_key()`.
+    pub fn finish256(&self) -> [u64; 4] {
+        self.state.finish256()
+    }
+}
+
+impl Default for HighwayHasher256 {
+    fn default() -> Self {
+        Self::with_key([0; 4])
+    }
+}
+
+impl Hasher for HighwayHasher256 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.state.write(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        self.state.finish256()[0]
+    }
+}
+
+/// Hash builder for HighwayHash implementation of the 256-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct HighwayHash256 {
+    key: [u64; 4],
+}
+
+impl HighwayHash256 {
+    /// Create a hash builder whose hashers use the given 256-bit key, like `hash256_with_key()`.
+    pub fn with_key(key: [u64; 4]) -> Self {
+        Self { key }
+    }
+}
+
+impl BuildHasher for HighwayHash256 {
+    type Hasher = HighwayHasher256;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_key(self.key)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly keyed hash builder for HighwayHash implementation of the 256-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct HighwayRandomState256(HighwayHash256::with_key(key: [u64; 4]));
+}
+
+/// HighwayHash implementation of the 256-bit hashing algorithm.
+///
+/// It is not part of the `registry`, which only handles hashes of up to 128 bits.
+pub struct Highway256;
+
+impl HashAlgorithm for Highway256 {
+    const NAME: &'static str = "highwayhash256";
+    type Seed = [u64; 4];
+    type Output = [u64; 4];
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hash256(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hash256_with_key(v, seed)
+    }
+}
+
+/// HighwayHash implementation of the 256-bit hashing algorithm.
+/// This version allows you to specify the 256-bit key.
+///
+/// The hash is returned as four 64-bit words, in the order of the reference implementation.
+pub fn hash256_with_key<T: AsRef<[u8]>>(v: T, key: [u64; 4]) -> [u64; 4] {
+    let mut state = State::new(key);
+    state.write(v.as_ref());
+    state.finish256()
+}
+
+/// HighwayHash implementation of the 256-bit hashing algorithm.
+/// The key is always 0 in this version.
+pub fn hash256<T: AsRef<[u8]>>(v: T) -> [u64; 4] {
+    hash256_with_key(v, [0; 4])
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    // the reference test key, with bytes {0, 1, ..., 31}
+    const KEY: [u64; 4] = [
+        0x0706050403020100,
+        0x0f0e0d0c0b0a0908,
+        0x1716151413121110,
+        0x1f1e1d1c1b1a1918,
+    ];
+
+    #[test]
+    fn compliance_test() {
+        // computed with the reference C implementation, hashing {0, 1, 2, ...}
+        let expected = [
+            (
+                0,
+                [
+                    0xdd44482ac2c874f5,
+                    0xd946017313c7351f,
+                    0xb3aebeccb98714ff,
+                    0x41da233145751df4,
+                ],
+            ),
+            (
+                1,
+                [
+                    0xedb941bce45f8254,
+                    0xe20d44ef3dcac60f,
+                    0x72651b9bcb324a47,
+                    0x2073624cb275e484,
+                ],
+            ),
+            (
+                3,
+                [
+                    0x480aa0d70dd1d95c,
+                    0x89225e7c6911d1d0,
+                    0x8ea8426b8bbb865a,
+                    0xe23dfbc390e1c722,
+                ],
+            ),
+            (
+                4,
+                [
+                    0xc9cfc497212be4dc,
+                    0xa85f9df6afd2929b,
+                    0x1fda9f211df4109e,
+                    0x07e4277a374d4f9b,
+                ],
+            ),
+            (
+                16,
+                [
+                    0xf51ad989a1b6cd1f,
+                    0xf7f075d62a627bd9,
+                    0x7e01d5f579f28a06,
+                    0x1ad415c16a174d9f,
+                ],
+            ),
+            (
+                31,
+                [
+                    0x6880e276601a644d,
+                    0xb3728b20b10fb7da,
+                    0xd0bd12060610d16e,
+                    0x8aef14ef33452ef2,
+                ],
+            ),
+            (
+                32,
+                [
+                    0xbce38c9039a1c3fe,
+                    0x42d56326a3c11289,
+                    0xe35595f764fcaea9,
+                    0xc9b03c6bc9475a99,
+                ],
+            ),
+            (
+                33,
+                [
+                    0xf60115cbf034a6e5,
+                    0x6c36ea75bfce46d0,
+                    0x3b17c8d382725990,
+                    0x7edaa2ed11007a35,
+                ],
+            ),
+            (
+                63,
+                [
+                    0xf5b1f8266a3aeb67,
+                    0x83b040be4dec1add,
+                    0x7fe1c8635b26fbae,
+                    0xf4a3a447defed79f,
+                ],
+            ),
+            (
+                64,
+                [
+                    0x90d8e6ff6ac12475,
+                    0x1a422a196edac1f2,
+                    0x9e3765fe1f8eb002,
+                    0xc1bdd7c4c351cfbe,
+                ],
+            ),
+        ];
+        let input: Vec<u8> = (0..64).collect();
+        for &(len, hash) in expected.iter() {
+            assert_eq!(
+                crate::highway::highway_256::hash256_with_key(&input[..len], KEY),
+                hash,
+                "{}",
+                len
+            );
+        }
+    }
+
+    #[test]
+    fn streaming_test() {
+        let input: Vec<u8> = (0..100).collect();
+        for split in 0..input.len() {
+            let mut hasher = crate::highway::HighwayHasher256::with_key(KEY);
+            hasher.write(&input[..split]);
+            hasher.write(&input[split..]);
+            let hash = crate::highway::highway_256::hash256_with_key(&input, KEY);
+            assert_eq!(hasher.finish256(), hash);
+            assert_eq!(hasher.finish(), hash[0]);
+        }
+    }
+}
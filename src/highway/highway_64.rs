@@ -0,0 +1,220 @@
+use std::hash::{BuildHasher, Hasher};
+
+use super::highwayhash::State;
+use crate::HashAlgorithm;
+
+/// Hasher for HighwayHash implementation of the 64-bit hashing algorithm.
+#[derive(Clone, Copy)]
+pub struct HighwayHasher64 {
+    state: State,
+}
+
+impl HighwayHasher64 {
+    /// Create a hasher which uses the given 256-bit key, like `hash64_with_key()`.
+    pub fn with_key(key: [u64; 4]) -> Self {
+        Self {
+            state: State::new(key),
+        }
+    }
+}
+
+impl Default for HighwayHasher64 {
+    fn default() -> Self {
+        Self::with_key([0; 4])
+    }
+}
+
+impl Hasher for HighwayHasher64 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.state.write(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        self.state.finish64()
+    }
+}
+
+/// Hash builder for HighwayHash implementation of the 64-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct HighwayHash64 {
+    key: [u64; 4],
+}
+
+impl HighwayHash64 {
+    /// Create a hash builder whose hashers use the given 256-bit key, like `hash64_with_key()`.
+    pub fn with_key(key: [u64; 4]) -> Self {
+        Self { key }
+    }
+}
+
+impl BuildHasher for HighwayHash64 {
+    type Hasher = HighwayHasher64;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_key(self.key)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly keyed hash builder for HighwayHash implementation of the 64-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct HighwayRandomState64(HighwayHash64::with_key(key: [u64; 4]));
+}
+
+/// HighwayHash implementation of the 64-bit hashing algorithm.
+pub struct Highway64;
+
+impl HashAlgorithm for Highway64 {
+    const NAME: &'static str = "highwayhash64";
+    type Seed = [u64; 4];
+    type Output = u64;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hash64(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hash64_with_key(v, seed)
+    }
+}
+
+/// HighwayHash implementation of the 64-bit hashing algorithm.
+/// This version allows you to specify the 256-bit key.
+pub fn hash64_with_key<T: AsRef<[u8]>>(v: T, key: [u64; 4]) -> u64 {
+    let mut state = State::new(key);
+    state.write(v.as_ref());
+    state.finish64()
+}
+
+/// HighwayHash implementation of the 64-bit hashing algorithm.
+/// The key is always 0 in this version.
+pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
+    hash64_with_key(v, [0; 4])
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    // the reference test key, with bytes {0, 1, ..., 31}
+    const KEY: [u64; 4] = [
+        0x0706050403020100,
+        0x0f0e0d0c0b0a0908,
+        0x1716151413121110,
+        0x1f1e1d1c1b1a1918,
+    ];
+
+    #[test]
+    fn compliance_test() {
+        // test vectors from the reference implementation, hashing {0, 1, 2, ...}
+        let expected: [u64; 65] = [
+            0x907a56de22c26e53,
+            0x7eab43aac7cddd78,
+            0xb8d0569ab0b53d62,
+            0x5c6befab8a463d80,
+            0xf205a46893007eda,
+            0x2b8a1668e4a94541,
+            0xbd4ccc325befca6f,
+            0x4d02ae1738f59482,
+            0xe1205108e55f3171,
+            0x32d2644ec77a1584,
+            0xf6e10acdb103a90b,
+            0xc3bbf4615b415c15,
+            0x243cc2040063fa9c,
+            0xa89a58ce65e641ff,
+            0x24b031a348455a23,
+            0x40793f86a449f33b,
+            0xcfab3489f97eb832,
+            0x19fe67d2c8c5c0e2,
+            0x04dd90a69c565cc2,
+            0x75d9518e2371c504,
+            0x38ad9b1141d3dd16,
+            0x0264432ccd8a70e0,
+            0xa9db5a6288683390,
+            0xd7b05492003f028c,
+            0x205f615aea59e51e,
+            0xeee0c89621052884,
+            0x1bfc1a93a7284f4f,
+            0x512175b5b70da91d,
+            0xf71f8976a0a2c639,
+            0xae093fef1f84e3e7,
+            0x22ca92b01161860f,
+            0x9fc7007ccf035a68,
+            0xa0c964d9ecd580fc,
+            0x2c90f73ca03181fc,
+            0x185cf84e5691eb9e,
+            0x4fc1f5ef2752aa9b,
+            0xf5b7391a5e0a33eb,
+            0xb9b84b83b4e96c9c,
+            0x5e42fe712a5cd9b4,
+            0xa150f2f90c3f97dc,
+            0x7fa522d75e2d637d,
+            0x181ad0cc0dffd32b,
+            0x3889ed981e854028,
+            0xfb4297e8c586ee2d,
+            0x6d064a45bb28059c,
+            0x90563609b3ec860c,
+            0x7aa4fce94097c666,
+            0x1326bac06b911e08,
+            0xb926168d2b154f34,
+            0x9919848945b1948d,
+            0xa2a98fc534825ebe,
+            0xe9809095213ef0b6,
+            0x582e5483707bc0e9,
+            0x086e9414a88a6af5,
+            0xee86b98d20f6743d,
+            0xf89b7ff609b1c0a7,
+            0x4c7d9cc19e22c3e8,
+            0x9a97005024562a6f,
+            0x5dd41cf423e6ebef,
+            0xdf13609c0468e227,
+            0x6e0da4f64188155a,
+            0xb755ba4b50d7d4a1,
+            0x887a3484647479bd,
+            0xab8eebe9bf2139a0,
+            0x75542c5d4cd2a6ff,
+        ];
+        let input: Vec<u8> = (0..64).collect();
+        for (len, &hash) in expected.iter().enumerate() {
+            assert_eq!(
+                crate::highway::highway_64::hash64_with_key(&input[..len], KEY),
+                hash,
+                "{}",
+                len
+            );
+        }
+        let input: Vec<u8> = (128..161).collect();
+        assert_eq!(
+            crate::highway::highway_64::hash64_with_key(&input, [1, 2, 3, 4]),
+            0x53c516cce478cad7
+        );
+    }
+
+    #[test]
+    fn streaming_test() {
+        let input: Vec<u8> = (0..100).collect();
+        for split in 0..input.len() {
+            let mut hasher = crate::highway::HighwayHasher64::with_key(KEY);
+            hasher.write(&input[..split]);
+            hasher.write(&input[split..]);
+            assert_eq!(
+                hasher.finish(),
+                crate::highway::highway_64::hash64_with_key(&input, KEY)
+            );
+        }
+    }
+
+    #[test]
+    fn seeded_builder_test() {
+        use std::hash::BuildHasher;
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let mut hasher = crate::highway::HighwayHash64::with_key(KEY).build_hasher();
+        for chunk in input.as_bytes().chunks(7) {
+            hasher.write(chunk);
+        }
+        assert_eq!(
+            hasher.finish(),
+            crate::highway::highway_64::hash64_with_key(input, KEY)
+        );
+    }
+}
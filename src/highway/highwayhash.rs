@@ -0,0 +1,258 @@
+// The code below is adapted from C code with the following license
+//-----------------------------------------------------------------------------
+// Copyright 2017 Google Inc. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+const INIT0: [u64; 4] = [
+    0xdbe6d5d5fe4cce2f,
+    0xa4093822299f31d0,
+    0x13198a2e03707344,
+    0x243f6a8885a308d3,
+];
+const INIT1: [u64; 4] = [
+    0x3bd39e10cb0ef593,
+    0xc0acf169b5f18a8c,
+    0xbe5466cf34e90c6c,
+    0x452821e638d01377,
+];
+
+/// Streaming state of HighwayHash, shared by the 64-bit, 128-bit and 256-bit variants.
+///
+/// Full 32-byte packets are processed as soon as they are written, so only the last partial
+/// packet is buffered.
+#[derive(Clone, Copy)]
+pub(super) struct State {
+    v0: [u64; 4],
+    v1: [u64; 4],
+    mul0: [u64; 4],
+    mul1: [u64; 4],
+    packet: [u8; 32],
+    num: usize,
+}
+
+impl State {
+    pub(super) fn new(key: [u64; 4]) -> Self {
+        let mut state = Self {
+            v0: [0; 4],
+            v1: [0; 4],
+            mul0: INIT0,
+            mul1: INIT1,
+            packet: [0; 32],
+            num: 0,
+        };
+        for i in 0..4 {
+            state.v0[i] = INIT0[i] ^ key[i];
+            state.v1[i] = INIT1[i] ^ key[i].rotate_left(32);
+        }
+        state
+    }
+
+    pub(super) fn write(&mut self, bytes: &[u8]) {
+        let mut data = bytes;
+        // complete a previously started packet
+        if self.num != 0 {
+            let fill = (32 - self.num).min(data.len());
+            self.packet[self.num..self.num + fill].copy_from_slice(&data[..fill]);
+            self.num += fill;
+            data = &data[fill..];
+            if self.num < 32 {
+                return;
+            }
+            let packet = self.packet;
+            self.update_packet(&packet);
+            self.num = 0;
+        }
+        // process full packets directly from the input
+        let mut chunks = data.chunks_exact(32);
+        for chunk in &mut chunks {
+            self.update_packet(chunk);
+        }
+        // keep the remainder for the next write
+        let rest = chunks.remainder();
+        self.packet[..rest.len()].copy_from_slice(rest);
+        self.num = rest.len();
+    }
+
+    pub(super) fn finish64(&self) -> u64 {
+        let mut state = self.finish_input();
+        for _ in 0..4 {
+            state.permute_and_update();
+        }
+        state.v0[0]
+            .wrapping_add(state.v1[0])
+            .wrapping_add(state.mul0[0])
+            .wrapping_add(state.mul1[0])
+    }
+
+    pub(super) fn finish128(&self) -> [u64; 2] {
+        let mut state = self.finish_input();
+        for _ in 0..6 {
+            state.permute_and_update();
+        }
+        [
+            state.v0[0]
+                .wrapping_add(state.mul0[0])
+                .wrapping_add(state.v1[2])
+                .wrapping_add(state.mul1[2]),
+            state.v0[1]
+                .wrapping_add(state.mul0[1])
+                .wrapping_add(state.v1[3])
+                .wrapping_add(state.mul1[3]),
+        ]
+    }
+
+    pub(super) fn finish256(&self) -> [u64; 4] {
+        let mut state = self.finish_input();
+        // 256-bit hashes are expected to be mostly used with long messages, so the extra rounds
+        // hardly increase the per-byte cost
+        for _ in 0..10 {
+            state.permute_and_update();
+        }
+        let (h1, h0) = modular_reduction(
+            state.v1[1].wrapping_add(state.mul1[1]),
+            state.v1[0].wrapping_add(state.mul1[0]),
+            state.v0[1].wrapping_add(state.mul0[1]),
+            state.v0[0].wrapping_add(state.mul0[0]),
+        );
+        let (h3, h2) = modular_reduction(
+            state.v1[3].wrapping_add(state.mul1[3]),
+            state.v1[2].wrapping_add(state.mul1[2]),
+            state.v0[3].wrapping_add(state.mul0[3]),
+            state.v0[2].wrapping_add(state.mul0[2]),
+        );
+        [h0, h1, h2, h3]
+    }
+
+    // a copy of the state with the buffered remainder mixed in
+    fn finish_input(&self) -> Self {
+        let mut state = *self;
+        if self.num != 0 {
+            state.update_remainder(&self.packet[..self.num]);
+        }
+        state
+    }
+
+    #[inline(always)]
+    fn update_packet(&mut self, packet: &[u8]) {
+        let lanes = [
+            fetch64(packet, 0),
+            fetch64(packet, 8),
+            fetch64(packet, 16),
+            fetch64(packet, 24),
+        ];
+        self.update(lanes);
+    }
+
+    // the final 1 to 31 bytes
+    fn update_remainder(&mut self, bytes: &[u8]) {
+        let size = bytes.len();
+        let size_mod4 = size & 3;
+        let whole = size & !3;
+        for i in 0..4 {
+            self.v0[i] = self.v0[i].wrapping_add(((size as u64) << 32) + size as u64);
+            // rotate both 32-bit halves of the lane by the size
+            let low = (self.v1[i] as u32).rotate_left(size as u32);
+            let high = ((self.v1[i] >> 32) as u32).rotate_left(size as u32);
+            self.v1[i] = (high as u64) << 32 | low as u64;
+        }
+        let mut packet = [0; 32];
+        packet[..whole].copy_from_slice(&bytes[..whole]);
+        if size & 16 != 0 {
+            // the last 4 bytes, which may overlap the ones already copied
+            packet[28..].copy_from_slice(&bytes[size - 4..]);
+        } else if size_mod4 != 0 {
+            let remainder = &bytes[whole..];
+            packet[16] = remainder[0];
+            packet[17] = remainder[size_mod4 >> 1];
+            packet[18] = remainder[size_mod4 - 1];
+        }
+        self.update_packet(&packet);
+    }
+
+    #[inline(always)]
+    fn permute_and_update(&mut self) {
+        let v0 = self.v0;
+        self.update([
+            v0[2].rotate_left(32),
+            v0[3].rotate_left(32),
+            v0[0].rotate_left(32),
+            v0[1].rotate_left(32),
+        ]);
+    }
+
+    #[inline(always)]
+    fn update(&mut self, lanes: [u64; 4]) {
+        for (i, lane) in lanes.iter().enumerate() {
+            self.v1[i] = self.v1[i].wrapping_add(self.mul0[i].wrapping_add(*lane));
+            self.mul0[i] ^= (self.v1[i] & 0xffffffff).wrapping_mul(self.v0[i] >> 32);
+            self.v0[i] = self.v0[i].wrapping_add(self.mul1[i]);
+            self.mul1[i] ^= (self.v0[i] & 0xffffffff).wrapping_mul(self.v1[i] >> 32);
+        }
+        let (v1, v0) = zipper_merge(self.v1[1], self.v1[0]);
+        self.v0[1] = self.v0[1].wrapping_add(v1);
+        self.v0[0] = self.v0[0].wrapping_add(v0);
+        let (v3, v2) = zipper_merge(self.v1[3], self.v1[2]);
+        self.v0[3] = self.v0[3].wrapping_add(v3);
+        self.v0[2] = self.v0[2].wrapping_add(v2);
+        let (v1, v0) = zipper_merge(self.v0[1], self.v0[0]);
+        self.v1[1] = self.v1[1].wrapping_add(v1);
+        self.v1[0] = self.v1[0].wrapping_add(v0);
+        let (v3, v2) = zipper_merge(self.v0[3], self.v0[2]);
+        self.v1[3] = self.v1[3].wrapping_add(v3);
+        self.v1[2] = self.v1[2].wrapping_add(v2);
+    }
+}
+
+// shuffles the bytes of the two lanes, returning the values to add to the high and low lane
+#[inline(always)]
+fn zipper_merge(v1: u64, v0: u64) -> (u64, u64) {
+    let add0 = (((v0 & 0xff000000) | (v1 & 0xff00000000)) >> 24)
+        | (((v0 & 0xff0000000000) | (v1 & 0xff000000000000)) >> 16)
+        | (v0 & 0xff0000)
+        | ((v0 & 0xff00) << 32)
+        | ((v1 & 0xff00000000000000) >> 8)
+        | (v0 << 56);
+    let add1 = (((v1 & 0xff000000) | (v0 & 0xff00000000)) >> 24)
+        | (v1 & 0xff0000)
+        | ((v1 & 0xff0000000000) >> 16)
+        | ((v1 & 0xff00) << 24)
+        | ((v0 & 0xff000000000000) >> 8)
+        | ((v1 & 0xff) << 48)
+        | (v0 & 0xff00000000000000);
+    (add1, add0)
+}
+
+// reduces the 256-bit value a3:a2:a1:a0 to 128 bits, returning the high and low half
+#[inline(always)]
+fn modular_reduction(a3_unmasked: u64, a2: u64, a1: u64, a0: u64) -> (u64, u64) {
+    let a3 = a3_unmasked & 0x3fffffffffffffff;
+    let m1 = a1 ^ ((a3 << 1) | (a2 >> 63)) ^ ((a3 << 2) | (a2 >> 62));
+    let m0 = a0 ^ (a2 << 1) ^ (a2 << 2);
+    (m1, m0)
+}
+
+#[inline(always)]
+fn fetch64(data: &[u8], i: usize) -> u64 {
+    let buf = [
+        data[i],
+        data[i + 1],
+        data[i + 2],
+        data[i + 3],
+        data[i + 4],
+        data[i + 5],
+        data[i + 6],
+        data[i + 7],
+    ];
+    u64::from_le_bytes(buf)
+}
@@ -0,0 +1,17 @@
+//! HighwayHash keyed hash function by Jyrki Alakuijala, Bill Cox and Jan Wassenberg.
+//!
+//! This is a portable implementation of the reference algorithm, without SIMD.
+mod highway_128;
+mod highway_256;
+mod highway_64;
+mod highwayhash;
+
+pub use highway_128::{
+    hash128, hash128_with_key, Highway128, HighwayHash128, HighwayHasher128, HighwayRandomState128,
+};
+pub use highway_256::{
+    hash256, hash256_with_key, Highway256, HighwayHash256, HighwayHasher256, HighwayRandomState256,
+};
+pub use highway_64::{
+    hash64, hash64_with_key, Highway64, HighwayHash64, HighwayHasher64, HighwayRandomState64,
+};
@@ -0,0 +1,259 @@
+use std::hash::{BuildHasher, Hasher};
+
+use crate::HashAlgorithm;
+
+/// Hasher for lookup2 implementation of the 32-bit hashing algorithm.
+///
+/// lookup2 only adds the length of the input to its state before the final mix, so full 12-byte
+/// blocks are hashed as they are written, and at most 11 bytes are kept until `finish()`.
+pub struct Lookup2Hasher32 {
+    a: u32,
+    b: u32,
+    c: u32,
+    tail: [u8; 12],
+    tail_len: usize,
+    length: u32,
+}
+
+impl Lookup2Hasher32 {
+    /// Create a hasher which uses the given seed, like `lookup2()`.
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            a: GOLDEN_RATIO,
+            b: GOLDEN_RATIO,
+            c: seed,
+            tail: [0; 12],
+            tail_len: 0,
+            length: 0,
+        }
+    }
+}
+
+impl Default for Lookup2Hasher32 {
+    fn default() -> Self {
+        Self::with_seed(0)
+    }
+}
+
+impl Hasher for Lookup2Hasher32 {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut data = bytes;
+        // like the reference implementation, the length is added modulo 2^32
+        self.length = self.length.wrapping_add(data.len() as u32);
+        // complete a previously started block
+        if self.tail_len != 0 {
+            let fill = (12 - self.tail_len).min(data.len());
+            self.tail[self.tail_len..self.tail_len + fill].copy_from_slice(&data[..fill]);
+            self.tail_len += fill;
+            data = &data[fill..];
+            if self.tail_len < 12 {
+                return;
+            }
+            let (a, b, c) = body(self.a, self.b, self.c, &self.tail);
+            self.a = a;
+            self.b = b;
+            self.c = c;
+            self.tail_len = 0;
+        }
+        // process full blocks directly from the input
+        let mut blocks = data.chunks_exact(12);
+        for block in &mut blocks {
+            let (a, b, c) = body(self.a, self.b, self.c, block);
+            self.a = a;
+            self.b = b;
+            self.c = c;
+        }
+        // keep the remainder for the next write
+        let rest = blocks.remainder();
+        self.tail[..rest.len()].copy_from_slice(rest);
+        self.tail_len = rest.len();
+    }
+
+    fn finish(&self) -> u64 {
+        finalize(
+            self.a,
+            self.b,
+            self.c,
+            &self.tail[..self.tail_len],
+            self.length,
+        ) as u64
+    }
+}
+
+/// Hash builder for lookup2 implementation of the 32-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct Lookup2Hash32 {
+    seed: u32,
+}
+
+impl Lookup2Hash32 {
+    /// Create a hash builder whose hashers use the given seed, like `lookup2()`.
+    pub fn with_seed(seed: u32) -> Self {
+        Self { seed }
+    }
+}
+
+impl BuildHasher for Lookup2Hash32 {
+    type Hasher = Lookup2Hasher32;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_seed(self.seed)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for lookup2 implementation of the 32-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct Lookup2RandomState32(Lookup2Hash32::with_seed(seed: u32));
+}
+
+// The code below is adapted from C code with the following disclaimer
+//-----------------------------------------------------------------------------
+// lookup2.c, by Bob Jenkins, December 1996, Public Domain.
+//
+// You may use this code any way you wish, private, educational, or commercial.
+// It's free.
+
+/// lookup2 implementation of the 32-bit hashing algorithm.
+pub struct Lookup2;
+
+impl HashAlgorithm for Lookup2 {
+    const NAME: &'static str = "lookup2";
+    type Seed = u32;
+    type Output = u32;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        lookup2(v, 0)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        lookup2(v, seed)
+    }
+}
+
+/// lookup2 implementation of the 32-bit hashing algorithm (`hash` in the reference implementation).
+/// `initval` is the seed, any 4-byte value, for example the previous hash.
+///
+/// This is also what the Linux kernel's `jhash()` computed before version 2.6.37.
+pub fn lookup2<T: AsRef<[u8]>>(v: T, initval: u32) -> u32 {
+    let data = v.as_ref();
+    let mut a = GOLDEN_RATIO;
+    let mut b = GOLDEN_RATIO;
+    let mut c = initval;
+
+    let mut blocks = data.chunks_exact(12);
+    for block in &mut blocks {
+        let mixed = body(a, b, c, block);
+        a = mixed.0;
+        b = mixed.1;
+        c = mixed.2;
+    }
+    finalize(a, b, c, blocks.remainder(), data.len() as u32)
+}
+
+// the initial value of a and b, an arbitrary value
+const GOLDEN_RATIO: u32 = 0x9e3779b9;
+
+// hash a full 12-byte block
+#[inline(always)]
+fn body(a: u32, b: u32, c: u32, block: &[u8]) -> (u32, u32, u32) {
+    mix(
+        a.wrapping_add(fetch32(block, 0)),
+        b.wrapping_add(fetch32(block, 4)),
+        c.wrapping_add(fetch32(block, 8)),
+    )
+}
+
+// hash the last 0 to 11 bytes, zero-padded, with the lowest byte of c reserved for the length
+#[inline(always)]
+fn finalize(a: u32, b: u32, c: u32, tail: &[u8], length: u32) -> u32 {
+    let mut block = [0; 12];
+    block[..tail.len()].copy_from_slice(tail);
+    let (_, _, c) = mix(
+        a.wrapping_add(fetch32(&block, 0)),
+        b.wrapping_add(fetch32(&block, 4)),
+        c.wrapping_add(length).wrapping_add(fetch32(&block, 8) << 8),
+    );
+    c
+}
+
+// mix 3 32-bit values reversibly
+#[inline(always)]
+fn mix(mut a: u32, mut b: u32, mut c: u32) -> (u32, u32, u32) {
+    a = a.wrapping_sub(b).wrapping_sub(c) ^ (c >> 13);
+    b = b.wrapping_sub(c).wrapping_sub(a) ^ (a << 8);
+    c = c.wrapping_sub(a).wrapping_sub(b) ^ (b >> 13);
+    a = a.wrapping_sub(b).wrapping_sub(c) ^ (c >> 12);
+    b = b.wrapping_sub(c).wrapping_sub(a) ^ (a << 16);
+    c = c.wrapping_sub(a).wrapping_sub(b) ^ (b >> 5);
+    a = a.wrapping_sub(b).wrapping_sub(c) ^ (c >> 3);
+    b = b.wrapping_sub(c).wrapping_sub(a) ^ (a << 10);
+    c = c.wrapping_sub(a).wrapping_sub(b) ^ (b >> 15);
+    (a, b, c)
+}
+
+#[inline(always)]
+fn fetch32(data: &[u8], i: usize) -> u32 {
+    let buf = [data[i], data[i + 1], data[i + 2], data[i + 3]];
+    u32::from_le_bytes(buf)
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    #[test]
+    fn compliance_test() {
+        // computed with the hash() function of lookup2.c, for every tail length and a few blocks
+        let input = "Four score and seven years ago";
+        let expected = [
+            (0, 0xbd49d10d, 0x99d60e14),
+            (1, 0x2f128c38, 0x3f1de835),
+            (2, 0x3a88b547, 0xf9452474),
+            (3, 0xed7fe140, 0xe8229b07),
+            (4, 0xbfa65111, 0xdf7c93f4),
+            (5, 0xe412b220, 0x161542f6),
+            (6, 0x13425875, 0x0d5924ca),
+            (7, 0x35392a09, 0x911111c6),
+            (8, 0x6f8ea12b, 0x5ac31e5c),
+            (9, 0x618cee90, 0x16281762),
+            (10, 0x466cf6bb, 0x645c1d46),
+            (11, 0xf2aade2b, 0xdb129ec0),
+            (12, 0xcc6600d8, 0x7177121f),
+            (13, 0xf4692621, 0x5e08b1fa),
+            (23, 0xe324fcd8, 0xab3ed8d4),
+            (24, 0x9357c18c, 0x343cd362),
+            (30, 0x50f2424b, 0x8974915b),
+        ];
+        for &(len, hash, seeded) in expected.iter() {
+            assert_eq!(crate::jenkins::lookup2(&input[..len], 0), hash, "{}", len);
+            assert_eq!(
+                crate::jenkins::lookup2(&input[..len], 4919),
+                seeded,
+                "{}",
+                len
+            );
+        }
+    }
+
+    #[test]
+    fn streaming_test() {
+        use std::hash::BuildHasher;
+        let input: Vec<u8> = (0..64u32).map(|i| (i * 7 + 3) as u8).collect();
+        for len in 0..input.len() {
+            for &chunk in [1, 5, 11, 12, 13].iter() {
+                let mut hasher = crate::jenkins::Lookup2Hash32::with_seed(4919).build_hasher();
+                for part in input[..len].chunks(chunk) {
+                    hasher.write(part);
+                }
+                assert_eq!(
+                    hasher.finish(),
+                    crate::jenkins::lookup2(&input[..len], 4919) as u64,
+                    "{} {}",
+                    len,
+                    chunk
+                );
+            }
+        }
+    }
+}
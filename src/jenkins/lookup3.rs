@@ -0,0 +1,526 @@
+use std::hash::{BuildHasher, Hasher};
+
+use crate::HashAlgorithm;
+
+/// Hasher for lookup3 implementation of the 32-bit hashing algorithm (`hashlittle`).
+///
+/// The input is buffered until `finish()` is called.
+/// lookup3 mixes the length of the input into its initial state, so it cannot be computed incrementally.
+#[derive(Default)]
+pub struct Lookup3Hasher32 {
+    buffer: Vec<u8>,
+    seed: u32,
+}
+
+impl Lookup3Hasher32 {
+    /// Create a hasher which uses the given seed, like `hashlittle()`.
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            buffer: Vec::new(),
+            seed,
+        }
+    }
+}
+
+impl Hasher for Lookup3Hasher32 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.buffer.extend(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        hashlittle(&self.buffer, self.seed) as u64
+    }
+}
+
+/// Hash builder for lookup3 implementation of the 32-bit hashing algorithm (`hashlittle`).
+#[derive(Clone, Copy, Default)]
+pub struct Lookup3Hash32 {
+    seed: u32,
+}
+
+impl Lookup3Hash32 {
+    /// Create a hash builder whose hashers use the given seed, like `hashlittle()`.
+    pub fn with_seed(seed: u32) -> Self {
+        Self { seed }
+    }
+}
+
+impl BuildHasher for Lookup3Hash32 {
+    type Hasher = Lookup3Hasher32;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_seed(self.seed)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for lookup3 implementation of the 32-bit hashing algorithm (`hashlittle`).
+    #[derive(Clone, Copy)]
+    pub struct Lookup3RandomState32(Lookup3Hash32::with_seed(seed: u32));
+}
+
+/// Hasher for lookup3 implementation of the 32-bit hashing algorithm, reading big-endian words (`hashbig`).
+///
+/// The input is buffered until `finish()` is called.
+/// lookup3 mixes the length of the input into its initial state, so it cannot be computed incrementally.
+#[derive(Default)]
+pub struct Lookup3BigHasher32 {
+    buffer: Vec<u8>,
+    seed: u32,
+}
+
+impl Lookup3BigHasher32 {
+    /// Create a hasher which uses the given seed, like `hashbig()`.
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            buffer: Vec::new(),
+            seed,
+        }
+    }
+}
+
+impl Hasher for Lookup3BigHasher32 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.buffer.extend(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        hashbig(&self.buffer, self.seed) as u64
+    }
+}
+
+/// Hash builder for lookup3 implementation of the 32-bit hashing algorithm, reading big-endian words (`hashbig`).
+#[derive(Clone, Copy, Default)]
+pub struct Lookup3BigHash32 {
+    seed: u32,
+}
+
+impl Lookup3BigHash32 {
+    /// Create a hash builder whose hashers use the given seed, like `hashbig()`.
+    pub fn with_seed(seed: u32) -> Self {
+        Self { seed }
+    }
+}
+
+impl BuildHasher for Lookup3BigHash32 {
+    type Hasher = Lookup3BigHasher32;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_seed(self.seed)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for lookup3 implementation of the 32-bit hashing algorithm, reading big-endian words (`hashbig`).
+    #[derive(Clone, Copy)]
+    pub struct Lookup3BigRandomState32(Lookup3BigHash32::with_seed(seed: u32));
+}
+
+/// Hasher for lookup3 implementation of the 64-bit hashing algorithm (`hashlittle2`).
+///
+/// The input is buffered until `finish()` is called.
+/// lookup3 mixes the length of the input into its initial state, so it cannot be computed incrementally.
+#[derive(Default)]
+pub struct Lookup3Hasher64 {
+    buffer: Vec<u8>,
+    seed: u64,
+}
+
+impl Lookup3Hasher64 {
+    /// Create a hasher which uses the given seed, like `hashlittle2_64()`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            buffer: Vec::new(),
+            seed,
+        }
+    }
+}
+
+impl Hasher for Lookup3Hasher64 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.buffer.extend(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        hashlittle2_64(&self.buffer, self.seed)
+    }
+}
+
+/// Hash builder for lookup3 implementation of the 64-bit hashing algorithm (`hashlittle2`).
+#[derive(Clone, Copy, Default)]
+pub struct Lookup3Hash64 {
+    seed: u64,
+}
+
+impl Lookup3Hash64 {
+    /// Create a hash builder whose hashers use the given seed, like `hashlittle2_64()`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl BuildHasher for Lookup3Hash64 {
+    type Hasher = Lookup3Hasher64;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_seed(self.seed)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for lookup3 implementation of the 64-bit hashing algorithm (`hashlittle2`).
+    #[derive(Clone, Copy)]
+    pub struct Lookup3RandomState64(Lookup3Hash64::with_seed(seed: u64));
+}
+
+// The code below is adapted from C code with the following disclaimer
+//-----------------------------------------------------------------------------
+// lookup3.c, by Bob Jenkins, May 2006, Public Domain.
+//
+// You can use this free for any purpose.  It's in the public domain.
+// It has no warranty.
+
+/// lookup3 implementation of the 32-bit hashing algorithm (`hashlittle`).
+pub struct Lookup3;
+
+impl HashAlgorithm for Lookup3 {
+    const NAME: &'static str = "lookup3";
+    type Seed = u32;
+    type Output = u32;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hashlittle(v, 0)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hashlittle(v, seed)
+    }
+}
+
+/// lookup3 implementation of the 32-bit hashing algorithm, reading big-endian words (`hashbig`).
+pub struct Lookup3Big;
+
+impl HashAlgorithm for Lookup3Big {
+    const NAME: &'static str = "lookup3_big";
+    type Seed = u32;
+    type Output = u32;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hashbig(v, 0)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hashbig(v, seed)
+    }
+}
+
+/// lookup3 implementation of the 64-bit hashing algorithm (`hashlittle2`).
+pub struct Lookup3_64;
+
+impl HashAlgorithm for Lookup3_64 {
+    const NAME: &'static str = "lookup3_64";
+    type Seed = u64;
+    type Output = u64;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hashlittle2_64(v, 0)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hashlittle2_64(v, seed)
+    }
+}
+
+/// lookup3 implementation of the 32-bit hashing algorithm (`hashlittle`).
+/// `initval` is the seed, any 4-byte value.
+///
+/// The input is read as little-endian words, which is what the reference implementation does
+/// on little-endian machines.
+pub fn hashlittle<T: AsRef<[u8]>>(v: T, initval: u32) -> u32 {
+    hashlittle2(v, initval, 0).0
+}
+
+/// lookup3 implementation returning two 32-bit hash values (`hashlittle2`).
+/// `pc` is the primary seed and `pb` the secondary one, the result is `(c, b)`.
+///
+/// `c` is the same as `hashlittle(v, pc)` when `pb` is 0, and `b` is a second, slightly worse hash.
+pub fn hashlittle2<T: AsRef<[u8]>>(v: T, pc: u32, pb: u32) -> (u32, u32) {
+    hash_bytes(v.as_ref(), pc, pb, fetch32_le)
+}
+
+/// lookup3 implementation of the 64-bit hashing algorithm, made of the two results of
+/// `hashlittle2()` as suggested by the reference implementation.
+/// The low 32 bits of the seed are `pc` and the high 32 bits are `pb`.
+pub fn hashlittle2_64<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
+    let (c, b) = hashlittle2(v, seed as u32, (seed >> 32) as u32);
+    (b as u64) << 32 | c as u64
+}
+
+/// lookup3 implementation of the 32-bit hashing algorithm, reading big-endian words (`hashbig`).
+/// `initval` is the seed, any 4-byte value.
+///
+/// This is what `hashlittle()` computes on big-endian machines.
+pub fn hashbig<T: AsRef<[u8]>>(v: T, initval: u32) -> u32 {
+    hash_bytes(v.as_ref(), initval, 0, fetch32_be).0
+}
+
+/// lookup3 implementation of the 32-bit hashing algorithm for an array of words (`hashword`).
+/// `initval` is the seed, any 4-byte value.
+///
+/// The result is the same as `hashlittle()` of the words in little-endian byte order.
+pub fn hashword(k: &[u32], initval: u32) -> u32 {
+    hashword2(k, initval, 0).0
+}
+
+/// lookup3 implementation returning two 32-bit hash values for an array of words (`hashword2`).
+/// `pc` is the primary seed and `pb` the secondary one, the result is `(c, b)`.
+pub fn hashword2(k: &[u32], pc: u32, pb: u32) -> (u32, u32) {
+    let mut a = 0xdeadbeef_u32
+        .wrapping_add((k.len() as u32) << 2)
+        .wrapping_add(pc);
+    let mut b = a;
+    let mut c = a.wrapping_add(pb);
+
+    let mut rest = k;
+    while rest.len() > 3 {
+        a = a.wrapping_add(rest[0]);
+        b = b.wrapping_add(rest[1]);
+        c = c.wrapping_add(rest[2]);
+        let mixed = mix(a, b, c);
+        a = mixed.0;
+        b = mixed.1;
+        c = mixed.2;
+        rest = &rest[3..];
+    }
+    // zero length requires no mixing
+    if rest.is_empty() {
+        return (c, b);
+    }
+    let mut block = [0; 3];
+    block[..rest.len()].copy_from_slice(rest);
+    let (_, b, c) = final_mix(
+        a.wrapping_add(block[0]),
+        b.wrapping_add(block[1]),
+        c.wrapping_add(block[2]),
+    );
+    (c, b)
+}
+
+fn hash_bytes(data: &[u8], pc: u32, pb: u32, fetch: fn(&[u8], usize) -> u32) -> (u32, u32) {
+    let mut a = 0xdeadbeef_u32
+        .wrapping_add(data.len() as u32)
+        .wrapping_add(pc);
+    let mut b = a;
+    let mut c = a.wrapping_add(pb);
+
+    let mut rest = data;
+    while rest.len() > 12 {
+        a = a.wrapping_add(fetch(rest, 0));
+        b = b.wrapping_add(fetch(rest, 4));
+        c = c.wrapping_add(fetch(rest, 8));
+        let mixed = mix(a, b, c);
+        a = mixed.0;
+        b = mixed.1;
+        c = mixed.2;
+        rest = &rest[12..];
+    }
+    // zero length strings require no mixing
+    if rest.is_empty() {
+        return (c, b);
+    }
+    // the last 1 to 12 bytes, zero-padded
+    let mut block = [0; 12];
+    block[..rest.len()].copy_from_slice(rest);
+    let (_, b, c) = final_mix(
+        a.wrapping_add(fetch(&block, 0)),
+        b.wrapping_add(fetch(&block, 4)),
+        c.wrapping_add(fetch(&block, 8)),
+    );
+    (c, b)
+}
+
+// mix 3 32-bit values reversibly
+#[inline(always)]
+pub(crate) fn mix(mut a: u32, mut b: u32, mut c: u32) -> (u32, u32, u32) {
+    a = a.wrapping_sub(c);
+    a ^= c.rotate_left(4);
+    c = c.wrapping_add(b);
+    b = b.wrapping_sub(a);
+    b ^= a.rotate_left(6);
+    a = a.wrapping_add(c);
+    c = c.wrapping_sub(b);
+    c ^= b.rotate_left(8);
+    b = b.wrapping_add(a);
+    a = a.wrapping_sub(c);
+    a ^= c.rotate_left(16);
+    c = c.wrapping_add(b);
+    b = b.wrapping_sub(a);
+    b ^= a.rotate_left(19);
+    a = a.wrapping_add(c);
+    c = c.wrapping_sub(b);
+    c ^= b.rotate_left(4);
+    b = b.wrapping_add(a);
+    (a, b, c)
+}
+
+// final mixing of 3 32-bit values into c
+#[inline(always)]
+pub(crate) fn final_mix(mut a: u32, mut b: u32, mut c: u32) -> (u32, u32, u32) {
+    c ^= b;
+    c = c.wrapping_sub(b.rotate_left(14));
+    a ^= c;
+    a = a.wrapping_sub(c.rotate_left(11));
+    b ^= a;
+    b = b.wrapping_sub(a.rotate_left(25));
+    c ^= b;
+    c = c.wrapping_sub(b.rotate_left(16));
+    a ^= c;
+    a = a.wrapping_sub(c.rotate_left(4));
+    b ^= a;
+    b = b.wrapping_sub(a.rotate_left(14));
+    c ^= b;
+    c = c.wrapping_sub(b.rotate_left(24));
+    (a, b, c)
+}
+
+#[inline(always)]
+pub(crate) fn fetch32_le(data: &[u8], i: usize) -> u32 {
+    let buf = [data[i], data[i + 1], data[i + 2], data[i + 3]];
+    u32::from_le_bytes(buf)
+}
+
+#[inline(always)]
+fn fetch32_be(data: &[u8], i: usize) -> u32 {
+    let buf = [data[i], data[i + 1], data[i + 2], data[i + 3]];
+    u32::from_be_bytes(buf)
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    #[test]
+    fn compliance_test() {
+        use crate::jenkins::lookup3::{hashlittle, hashlittle2};
+        // test vectors from the driver of the reference implementation
+        let input = "Four score and seven years ago";
+        assert_eq!(hashlittle("", 0), 0xdeadbeef);
+        assert_eq!(hashlittle(input, 0), 0x17770551);
+        assert_eq!(hashlittle(input, 1), 0xcd628161);
+        assert_eq!(hashlittle2("", 0, 0), (0xdeadbeef, 0xdeadbeef));
+        assert_eq!(hashlittle2("", 0, 0xdeadbeef), (0xbd5b7dde, 0xdeadbeef));
+        assert_eq!(
+            hashlittle2("", 0xdeadbeef, 0xdeadbeef),
+            (0x9c093ccd, 0xbd5b7dde)
+        );
+        assert_eq!(hashlittle2(input, 0, 0), (0x17770551, 0xce7226e6));
+        assert_eq!(hashlittle2(input, 0, 1), (0xe3607cae, 0xbd371de4));
+        assert_eq!(hashlittle2(input, 1, 0), (0xcd628161, 0x6cbea4b3));
+    }
+
+    #[test]
+    fn verification_test() {
+        // SMHasher's verification value: hash keys of the form {0, 1, 2, ...} with lengths 0 to 255
+        // and seeds 256 - length, then hash the concatenated results
+        let key: Vec<u8> = (0..=255).collect();
+        let mut hashes = Vec::new();
+        for i in 0..256 {
+            hashes.extend(&crate::jenkins::hashlittle(&key[..i], 256 - i as u32).to_le_bytes());
+        }
+        assert_eq!(crate::jenkins::hashlittle(&hashes, 0), 0x3D83917A);
+    }
+
+    #[test]
+    fn hashword_test() {
+        // hashword and hashbig are hashlittle of the words in little-endian and big-endian order
+        let words: Vec<u32> = (0..20).map(|i| 0x01020304 * i + 0x9e3779b9).collect();
+        for len in 0..words.len() {
+            let le: Vec<u8> = words[..len].iter().flat_map(|w| w.to_le_bytes()).collect();
+            let be: Vec<u8> = words[..len].iter().flat_map(|w| w.to_be_bytes()).collect();
+            assert_eq!(
+                crate::jenkins::hashword(&words[..len], 4919),
+                crate::jenkins::hashlittle(&le, 4919)
+            );
+            assert_eq!(
+                crate::jenkins::hashword2(&words[..len], 4919, 77),
+                crate::jenkins::hashlittle2(&le, 4919, 77)
+            );
+            assert_eq!(
+                crate::jenkins::hashbig(&be, 4919),
+                crate::jenkins::hashlittle(&le, 4919)
+            );
+        }
+    }
+
+    #[test]
+    fn hashbig_test() {
+        // test vectors from hashbig() of the reference implementation, for inputs which end in
+        // a partial word
+        let input = "Four score and seven years ago";
+        let expected = [
+            (1, 0xefe3832d, 0x46bbf330),
+            (2, 0xe0f1e07b, 0xbed246ba),
+            (3, 0x37e62716, 0xa9c153a8),
+            (5, 0x1e034b08, 0x8b377c7e),
+            (6, 0x3fce54f3, 0x3abb4f60),
+            (7, 0x3f1e516e, 0x5d503efa),
+            (9, 0xae927886, 0xfad077ee),
+            (10, 0x1ab02fb9, 0xdef722c9),
+            (11, 0xee59ba2f, 0x0c2b1fc8),
+            (13, 0xf1d980c0, 0x5aff00b2),
+            (14, 0x02cf71e9, 0x81ad87b2),
+            (15, 0x21133354, 0x4171624a),
+            (17, 0x4e683fc2, 0xdab9360f),
+            (18, 0x9ffd0a12, 0x737f7a10),
+            (19, 0xbc5059be, 0xc418ecd5),
+            (21, 0xb0055ddb, 0x2e726d9c),
+            (22, 0xf27677fa, 0xcd02d270),
+            (23, 0x57120df9, 0xd817cf19),
+            (25, 0x0739fa16, 0x4d1c6ffb),
+            (26, 0x65e2872e, 0xfebbfc65),
+            (27, 0xd3addf3f, 0xb87b5cd0),
+            (29, 0x4d10c45d, 0xf6673d1f),
+            (30, 0x65e759cb, 0x0b89cd74),
+        ];
+        for &(len, hash, seeded) in expected.iter() {
+            assert_eq!(crate::jenkins::hashbig(&input[..len], 0), hash, "{}", len);
+            assert_eq!(
+                crate::jenkins::hashbig(&input[..len], 4919),
+                seeded,
+                "{}",
+                len
+            );
+        }
+    }
+
+    #[test]
+    fn seeded_builder_test() {
+        use std::hash::BuildHasher;
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let mut hasher = crate::jenkins::Lookup3Hash32::with_seed(4919).build_hasher();
+        for chunk in input.as_bytes().chunks(7) {
+            hasher.write(chunk);
+        }
+        assert_eq!(
+            hasher.finish(),
+            crate::jenkins::hashlittle(input, 4919) as u64
+        );
+        let mut hasher = crate::jenkins::Lookup3Hash64::with_seed(77 << 32 | 4919).build_hasher();
+        hasher.write(input.as_bytes());
+        let (c, b) = crate::jenkins::hashlittle2(input, 4919, 77);
+        assert_eq!(hasher.finish(), (b as u64) << 32 | c as u64);
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn fasthash_interop_test() {
+        use fasthash::FastHash;
+        let input: Vec<u8> = (0..100u32).map(|i| (i * 7 + 3) as u8).collect();
+        for len in 0..input.len() {
+            assert_eq!(
+                crate::jenkins::hashlittle(&input[..len], 4919),
+                fasthash::lookup3::Hash32::hash_with_seed(&input[..len], 4919)
+            );
+        }
+    }
+}
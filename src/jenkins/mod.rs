@@ -0,0 +1,19 @@
+//! lookup3, lookup2 and one-at-a-time algorithms by Bob Jenkins.
+//!
+//! The lookup3 and lookup2 functions keep the names and arguments of the reference implementation.
+mod lookup2;
+mod lookup3;
+mod one_at_a_time;
+
+pub(crate) use lookup3::{fetch32_le, final_mix, mix};
+
+pub use lookup2::{lookup2, Lookup2, Lookup2Hash32, Lookup2Hasher32, Lookup2RandomState32};
+pub use lookup3::{
+    hashbig, hashlittle, hashlittle2, hashlittle2_64, hashword, hashword2, Lookup3, Lookup3Big,
+    Lookup3BigHash32, Lookup3BigHasher32, Lookup3BigRandomState32, Lookup3Hash32, Lookup3Hash64,
+    Lookup3Hasher32, Lookup3Hasher64, Lookup3RandomState32, Lookup3RandomState64, Lookup3_64,
+};
+pub use one_at_a_time::{
+    jenkins_one_at_a_time, jenkins_one_at_a_time_with_seed, OneAtATime, OneAtATimeHash32,
+    OneAtATimeHasher32, OneAtATimeRandomState32,
+};
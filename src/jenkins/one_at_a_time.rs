@@ -0,0 +1,136 @@
+use std::hash::{BuildHasher, Hasher};
+
+use crate::HashAlgorithm;
+
+/// Hasher for Jenkins one-at-a-time implementation of the 32-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct OneAtATimeHasher32 {
+    hash: u32,
+}
+
+impl OneAtATimeHasher32 {
+    /// Create a hasher which uses the given seed, like `jenkins_one_at_a_time_with_seed()`.
+    pub fn with_seed(seed: u32) -> Self {
+        Self { hash: seed }
+    }
+}
+
+impl Hasher for OneAtATimeHasher32 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.hash = update(self.hash, bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        finalize(self.hash) as u64
+    }
+}
+
+/// Hash builder for Jenkins one-at-a-time implementation of the 32-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct OneAtATimeHash32 {
+    seed: u32,
+}
+
+impl OneAtATimeHash32 {
+    /// Create a hash builder whose hashers use the given seed.
+    pub fn with_seed(seed: u32) -> Self {
+        Self { seed }
+    }
+}
+
+impl BuildHasher for OneAtATimeHash32 {
+    type Hasher = OneAtATimeHasher32;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_seed(self.seed)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for Jenkins one-at-a-time implementation of the 32-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct OneAtATimeRandomState32(OneAtATimeHash32::with_seed(seed: u32));
+}
+
+// The code below is adapted from C code with the following disclaimer
+//-----------------------------------------------------------------------------
+// By Bob Jenkins, 1996. hash.c, Public Domain.
+
+/// Jenkins one-at-a-time implementation of the 32-bit hashing algorithm.
+pub struct OneAtATime;
+
+impl HashAlgorithm for OneAtATime {
+    const NAME: &'static str = "one_at_a_time";
+    type Seed = u32;
+    type Output = u32;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        jenkins_one_at_a_time(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        jenkins_one_at_a_time_with_seed(v, seed)
+    }
+}
+
+/// Jenkins one-at-a-time implementation of the 32-bit hashing algorithm.
+/// This version allows you to specify a seed, which is the initial value of the hash.
+pub fn jenkins_one_at_a_time_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
+    finalize(update(seed, v.as_ref()))
+}
+
+/// Jenkins one-at-a-time implementation of the 32-bit hashing algorithm.
+/// The seed is always 0 in this version.
+pub fn jenkins_one_at_a_time<T: AsRef<[u8]>>(v: T) -> u32 {
+    jenkins_one_at_a_time_with_seed(v, 0)
+}
+
+#[inline(always)]
+fn update(hash: u32, data: &[u8]) -> u32 {
+    data.iter().fold(hash, |mut hash, &byte| {
+        hash = hash.wrapping_add(byte as u32);
+        hash = hash.wrapping_add(hash << 10);
+        hash ^ (hash >> 6)
+    })
+}
+
+#[inline(always)]
+fn finalize(mut hash: u32) -> u32 {
+    hash = hash.wrapping_add(hash << 3);
+    hash ^= hash >> 11;
+    hash.wrapping_add(hash << 15)
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    #[test]
+    fn compliance_test() {
+        let expected = [
+            ("a", 0xca2e9442),
+            ("The quick brown fox jumps over the lazy dog", 0x519e91f5),
+        ];
+        for &(input, hash) in expected.iter() {
+            assert_eq!(
+                crate::jenkins::one_at_a_time::jenkins_one_at_a_time(input),
+                hash,
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn streaming_test() {
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let mut hasher = crate::jenkins::OneAtATimeHasher32::with_seed(4919);
+        for chunk in input.as_bytes().chunks(7) {
+            hasher.write(chunk);
+        }
+        assert_eq!(
+            hasher.finish(),
+            crate::jenkins::one_at_a_time::jenkins_one_at_a_time_with_seed(input, 4919) as u64
+        );
+    }
+}
@@ -1,8 +1,61 @@
 //! High speed hashing algorithms.
 //! Algorithms translated into Rust from C++ source found here: https://github.com/rurban/smhasher
+//!
+//! # Random seeds
+//!
+//! The `RandomState` hash builders draw a new seed or key for every instance. It comes from
+//! `std::collections::hash_map::RandomState`, through `random::random_u64()`, and std draws its
+//! keys from OS entropy.
+//!
+//! SipHash and HighwayHash are keyed hashes, designed so that their output reveals nothing about a
+//! secret key. Their random state builders make hash tables resistant to HashDoS attacks.
+//!
+//! The other algorithms are not designed to hide their seed, and several of them (MurmurHash,
+//! CityHash, the Jenkins hashes, ...) have collisions which do not depend on the seed at all.
+//! Many also take a 32-bit seed, so their builders truncate the random value to 32 bits.
+//! A random seed therefore only stops collisions precomputed for one fixed seed.
 #![warn(missing_docs)]
 
 pub mod city;
+pub mod compat;
+pub mod farm;
+pub mod fnv;
+pub mod highway;
+pub mod jenkins;
+pub mod metro;
 pub mod murmur;
+mod random;
+pub mod registry;
+pub mod sip;
+pub mod spooky;
+pub mod wy;
+pub mod xxhash;
 
 pub use murmur::Murmur3Hasher32 as Murmur3Hasher;
+
+/// A hashing algorithm, described by its one-shot hash functions.
+///
+/// Every algorithm in this crate has a marker type implementing this trait,
+/// so code can be generic over the hash family.
+pub trait HashAlgorithm {
+    /// Canonical name of the algorithm.
+    const NAME: &'static str;
+    /// Type of the seed accepted by `hash_with_seed()`.
+    type Seed: Copy;
+    /// Type of the hash value.
+    type Output: Copy + Eq + std::fmt::Debug;
+
+    /// Hash the data the same way as the algorithm's unseeded function.
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output;
+
+    /// Hash the data using the given seed.
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output;
+}
+
+/// A `Hasher` which produces 128-bit hashes.
+///
+/// `Hasher::finish()` truncates the hash to its lower 64 bits, while `finish128()` returns all of it.
+pub trait Hasher128: std::hash::Hasher {
+    /// Returns the full 128-bit hash value for the values written.
+    fn finish128(&self) -> u128;
+}
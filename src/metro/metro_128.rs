@@ -0,0 +1,396 @@
+use std::hash::{BuildHasher, Hasher};
+
+use super::metro_64::{bulk, fetch16, fetch32, fetch64, mul_add, rotr};
+use crate::{HashAlgorithm, Hasher128};
+
+/// Hasher for MetroHash implementation of the 128-bit hashing algorithm, variant 1.
+///
+/// MetroHash mixes the input length into its initial state, so the input is buffered until
+/// `finish()` is called.
+#[derive(Default)]
+pub struct MetroHasher128_1 {
+    buffer: Vec<u8>,
+    seed: u32,
+}
+
+impl MetroHasher128_1 {
+    /// Create a hasher which uses the given seed, like `hash128_1_with_seed()`.
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            buffer: Vec::new(),
+            seed,
+        }
+    }
+}
+
+impl Hasher for MetroHasher128_1 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.buffer.extend(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        self.finish128() as u64
+    }
+}
+
+impl Hasher128 for MetroHasher128_1 {
+    fn finish128(&self) -> u128 {
+        hash128_1_with_seed(&self.buffer, self.seed)
+    }
+}
+
+/// Hash builder for MetroHash implementation of the 128-bit hashing algorithm, variant 1.
+#[derive(Clone, Copy, Default)]
+pub struct MetroHash128_1 {
+    seed: u32,
+}
+
+impl MetroHash128_1 {
+    /// Create a hash builder whose hashers use the given seed, like `hash128_1_with_seed()`.
+    pub fn with_seed(seed: u32) -> Self {
+        Self { seed }
+    }
+}
+
+impl BuildHasher for MetroHash128_1 {
+    type Hasher = MetroHasher128_1;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_seed(self.seed)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for MetroHash implementation of the 128-bit hashing algorithm,
+    /// variant 1.
+    #[derive(Clone, Copy)]
+    pub struct MetroRandomState128_1(MetroHash128_1::with_seed(seed: u32));
+}
+
+/// Hasher for MetroHash implementation of the 128-bit hashing algorithm, variant 2.
+///
+/// MetroHash mixes the input length into its initial state, so the input is buffered until
+/// `finish()` is called.
+#[derive(Default)]
+pub struct MetroHasher128_2 {
+    buffer: Vec<u8>,
+    seed: u32,
+}
+
+impl MetroHasher128_2 {
+    /// Create a hasher which uses the given seed, like `hash128_2_with_seed()`.
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            buffer: Vec::new(),
+            seed,
+        }
+    }
+}
+
+impl Hasher for MetroHasher128_2 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.buffer.extend(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        self.finish128() as u64
+    }
+}
+
+impl Hasher128 for MetroHasher128_2 {
+    fn finish128(&self) -> u128 {
+        hash128_2_with_seed(&self.buffer, self.seed)
+    }
+}
+
+/// Hash builder for MetroHash implementation of the 128-bit hashing algorithm, variant 2.
+#[derive(Clone, Copy, Default)]
+pub struct MetroHash128_2 {
+    seed: u32,
+}
+
+impl MetroHash128_2 {
+    /// Create a hash builder whose hashers use the given seed, like `hash128_2_with_seed()`.
+    pub fn with_seed(seed: u32) -> Self {
+        Self { seed }
+    }
+}
+
+impl BuildHasher for MetroHash128_2 {
+    type Hasher = MetroHasher128_2;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_seed(self.seed)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for MetroHash implementation of the 128-bit hashing algorithm,
+    /// variant 2.
+    #[derive(Clone, Copy)]
+    pub struct MetroRandomState128_2(MetroHash128_2::with_seed(seed: u32));
+}
+
+// The code below is adapted from C++ code with the following license
+//-----------------------------------------------------------------------------
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 J. Andrew Rogers
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// MetroHash implementation of the 128-bit hashing algorithm, variant 1.
+pub struct Metro128_1;
+
+impl HashAlgorithm for Metro128_1 {
+    const NAME: &'static str = "metro128_1";
+    type Seed = u32;
+    type Output = u128;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hash128_1(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hash128_1_with_seed(v, seed)
+    }
+}
+
+/// MetroHash implementation of the 128-bit hashing algorithm, variant 1.
+/// This version allows you to specify a seed.
+pub fn hash128_1_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u128 {
+    const K0: u64 = 0xC83A91E1;
+    const K1: u64 = 0x8648DBDB;
+    const K2: u64 = 0x7BDEC03B;
+    const K3: u64 = 0x2F5870A5;
+
+    let data = v.as_ref();
+    let len = data.len() as u64;
+    let seed = seed as u64;
+    let mut v = [
+        seed.wrapping_sub(K0).wrapping_mul(K3).wrapping_add(len),
+        seed.wrapping_add(K1).wrapping_mul(K2).wrapping_add(len),
+        0,
+        0,
+    ];
+
+    let mut rest = data;
+    if data.len() >= 32 {
+        v[2] = seed.wrapping_add(K0).wrapping_mul(K2).wrapping_add(len);
+        v[3] = seed.wrapping_sub(K1).wrapping_mul(K3).wrapping_add(len);
+        let (lanes, tail) = bulk(data, v, [K0, K1, K2, K3]);
+        v = lanes;
+        rest = tail;
+        v[2] ^= rotr(mul_add(v[0].wrapping_add(v[3]), K0, v[1]), 26).wrapping_mul(K1);
+        v[3] ^= rotr(mul_add(v[1].wrapping_add(v[2]), K1, v[0]), 26).wrapping_mul(K0);
+        v[0] ^= rotr(mul_add(v[0].wrapping_add(v[2]), K0, v[3]), 26).wrapping_mul(K1);
+        v[1] ^= rotr(mul_add(v[1].wrapping_add(v[3]), K1, v[2]), 30).wrapping_mul(K0);
+    }
+
+    if rest.len() >= 16 {
+        v[0] = rotr(mul_add(fetch64(rest, 0), K2, v[0]), 33).wrapping_mul(K3);
+        v[1] = rotr(mul_add(fetch64(rest, 8), K2, v[1]), 33).wrapping_mul(K3);
+        v[0] ^= rotr(mul_add(v[0], K2, v[1]), 17).wrapping_mul(K1);
+        v[1] ^= rotr(mul_add(v[1], K3, v[0]), 17).wrapping_mul(K0);
+        rest = &rest[16..];
+    }
+
+    if rest.len() >= 8 {
+        v[0] = rotr(mul_add(fetch64(rest, 0), K2, v[0]), 33).wrapping_mul(K3);
+        v[0] ^= rotr(mul_add(v[0], K2, v[1]), 20).wrapping_mul(K1);
+        rest = &rest[8..];
+    }
+
+    if rest.len() >= 4 {
+        v[1] = rotr(mul_add(fetch32(rest, 0), K2, v[1]), 33).wrapping_mul(K3);
+        v[1] ^= rotr(mul_add(v[1], K3, v[0]), 18).wrapping_mul(K0);
+        rest = &rest[4..];
+    }
+
+    if rest.len() >= 2 {
+        v[0] = rotr(mul_add(fetch16(rest, 0), K2, v[0]), 33).wrapping_mul(K3);
+        v[0] ^= rotr(mul_add(v[0], K2, v[1]), 24).wrapping_mul(K1);
+        rest = &rest[2..];
+    }
+
+    if let Some(&byte) = rest.first() {
+        v[1] = rotr(mul_add(byte as u64, K2, v[1]), 33).wrapping_mul(K3);
+        v[1] ^= rotr(mul_add(v[1], K3, v[0]), 24).wrapping_mul(K0);
+    }
+
+    v[0] = v[0].wrapping_add(rotr(mul_add(v[0], K0, v[1]), 13));
+    v[1] = v[1].wrapping_add(rotr(mul_add(v[1], K1, v[0]), 37));
+    v[0] = v[0].wrapping_add(rotr(mul_add(v[0], K2, v[1]), 13));
+    v[1] = v[1].wrapping_add(rotr(mul_add(v[1], K3, v[0]), 37));
+    to_u128(v[0], v[1])
+}
+
+/// MetroHash implementation of the 128-bit hashing algorithm, variant 1.
+/// The seed is always 0 in this version.
+pub fn hash128_1<T: AsRef<[u8]>>(v: T) -> u128 {
+    hash128_1_with_seed(v, 0)
+}
+
+/// MetroHash implementation of the 128-bit hashing algorithm, variant 2.
+pub struct Metro128_2;
+
+impl HashAlgorithm for Metro128_2 {
+    const NAME: &'static str = "metro128_2";
+    type Seed = u32;
+    type Output = u128;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hash128_2(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hash128_2_with_seed(v, seed)
+    }
+}
+
+/// MetroHash implementation of the 128-bit hashing algorithm, variant 2.
+/// This version allows you to specify a seed.
+pub fn hash128_2_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u128 {
+    const K0: u64 = 0xD6D018F5;
+    const K1: u64 = 0xA2AA033B;
+    const K2: u64 = 0x62992FC1;
+    const K3: u64 = 0x30BC5B29;
+
+    let data = v.as_ref();
+    let len = data.len() as u64;
+    let seed = seed as u64;
+    let mut v = [
+        seed.wrapping_sub(K0).wrapping_mul(K3).wrapping_add(len),
+        seed.wrapping_add(K1).wrapping_mul(K2).wrapping_add(len),
+        0,
+        0,
+    ];
+
+    let mut rest = data;
+    if data.len() >= 32 {
+        v[2] = seed.wrapping_add(K0).wrapping_mul(K2).wrapping_add(len);
+        v[3] = seed.wrapping_sub(K1).wrapping_mul(K3).wrapping_add(len);
+        let (lanes, tail) = bulk(data, v, [K0, K1, K2, K3]);
+        v = lanes;
+        rest = tail;
+        v[2] ^= rotr(mul_add(v[0].wrapping_add(v[3]), K0, v[1]), 33).wrapping_mul(K1);
+        v[3] ^= rotr(mul_add(v[1].wrapping_add(v[2]), K1, v[0]), 33).wrapping_mul(K0);
+        v[0] ^= rotr(mul_add(v[0].wrapping_add(v[2]), K0, v[3]), 33).wrapping_mul(K1);
+        v[1] ^= rotr(mul_add(v[1].wrapping_add(v[3]), K1, v[2]), 33).wrapping_mul(K0);
+    }
+
+    if rest.len() >= 16 {
+        v[0] = rotr(mul_add(fetch64(rest, 0), K2, v[0]), 29).wrapping_mul(K3);
+        v[1] = rotr(mul_add(fetch64(rest, 8), K2, v[1]), 29).wrapping_mul(K3);
+        v[0] ^= rotr(mul_add(v[0], K2, v[1]), 29).wrapping_mul(K1);
+        v[1] ^= rotr(mul_add(v[1], K3, v[0]), 29).wrapping_mul(K0);
+        rest = &rest[16..];
+    }
+
+    if rest.len() >= 8 {
+        v[0] = rotr(mul_add(fetch64(rest, 0), K2, v[0]), 29).wrapping_mul(K3);
+        v[0] ^= rotr(mul_add(v[0], K2, v[1]), 29).wrapping_mul(K1);
+        rest = &rest[8..];
+    }
+
+    if rest.len() >= 4 {
+        v[1] = rotr(mul_add(fetch32(rest, 0), K2, v[1]), 29).wrapping_mul(K3);
+        v[1] ^= rotr(mul_add(v[1], K3, v[0]), 25).wrapping_mul(K0);
+        rest = &rest[4..];
+    }
+
+    if rest.len() >= 2 {
+        v[0] = rotr(mul_add(fetch16(rest, 0), K2, v[0]), 29).wrapping_mul(K3);
+        v[0] ^= rotr(mul_add(v[0], K2, v[1]), 30).wrapping_mul(K1);
+        rest = &rest[2..];
+    }
+
+    if let Some(&byte) = rest.first() {
+        v[1] = rotr(mul_add(byte as u64, K2, v[1]), 29).wrapping_mul(K3);
+        v[1] ^= rotr(mul_add(v[1], K3, v[0]), 18).wrapping_mul(K0);
+    }
+
+    v[0] = v[0].wrapping_add(rotr(mul_add(v[0], K0, v[1]), 33));
+    v[1] = v[1].wrapping_add(rotr(mul_add(v[1], K1, v[0]), 33));
+    v[0] = v[0].wrapping_add(rotr(mul_add(v[0], K2, v[1]), 33));
+    v[1] = v[1].wrapping_add(rotr(mul_add(v[1], K3, v[0]), 33));
+    to_u128(v[0], v[1])
+}
+
+/// MetroHash implementation of the 128-bit hashing algorithm, variant 2.
+/// The seed is always 0 in this version.
+pub fn hash128_2<T: AsRef<[u8]>>(v: T) -> u128 {
+    hash128_2_with_seed(v, 0)
+}
+
+// the reference implementation writes v[0] and v[1] to memory in this order
+#[inline(always)]
+fn to_u128(low: u64, high: u64) -> u128 {
+    (high as u128) << 64 | low as u128
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Hasher128;
+    use std::hash::Hasher;
+
+    #[test]
+    fn compliance_test() {
+        use crate::metro::{verification, Metro128_1, Metro128_2};
+        assert_eq!(verification::<Metro128_1>(), 0x20E8A1D7);
+        assert_eq!(verification::<Metro128_2>(), 0x5437C684);
+    }
+
+    #[test]
+    fn seeded_builder_test() {
+        use std::hash::BuildHasher;
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let mut hasher = crate::metro::MetroHash128_1::with_seed(4919).build_hasher();
+        for chunk in input.as_bytes().chunks(7) {
+            hasher.write(chunk);
+        }
+        let expected = crate::metro::metro_128::hash128_1_with_seed(input, 4919);
+        assert_eq!(hasher.finish(), expected as u64);
+        assert_eq!(hasher.finish128(), expected);
+        let mut hasher = crate::metro::MetroHash128_2::with_seed(4919).build_hasher();
+        hasher.write(input.as_bytes());
+        assert_eq!(
+            hasher.finish128(),
+            crate::metro::metro_128::hash128_2_with_seed(input, 4919)
+        );
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn fasthash_interop_test() {
+        use fasthash::FastHash;
+        let input: Vec<u8> = (0..100u32).map(|i| (i * 7 + 3) as u8).collect();
+        let seed = 4919;
+        for len in 0..input.len() {
+            assert_eq!(
+                crate::metro::metro_128::hash128_1_with_seed(&input[..len], seed),
+                fasthash::metro::Hash128_1::hash_with_seed(&input[..len], seed)
+            );
+            assert_eq!(
+                crate::metro::metro_128::hash128_2_with_seed(&input[..len], seed),
+                fasthash::metro::Hash128_2::hash_with_seed(&input[..len], seed)
+            );
+        }
+    }
+}
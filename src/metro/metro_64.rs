@@ -0,0 +1,421 @@
+use std::hash::{BuildHasher, Hasher};
+
+use crate::HashAlgorithm;
+
+/// Hasher for MetroHash implementation of the 64-bit hashing algorithm, variant 1.
+///
+/// MetroHash mixes the input length into its initial state, so the input is buffered until
+/// `finish()` is called.
+#[derive(Default)]
+pub struct MetroHasher64_1 {
+    buffer: Vec<u8>,
+    seed: u32,
+}
+
+impl MetroHasher64_1 {
+    /// Create a hasher which uses the given seed, like `hash64_1_with_seed()`.
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            buffer: Vec::new(),
+            seed,
+        }
+    }
+}
+
+impl Hasher for MetroHasher64_1 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.buffer.extend(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        hash64_1_with_seed(&self.buffer, self.seed)
+    }
+}
+
+/// Hash builder for MetroHash implementation of the 64-bit hashing algorithm, variant 1.
+#[derive(Clone, Copy, Default)]
+pub struct MetroHash64_1 {
+    seed: u32,
+}
+
+impl MetroHash64_1 {
+    /// Create a hash builder whose hashers use the given seed, like `hash64_1_with_seed()`.
+    pub fn with_seed(seed: u32) -> Self {
+        Self { seed }
+    }
+}
+
+impl BuildHasher for MetroHash64_1 {
+    type Hasher = MetroHasher64_1;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_seed(self.seed)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for MetroHash implementation of the 64-bit hashing algorithm,
+    /// variant 1.
+    #[derive(Clone, Copy)]
+    pub struct MetroRandomState64_1(MetroHash64_1::with_seed(seed: u32));
+}
+
+/// Hasher for MetroHash implementation of the 64-bit hashing algorithm, variant 2.
+///
+/// MetroHash mixes the input length into its initial state, so the input is buffered until
+/// `finish()` is called.
+#[derive(Default)]
+pub struct MetroHasher64_2 {
+    buffer: Vec<u8>,
+    seed: u32,
+}
+
+impl MetroHasher64_2 {
+    /// Create a hasher which uses the given seed, like `hash64_2_with_seed()`.
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            buffer: Vec::new(),
+            seed,
+        }
+    }
+}
+
+impl Hasher for MetroHasher64_2 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.buffer.extend(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        hash64_2_with_seed(&self.buffer, self.seed)
+    }
+}
+
+/// Hash builder for MetroHash implementation of the 64-bit hashing algorithm, variant 2.
+#[derive(Clone, Copy, Default)]
+pub struct MetroHash64_2 {
+    seed: u32,
+}
+
+impl MetroHash64_2 {
+    /// Create a hash builder whose hashers use the given seed, like `hash64_2_with_seed()`.
+    pub fn with_seed(seed: u32) -> Self {
+        Self { seed }
+    }
+}
+
+impl BuildHasher for MetroHash64_2 {
+    type Hasher = MetroHasher64_2;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_seed(self.seed)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for MetroHash implementation of the 64-bit hashing algorithm,
+    /// variant 2.
+    #[derive(Clone, Copy)]
+    pub struct MetroRandomState64_2(MetroHash64_2::with_seed(seed: u32));
+}
+
+// The code below is adapted from C++ code with the following license
+//-----------------------------------------------------------------------------
+// The MIT License (MIT)
+//
+// Copyright (c) 2015 J. Andrew Rogers
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// MetroHash implementation of the 64-bit hashing algorithm, variant 1.
+pub struct Metro64_1;
+
+impl HashAlgorithm for Metro64_1 {
+    const NAME: &'static str = "metro64_1";
+    type Seed = u32;
+    type Output = u64;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hash64_1(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hash64_1_with_seed(v, seed)
+    }
+}
+
+/// MetroHash implementation of the 64-bit hashing algorithm, variant 1.
+/// This version allows you to specify a seed.
+pub fn hash64_1_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u64 {
+    const K0: u64 = 0xC83A91E1;
+    const K1: u64 = 0x8648DBDB;
+    const K2: u64 = 0x7BDEC03B;
+    const K3: u64 = 0x2F5870A5;
+
+    let data = v.as_ref();
+    let mut hash = (seed as u64)
+        .wrapping_add(K2)
+        .wrapping_mul(K0)
+        .wrapping_add(data.len() as u64);
+
+    let mut rest = data;
+    if data.len() >= 32 {
+        let (mut v, tail) = bulk(data, [hash; 4], [K0, K1, K2, K3]);
+        rest = tail;
+        v[2] ^= rotr(mul_add(v[0].wrapping_add(v[3]), K0, v[1]), 33).wrapping_mul(K1);
+        v[3] ^= rotr(mul_add(v[1].wrapping_add(v[2]), K1, v[0]), 33).wrapping_mul(K0);
+        v[0] ^= rotr(mul_add(v[0].wrapping_add(v[2]), K0, v[3]), 33).wrapping_mul(K1);
+        v[1] ^= rotr(mul_add(v[1].wrapping_add(v[3]), K1, v[2]), 33).wrapping_mul(K0);
+        hash = hash.wrapping_add(v[0] ^ v[1]);
+    }
+
+    if rest.len() >= 16 {
+        let mut v0 = hash.wrapping_add(fetch64(rest, 0).wrapping_mul(K0));
+        v0 = rotr(v0, 33).wrapping_mul(K1);
+        let mut v1 = hash.wrapping_add(fetch64(rest, 8).wrapping_mul(K1));
+        v1 = rotr(v1, 33).wrapping_mul(K2);
+        v0 ^= rotr(v0.wrapping_mul(K0), 35).wrapping_add(v1);
+        v1 ^= rotr(v1.wrapping_mul(K3), 35).wrapping_add(v0);
+        hash = hash.wrapping_add(v1);
+        rest = &rest[16..];
+    }
+
+    if rest.len() >= 8 {
+        hash = hash.wrapping_add(fetch64(rest, 0).wrapping_mul(K3));
+        hash ^= rotr(hash, 33).wrapping_mul(K1);
+        rest = &rest[8..];
+    }
+
+    if rest.len() >= 4 {
+        hash = hash.wrapping_add(fetch32(rest, 0).wrapping_mul(K3));
+        hash ^= rotr(hash, 15).wrapping_mul(K1);
+        rest = &rest[4..];
+    }
+
+    if rest.len() >= 2 {
+        hash = hash.wrapping_add(fetch16(rest, 0).wrapping_mul(K3));
+        hash ^= rotr(hash, 13).wrapping_mul(K1);
+        rest = &rest[2..];
+    }
+
+    if let Some(&byte) = rest.first() {
+        hash = hash.wrapping_add((byte as u64).wrapping_mul(K3));
+        hash ^= rotr(hash, 25).wrapping_mul(K1);
+    }
+
+    hash ^= rotr(hash, 33);
+    hash = hash.wrapping_mul(K0);
+    hash ^= rotr(hash, 33);
+    hash
+}
+
+/// MetroHash implementation of the 64-bit hashing algorithm, variant 1.
+/// The seed is always 0 in this version.
+pub fn hash64_1<T: AsRef<[u8]>>(v: T) -> u64 {
+    hash64_1_with_seed(v, 0)
+}
+
+/// MetroHash implementation of the 64-bit hashing algorithm, variant 2.
+pub struct Metro64_2;
+
+impl HashAlgorithm for Metro64_2 {
+    const NAME: &'static str = "metro64_2";
+    type Seed = u32;
+    type Output = u64;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hash64_2(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hash64_2_with_seed(v, seed)
+    }
+}
+
+/// MetroHash implementation of the 64-bit hashing algorithm, variant 2.
+/// This version allows you to specify a seed.
+pub fn hash64_2_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u64 {
+    const K0: u64 = 0xD6D018F5;
+    const K1: u64 = 0xA2AA033B;
+    const K2: u64 = 0x62992FC1;
+    const K3: u64 = 0x30BC5B29;
+
+    let data = v.as_ref();
+    let mut hash = (seed as u64)
+        .wrapping_add(K2)
+        .wrapping_mul(K0)
+        .wrapping_add(data.len() as u64);
+
+    let mut rest = data;
+    if data.len() >= 32 {
+        let (mut v, tail) = bulk(data, [hash; 4], [K0, K1, K2, K3]);
+        rest = tail;
+        v[2] ^= rotr(mul_add(v[0].wrapping_add(v[3]), K0, v[1]), 30).wrapping_mul(K1);
+        v[3] ^= rotr(mul_add(v[1].wrapping_add(v[2]), K1, v[0]), 30).wrapping_mul(K0);
+        v[0] ^= rotr(mul_add(v[0].wrapping_add(v[2]), K0, v[3]), 30).wrapping_mul(K1);
+        v[1] ^= rotr(mul_add(v[1].wrapping_add(v[3]), K1, v[2]), 30).wrapping_mul(K0);
+        hash = hash.wrapping_add(v[0] ^ v[1]);
+    }
+
+    if rest.len() >= 16 {
+        let mut v0 = hash.wrapping_add(fetch64(rest, 0).wrapping_mul(K2));
+        v0 = rotr(v0, 29).wrapping_mul(K3);
+        let mut v1 = hash.wrapping_add(fetch64(rest, 8).wrapping_mul(K2));
+        v1 = rotr(v1, 29).wrapping_mul(K3);
+        v0 ^= rotr(v0.wrapping_mul(K0), 34).wrapping_add(v1);
+        v1 ^= rotr(v1.wrapping_mul(K3), 34).wrapping_add(v0);
+        hash = hash.wrapping_add(v1);
+        rest = &rest[16..];
+    }
+
+    if rest.len() >= 8 {
+        hash = hash.wrapping_add(fetch64(rest, 0).wrapping_mul(K3));
+        hash ^= rotr(hash, 36).wrapping_mul(K1);
+        rest = &rest[8..];
+    }
+
+    if rest.len() >= 4 {
+        hash = hash.wrapping_add(fetch32(rest, 0).wrapping_mul(K3));
+        hash ^= rotr(hash, 15).wrapping_mul(K1);
+        rest = &rest[4..];
+    }
+
+    if rest.len() >= 2 {
+        hash = hash.wrapping_add(fetch16(rest, 0).wrapping_mul(K3));
+        hash ^= rotr(hash, 15).wrapping_mul(K1);
+        rest = &rest[2..];
+    }
+
+    if let Some(&byte) = rest.first() {
+        hash = hash.wrapping_add((byte as u64).wrapping_mul(K3));
+        hash ^= rotr(hash, 23).wrapping_mul(K1);
+    }
+
+    hash ^= rotr(hash, 28);
+    hash = hash.wrapping_mul(K0);
+    hash ^= rotr(hash, 29);
+    hash
+}
+
+/// MetroHash implementation of the 64-bit hashing algorithm, variant 2.
+/// The seed is always 0 in this version.
+pub fn hash64_2<T: AsRef<[u8]>>(v: T) -> u64 {
+    hash64_2_with_seed(v, 0)
+}
+
+// Main loop shared by all variants: mixes 32-byte stripes into the four lanes `v`,
+// and returns the lanes along with the unprocessed tail.
+#[inline(always)]
+pub(super) fn bulk(data: &[u8], mut v: [u64; 4], k: [u64; 4]) -> ([u64; 4], &[u8]) {
+    let mut chunks = data.chunks_exact(32);
+    for chunk in &mut chunks {
+        v[0] = rotr(mul_add(fetch64(chunk, 0), k[0], v[0]), 29).wrapping_add(v[2]);
+        v[1] = rotr(mul_add(fetch64(chunk, 8), k[1], v[1]), 29).wrapping_add(v[3]);
+        v[2] = rotr(mul_add(fetch64(chunk, 16), k[2], v[2]), 29).wrapping_add(v[0]);
+        v[3] = rotr(mul_add(fetch64(chunk, 24), k[3], v[3]), 29).wrapping_add(v[1]);
+    }
+    (v, chunks.remainder())
+}
+
+// a * b + c
+#[inline(always)]
+pub(super) fn mul_add(a: u64, b: u64, c: u64) -> u64 {
+    a.wrapping_mul(b).wrapping_add(c)
+}
+
+#[inline(always)]
+pub(super) fn rotr(v: u64, k: u32) -> u64 {
+    v.rotate_right(k)
+}
+
+#[inline(always)]
+pub(super) fn fetch64(data: &[u8], i: usize) -> u64 {
+    let buf = [
+        data[i],
+        data[i + 1],
+        data[i + 2],
+        data[i + 3],
+        data[i + 4],
+        data[i + 5],
+        data[i + 6],
+        data[i + 7],
+    ];
+    u64::from_le_bytes(buf)
+}
+
+#[inline(always)]
+pub(super) fn fetch32(data: &[u8], i: usize) -> u64 {
+    let buf = [data[i], data[i + 1], data[i + 2], data[i + 3]];
+    u32::from_le_bytes(buf) as u64
+}
+
+#[inline(always)]
+pub(super) fn fetch16(data: &[u8], i: usize) -> u64 {
+    let buf = [data[i], data[i + 1]];
+    u16::from_le_bytes(buf) as u64
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    #[test]
+    fn compliance_test() {
+        use crate::metro::{verification, Metro64_1, Metro64_2};
+        assert_eq!(verification::<Metro64_1>(), 0xEE88F7D2);
+        assert_eq!(verification::<Metro64_2>(), 0xE1FC7C6E);
+    }
+
+    #[test]
+    fn seeded_builder_test() {
+        use std::hash::BuildHasher;
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let mut hasher = crate::metro::MetroHash64_1::with_seed(4919).build_hasher();
+        for chunk in input.as_bytes().chunks(7) {
+            hasher.write(chunk);
+        }
+        assert_eq!(
+            hasher.finish(),
+            crate::metro::metro_64::hash64_1_with_seed(input, 4919)
+        );
+        let mut hasher = crate::metro::MetroHash64_2::with_seed(4919).build_hasher();
+        hasher.write(input.as_bytes());
+        assert_eq!(
+            hasher.finish(),
+            crate::metro::metro_64::hash64_2_with_seed(input, 4919)
+        );
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn fasthash_interop_test() {
+        use fasthash::FastHash;
+        let input: Vec<u8> = (0..100u32).map(|i| (i * 7 + 3) as u8).collect();
+        let seed = 4919;
+        for len in 0..input.len() {
+            assert_eq!(
+                crate::metro::metro_64::hash64_1_with_seed(&input[..len], seed),
+                fasthash::metro::Hash64_1::hash_with_seed(&input[..len], seed)
+            );
+            assert_eq!(
+                crate::metro::metro_64::hash64_2_with_seed(&input[..len], seed),
+                fasthash::metro::Hash64_2::hash_with_seed(&input[..len], seed)
+            );
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//! MetroHash algorithm by J. Andrew Rogers.
+mod metro_128;
+mod metro_64;
+
+pub use metro_128::{
+    hash128_1, hash128_1_with_seed, hash128_2, hash128_2_with_seed, Metro128_1, Metro128_2,
+    MetroHash128_1, MetroHash128_2, MetroHasher128_1, MetroHasher128_2, MetroRandomState128_1,
+    MetroRandomState128_2,
+};
+pub use metro_64::{
+    hash64_1, hash64_1_with_seed, hash64_2, hash64_2_with_seed, Metro64_1, Metro64_2,
+    MetroHash64_1, MetroHash64_2, MetroHasher64_1, MetroHasher64_2, MetroRandomState64_1,
+    MetroRandomState64_2,
+};
+
+// SMHasher's verification value: hash keys of the form {0, 1, 2, ...} with lengths 0 to 255
+// and seeds 256 - length, then hash the concatenated results
+#[cfg(test)]
+fn verification<A>() -> u32
+where
+    A: crate::HashAlgorithm<Seed = u32>,
+    A::Output: Into<u128>,
+{
+    let size = std::mem::size_of::<A::Output>();
+    let key: Vec<u8> = (0..=255).collect();
+    let mut hashes = Vec::new();
+    for i in 0..256 {
+        let hash: u128 = A::hash_with_seed(&key[..i], 256 - i as u32).into();
+        hashes.extend(&hash.to_le_bytes()[..size]);
+    }
+    let hash: u128 = A::hash(&hashes).into();
+    hash as u32
+}
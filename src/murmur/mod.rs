@@ -1,10 +1,40 @@
-//! MurmurHash3 algorithm by Austin Appleby.
+//! MurmurHash2 and MurmurHash3 algorithms by Austin Appleby.
+mod murmur2_32;
+mod murmur2_64a;
+mod murmur2_64b;
+mod murmur2a_32;
 mod murmur3_128;
 mod murmur3_128_64;
 mod murmur3_32;
 
-pub use murmur3_128::{hash128, hash128_with_seed, Murmur3Hash128, Murmur3Hasher128};
+pub(crate) use murmur3_128_64::{hash128_x64_sign_extended_with_seed, hash128_x64_with_seed64};
+pub(crate) use murmur3_32::{hash32_bytewise_tail_with_seed, hash32_sign_extended_with_seed};
+
+pub use murmur2_32::{
+    murmur2_32, murmur2_32_with_seed, murmur_aligned2_32, murmur_aligned2_32_with_seed,
+    murmur_neutral2_32, murmur_neutral2_32_with_seed, Murmur2Hash32, Murmur2Hasher32,
+    Murmur2RandomState32, Murmur2_32, MurmurAligned2_32, MurmurNeutral2_32,
+};
+pub use murmur2_64a::{
+    murmur2_64a, murmur2_64a_with_seed, Murmur2Hash64A, Murmur2Hasher64A, Murmur2RandomState64A,
+    Murmur2_64A,
+};
+pub use murmur2_64b::{
+    murmur2_64b, murmur2_64b_with_seed, Murmur2Hash64B, Murmur2Hasher64B, Murmur2RandomState64B,
+    Murmur2_64B,
+};
+pub use murmur2a_32::{
+    murmur2a_32, murmur2a_32_with_seed, Murmur2A32, Murmur2AHash32, Murmur2AHasher32,
+    Murmur2ARandomState32,
+};
+pub use murmur3_128::{
+    hash128, hash128_with_seed, Murmur3Hash128, Murmur3Hasher128, Murmur3RandomState128,
+    Murmur3x86_128,
+};
 pub use murmur3_128_64::{
     hash128_x64, hash128_x64_with_seed, Murmur3Hash128x64, Murmur3Hasher128x64,
+    Murmur3RandomState128x64, Murmur3x64_128,
+};
+pub use murmur3_32::{
+    hash32, hash32_with_seed, Murmur3Hash32, Murmur3Hasher32, Murmur3RandomState32, Murmur3x86_32,
 };
-pub use murmur3_32::{hash32, hash32_with_seed, Murmur3Hash32, Murmur3Hasher32};
@@ -0,0 +1,274 @@
+use std::hash::{BuildHasher, Hasher};
+
+use super::murmur3_32::get_u32;
+use crate::HashAlgorithm;
+
+/// Hasher for MurmurHash2 implementation of the 32-bit hashing algorithm.
+///
+/// MurmurHash2 mixes the input length into its initial state, so the input is buffered until `finish()`
+/// is called. `Murmur2AHasher32` hashes its input incrementally instead.
+#[derive(Default)]
+pub struct Murmur2Hasher32 {
+    buffer: Vec<u8>,
+    seed: u32,
+}
+
+impl Murmur2Hasher32 {
+    /// Create a hasher which uses the given seed.
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            buffer: Vec::new(),
+            seed,
+        }
+    }
+}
+
+impl Hasher for Murmur2Hasher32 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.buffer.extend(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        murmur2_32_with_seed(&self.buffer, self.seed) as u64
+    }
+}
+
+/// Hash builder for MurmurHash2 implementation of the 32-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct Murmur2Hash32 {
+    seed: u32,
+}
+
+impl Murmur2Hash32 {
+    /// Create a hash builder whose hashers use the given seed.
+    pub fn with_seed(seed: u32) -> Self {
+        Self { seed }
+    }
+}
+
+impl BuildHasher for Murmur2Hash32 {
+    type Hasher = Murmur2Hasher32;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_seed(self.seed)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for MurmurHash2 implementation of the 32-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct Murmur2RandomState32(Murmur2Hash32::with_seed(seed: u32));
+}
+
+// The code below is adapted from C++ code with the following disclaimer
+//-----------------------------------------------------------------------------
+// MurmurHash2 was written by Austin Appleby, and is placed in the public
+// domain. The author hereby disclaims copyright to this source code.
+
+// 'm' and 'r' are mixing constants generated offline.
+// They're not really 'magic', they just happen to work well.
+pub(super) const M: u32 = 0x5bd1e995;
+pub(super) const R: u32 = 24;
+
+/// MurmurHash2 implementation of the 32-bit hashing algorithm.
+pub struct Murmur2_32;
+
+impl HashAlgorithm for Murmur2_32 {
+    const NAME: &'static str = "murmur2_32";
+    type Seed = u32;
+    type Output = u32;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        murmur2_32(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        murmur2_32_with_seed(v, seed)
+    }
+}
+
+/// MurmurHash2 implementation of the 32-bit hashing algorithm.
+/// This version allows you to specify a seed.
+///
+/// Blocks are read in little-endian order, so the result matches the reference implementation on
+/// little-endian machines.
+pub fn murmur2_32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
+    let data = v.as_ref();
+    let n_blocks = data.len() / 4;
+
+    let mut h = seed ^ data.len() as u32;
+
+    // body
+    for i in 0..n_blocks {
+        h = mix(h, get_u32(data, i * 4));
+    }
+
+    // tail
+    let tail = &data[n_blocks * 4..];
+    if !tail.is_empty() {
+        h ^= get_tail(tail);
+        h = h.wrapping_mul(M);
+    }
+
+    finalize(h)
+}
+
+/// MurmurHash2 implementation of the 32-bit hashing algorithm.
+/// The seed is always 0 in this version.
+pub fn murmur2_32<T: AsRef<[u8]>>(v: T) -> u32 {
+    murmur2_32_with_seed(v, 0)
+}
+
+/// MurmurHashNeutral2 implementation of the 32-bit hashing algorithm.
+pub struct MurmurNeutral2_32;
+
+impl HashAlgorithm for MurmurNeutral2_32 {
+    const NAME: &'static str = "murmur_neutral2_32";
+    type Seed = u32;
+    type Output = u32;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        murmur_neutral2_32(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        murmur_neutral2_32_with_seed(v, seed)
+    }
+}
+
+/// MurmurHashNeutral2 implementation of the 32-bit hashing algorithm.
+/// This version allows you to specify a seed.
+///
+/// The reference implementation is the endian-neutral version of MurmurHash2. Since this crate always
+/// reads blocks in little-endian order, it returns the same values as `murmur2_32_with_seed()`.
+pub fn murmur_neutral2_32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
+    murmur2_32_with_seed(v, seed)
+}
+
+/// MurmurHashNeutral2 implementation of the 32-bit hashing algorithm.
+/// The seed is always 0 in this version.
+pub fn murmur_neutral2_32<T: AsRef<[u8]>>(v: T) -> u32 {
+    murmur_neutral2_32_with_seed(v, 0)
+}
+
+/// MurmurHashAligned2 implementation of the 32-bit hashing algorithm.
+pub struct MurmurAligned2_32;
+
+impl HashAlgorithm for MurmurAligned2_32 {
+    const NAME: &'static str = "murmur_aligned2_32";
+    type Seed = u32;
+    type Output = u32;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        murmur_aligned2_32(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        murmur_aligned2_32_with_seed(v, seed)
+    }
+}
+
+/// MurmurHashAligned2 implementation of the 32-bit hashing algorithm.
+/// This version allows you to specify a seed.
+///
+/// The reference implementation only differs from MurmurHash2 in avoiding unaligned memory reads,
+/// which never happen here, so it returns the same values as `murmur2_32_with_seed()`.
+pub fn murmur_aligned2_32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
+    murmur2_32_with_seed(v, seed)
+}
+
+/// MurmurHashAligned2 implementation of the 32-bit hashing algorithm.
+/// The seed is always 0 in this version.
+pub fn murmur_aligned2_32<T: AsRef<[u8]>>(v: T) -> u32 {
+    murmur_aligned2_32_with_seed(v, 0)
+}
+
+#[inline(always)]
+pub(super) fn mix(h: u32, mut k: u32) -> u32 {
+    k = k.wrapping_mul(M);
+    k ^= k >> R;
+    k = k.wrapping_mul(M);
+    h.wrapping_mul(M) ^ k
+}
+
+// the last 1 to 3 bytes of the input, as a little-endian number
+#[inline(always)]
+pub(super) fn get_tail(tail: &[u8]) -> u32 {
+    tail.iter()
+        .enumerate()
+        .fold(0, |k, (i, &byte)| k ^ (byte as u32) << (i * 8))
+}
+
+// a few final mixes of the hash to ensure the last few bytes are well-incorporated
+#[inline(always)]
+pub(super) fn finalize(mut h: u32) -> u32 {
+    h ^= h >> 13;
+    h = h.wrapping_mul(M);
+    h ^= h >> 15;
+    h
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    #[test]
+    fn compliance_test() {
+        assert_eq!(crate::murmur::murmur2_32(""), 0);
+        assert_eq!(crate::murmur::murmur2_32("a"), 0x92685f5e);
+        assert_eq!(crate::murmur::murmur2_32("abc"), 0x13577c9b);
+        assert_eq!(crate::murmur::murmur2_32("hello world"), 0x44a81419);
+        assert_eq!(
+            crate::murmur::murmur2_32_with_seed("", 0x9747b28c),
+            0x106e08d9
+        );
+        assert_eq!(
+            crate::murmur::murmur2_32_with_seed("abcd", 0x9747b28c),
+            0xb11ab5f4
+        );
+        assert_eq!(
+            crate::murmur::murmur2_32_with_seed(
+                "The quick brown fox jumps over the lazy dog",
+                0x9747b28c
+            ),
+            0x1d84d036
+        );
+    }
+
+    #[test]
+    fn streaming_test() {
+        let input: Vec<u8> = (0..=255).collect();
+        for len in 0..input.len() {
+            let expected = crate::murmur::murmur2_32_with_seed(&input[..len], 4919) as u64;
+            let mut hasher = crate::murmur::Murmur2Hasher32::with_seed(4919);
+            for chunk in input[..len].chunks(3) {
+                hasher.write(chunk);
+            }
+            assert_eq!(hasher.finish(), expected);
+        }
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn fasthash_interop_test() {
+        use fasthash::FastHash;
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let seed = 4919;
+        for len in 0..input.len() {
+            // unaligned slices take a different path through MurmurHashAligned2
+            let input = &input.as_bytes()[input.len() - len..];
+            assert_eq!(
+                crate::murmur::murmur2_32_with_seed(input, seed),
+                fasthash::murmur2::Hash32::hash_with_seed(input, seed)
+            );
+            assert_eq!(
+                crate::murmur::murmur_neutral2_32_with_seed(input, seed),
+                fasthash::murmur2::Hash32Neutral::hash_with_seed(input, seed)
+            );
+            assert_eq!(
+                crate::murmur::murmur_aligned2_32_with_seed(input, seed),
+                fasthash::murmur2::Hash32Aligned::hash_with_seed(input, seed)
+            );
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use std::hash::{BuildHasher, Hasher};
+
+use super::murmur3_128_64::get_u64;
+use crate::HashAlgorithm;
+
+/// Hasher for MurmurHash64A implementation of the 64-bit hashing algorithm.
+///
+/// MurmurHash64A mixes the input length into its initial state, so the input is buffered until
+/// `finish()` is called.
+#[derive(Default)]
+pub struct Murmur2Hasher64A {
+    buffer: Vec<u8>,
+    seed: u64,
+}
+
+impl Murmur2Hasher64A {
+    /// Create a hasher which uses the given seed.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            buffer: Vec::new(),
+            seed,
+        }
+    }
+}
+
+impl Hasher for Murmur2Hasher64A {
+    fn write(&mut self, bytes: &[u8]) {
+        self.buffer.extend(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        murmur2_64a_with_seed(&self.buffer, self.seed)
+    }
+}
+
+/// Hash builder for MurmurHash64A implementation of the 64-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct Murmur2Hash64A {
+    seed: u64,
+}
+
+impl Murmur2Hash64A {
+    /// Create a hash builder whose hashers use the given seed.
+    pub fn with_seed(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl BuildHasher for Murmur2Hash64A {
+    type Hasher = Murmur2Hasher64A;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_seed(self.seed)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for MurmurHash64A implementation of the 64-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct Murmur2RandomState64A(Murmur2Hash64A::with_seed(seed: u64));
+}
+
+// The code below is adapted from C++ code with the following disclaimer
+//-----------------------------------------------------------------------------
+// MurmurHash2 was written by Austin Appleby, and is placed in the public
+// domain. The author hereby disclaims copyright to this source code.
+
+const M: u64 = 0xc6a4a7935bd1e995;
+const R: u32 = 47;
+
+/// MurmurHash64A implementation of the 64-bit hashing algorithm.
+pub struct Murmur2_64A;
+
+impl HashAlgorithm for Murmur2_64A {
+    const NAME: &'static str = "murmur2_64a";
+    type Seed = u64;
+    type Output = u64;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        murmur2_64a(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        murmur2_64a_with_seed(v, seed)
+    }
+}
+
+/// MurmurHash64A implementation of the 64-bit hashing algorithm, designed for 64-bit platforms.
+/// This version allows you to specify a seed.
+pub fn murmur2_64a_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
+    let data = v.as_ref();
+    let n_blocks = data.len() / 8;
+
+    let mut h = seed ^ (data.len() as u64).wrapping_mul(M);
+
+    // body
+    for i in 0..n_blocks {
+        let mut k = get_u64(data, i * 8);
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+
+        h ^= k;
+        h = h.wrapping_mul(M);
+    }
+
+    // tail
+    let tail = &data[n_blocks * 8..];
+    if !tail.is_empty() {
+        for (i, &byte) in tail.iter().enumerate() {
+            h ^= (byte as u64) << (i * 8);
+        }
+        h = h.wrapping_mul(M);
+    }
+
+    // finalization
+    h ^= h >> R;
+    h = h.wrapping_mul(M);
+    h ^= h >> R;
+    h
+}
+
+/// MurmurHash64A implementation of the 64-bit hashing algorithm, designed for 64-bit platforms.
+/// The seed is always 0 in this version.
+pub fn murmur2_64a<T: AsRef<[u8]>>(v: T) -> u64 {
+    murmur2_64a_with_seed(v, 0)
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    #[test]
+    fn compliance_test() {
+        assert_eq!(crate::murmur::murmur2_64a(""), 0);
+        assert_eq!(crate::murmur::murmur2_64a("a"), 0x071717d2d36b6b11);
+        assert_eq!(crate::murmur::murmur2_64a("abc"), 0x9cc9c33498a95efb);
+        assert_eq!(
+            crate::murmur::murmur2_64a("hello world"),
+            0xd3ba2368a832afce
+        );
+        assert_eq!(
+            crate::murmur::murmur2_64a_with_seed("", 0x0123456789abcdef),
+            0x1b8d96b11cf99946
+        );
+        assert_eq!(
+            crate::murmur::murmur2_64a_with_seed(
+                "The quick brown fox jumps over the lazy dog",
+                0x0123456789abcdef
+            ),
+            0xc9e21e5d9e6a87dc
+        );
+    }
+
+    #[test]
+    fn seeded_builder_test() {
+        use std::hash::BuildHasher;
+        let input = "The quick brown fox jumps over the lazy dog";
+        let mut hasher =
+            crate::murmur::Murmur2Hash64A::with_seed(0x0123456789abcdef).build_hasher();
+        hasher.write(&input.as_bytes()[..10]);
+        hasher.write(&input.as_bytes()[10..]);
+        assert_eq!(hasher.finish(), 0xc9e21e5d9e6a87dc);
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn fasthash_interop_test() {
+        use fasthash::FastHash;
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let seed = 4919;
+        for len in 0..input.len() {
+            let input = &input[..len];
+            assert_eq!(
+                crate::murmur::murmur2_64a_with_seed(input, seed),
+                fasthash::murmur2::Hash64_x64::hash_with_seed(input, seed)
+            );
+        }
+    }
+}
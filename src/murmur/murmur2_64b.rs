@@ -0,0 +1,182 @@
+use std::hash::{BuildHasher, Hasher};
+
+use super::murmur2_32::{get_tail, mix, M};
+use super::murmur3_32::get_u32;
+use crate::HashAlgorithm;
+
+/// Hasher for MurmurHash64B implementation of the 64-bit hashing algorithm.
+///
+/// MurmurHash64B mixes the input length into its initial state, so the input is buffered until
+/// `finish()` is called.
+#[derive(Default)]
+pub struct Murmur2Hasher64B {
+    buffer: Vec<u8>,
+    seed: u64,
+}
+
+impl Murmur2Hasher64B {
+    /// Create a hasher which uses the given seed.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            buffer: Vec::new(),
+            seed,
+        }
+    }
+}
+
+impl Hasher for Murmur2Hasher64B {
+    fn write(&mut self, bytes: &[u8]) {
+        self.buffer.extend(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        murmur2_64b_with_seed(&self.buffer, self.seed)
+    }
+}
+
+/// Hash builder for MurmurHash64B implementation of the 64-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct Murmur2Hash64B {
+    seed: u64,
+}
+
+impl Murmur2Hash64B {
+    /// Create a hash builder whose hashers use the given seed.
+    pub fn with_seed(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl BuildHasher for Murmur2Hash64B {
+    type Hasher = Murmur2Hasher64B;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_seed(self.seed)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for MurmurHash64B implementation of the 64-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct Murmur2RandomState64B(Murmur2Hash64B::with_seed(seed: u64));
+}
+
+// The code below is adapted from C++ code with the following disclaimer
+//-----------------------------------------------------------------------------
+// MurmurHash2 was written by Austin Appleby, and is placed in the public
+// domain. The author hereby disclaims copyright to this source code.
+
+/// MurmurHash64B implementation of the 64-bit hashing algorithm.
+pub struct Murmur2_64B;
+
+impl HashAlgorithm for Murmur2_64B {
+    const NAME: &'static str = "murmur2_64b";
+    type Seed = u64;
+    type Output = u64;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        murmur2_64b(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        murmur2_64b_with_seed(v, seed)
+    }
+}
+
+/// MurmurHash64B implementation of the 64-bit hashing algorithm, designed for 32-bit platforms.
+/// This version allows you to specify a seed.
+pub fn murmur2_64b_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
+    let data = v.as_ref();
+    let n_blocks = data.len() / 4;
+
+    let mut h1 = seed as u32 ^ data.len() as u32;
+    let mut h2 = (seed >> 32) as u32;
+
+    // body, alternating between the two halves of the state
+    for i in 0..n_blocks {
+        let k = get_u32(data, i * 4);
+        if i % 2 == 0 {
+            h1 = mix(h1, k);
+        } else {
+            h2 = mix(h2, k);
+        }
+    }
+
+    // tail
+    let tail = &data[n_blocks * 4..];
+    if !tail.is_empty() {
+        h2 ^= get_tail(tail);
+        h2 = h2.wrapping_mul(M);
+    }
+
+    // finalization
+    h1 ^= h2 >> 18;
+    h1 = h1.wrapping_mul(M);
+    h2 ^= h1 >> 22;
+    h2 = h2.wrapping_mul(M);
+    h1 ^= h2 >> 17;
+    h1 = h1.wrapping_mul(M);
+    h2 ^= h1 >> 19;
+    h2 = h2.wrapping_mul(M);
+
+    (h1 as u64) << 32 | h2 as u64
+}
+
+/// MurmurHash64B implementation of the 64-bit hashing algorithm, designed for 32-bit platforms.
+/// The seed is always 0 in this version.
+pub fn murmur2_64b<T: AsRef<[u8]>>(v: T) -> u64 {
+    murmur2_64b_with_seed(v, 0)
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    #[test]
+    fn compliance_test() {
+        assert_eq!(crate::murmur::murmur2_64b(""), 0);
+        assert_eq!(crate::murmur::murmur2_64b("a"), 0x716e41e3dff50b85);
+        assert_eq!(crate::murmur::murmur2_64b("abc"), 0xa60d4251ce5c599d);
+        assert_eq!(
+            crate::murmur::murmur2_64b("hello world"),
+            0x626031fa7b0309e0
+        );
+        assert_eq!(
+            crate::murmur::murmur2_64b_with_seed("", 0x0123456789abcdef),
+            0x55aec590a99e08c0
+        );
+        assert_eq!(
+            crate::murmur::murmur2_64b_with_seed(
+                "The quick brown fox jumps over the lazy dog",
+                0x0123456789abcdef
+            ),
+            0xa33fb8b465d6e1d1
+        );
+    }
+
+    #[test]
+    fn seeded_builder_test() {
+        use std::hash::BuildHasher;
+        let input = "The quick brown fox jumps over the lazy dog";
+        let mut hasher =
+            crate::murmur::Murmur2Hash64B::with_seed(0x0123456789abcdef).build_hasher();
+        hasher.write(&input.as_bytes()[..10]);
+        hasher.write(&input.as_bytes()[10..]);
+        assert_eq!(hasher.finish(), 0xa33fb8b465d6e1d1);
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn fasthash_interop_test() {
+        use fasthash::FastHash;
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let seed = 4919;
+        for len in 0..input.len() {
+            let input = &input[..len];
+            assert_eq!(
+                crate::murmur::murmur2_64b_with_seed(input, seed),
+                fasthash::murmur2::Hash64_x86::hash_with_seed(input, seed)
+            );
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use std::hash::{BuildHasher, Hasher};
+
+use super::murmur2_32::{finalize, get_tail, mix};
+use super::murmur3_32::get_u32;
+use crate::HashAlgorithm;
+
+/// Hasher for MurmurHash2A implementation of the 32-bit hashing algorithm.
+#[derive(Default)]
+pub struct Murmur2AHasher32 {
+    h: u32,
+    tail: [u8; 4],
+    tail_len: usize,
+    length: u64,
+}
+
+impl Murmur2AHasher32 {
+    /// Create a hasher which uses the given seed.
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            h: seed,
+            ..Self::default()
+        }
+    }
+}
+
+impl Hasher for Murmur2AHasher32 {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut data = bytes;
+        self.length += data.len() as u64;
+        // complete a previously started block
+        if self.tail_len != 0 {
+            let fill = (4 - self.tail_len).min(data.len());
+            self.tail[self.tail_len..self.tail_len + fill].copy_from_slice(&data[..fill]);
+            self.tail_len += fill;
+            data = &data[fill..];
+            if self.tail_len < 4 {
+                return;
+            }
+            self.h = mix(self.h, get_u32(&self.tail, 0));
+            self.tail_len = 0;
+        }
+        // process full blocks directly from the input
+        let n_blocks = data.len() / 4;
+        for i in 0..n_blocks {
+            self.h = mix(self.h, get_u32(data, i * 4));
+        }
+        // keep the remainder for the next write
+        let rest = &data[n_blocks * 4..];
+        self.tail[..rest.len()].copy_from_slice(rest);
+        self.tail_len = rest.len();
+    }
+
+    fn finish(&self) -> u64 {
+        finish(self.h, &self.tail[..self.tail_len], self.length) as u64
+    }
+}
+
+/// Hash builder for MurmurHash2A implementation of the 32-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct Murmur2AHash32 {
+    seed: u32,
+}
+
+impl Murmur2AHash32 {
+    /// Create a hash builder whose hashers use the given seed.
+    pub fn with_seed(seed: u32) -> Self {
+        Self { seed }
+    }
+}
+
+impl BuildHasher for Murmur2AHash32 {
+    type Hasher = Murmur2AHasher32;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_seed(self.seed)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for MurmurHash2A implementation of the 32-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct Murmur2ARandomState32(Murmur2AHash32::with_seed(seed: u32));
+}
+
+// The code below is adapted from C++ code with the following disclaimer
+//-----------------------------------------------------------------------------
+// MurmurHash2 was written by Austin Appleby, and is placed in the public
+// domain. The author hereby disclaims copyright to this source code.
+
+/// MurmurHash2A implementation of the 32-bit hashing algorithm.
+pub struct Murmur2A32;
+
+impl HashAlgorithm for Murmur2A32 {
+    const NAME: &'static str = "murmur2a_32";
+    type Seed = u32;
+    type Output = u32;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        murmur2a_32(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        murmur2a_32_with_seed(v, seed)
+    }
+}
+
+/// MurmurHash2A implementation of the 32-bit hashing algorithm.
+/// This version allows you to specify a seed.
+///
+/// MurmurHash2A is a variant of MurmurHash2 which mixes in the input length last,
+/// which allows hashing the input incrementally.
+pub fn murmur2a_32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
+    let data = v.as_ref();
+    let n_blocks = data.len() / 4;
+
+    let mut h = seed;
+
+    // body
+    for i in 0..n_blocks {
+        h = mix(h, get_u32(data, i * 4));
+    }
+
+    finish(h, &data[n_blocks * 4..], data.len() as u64)
+}
+
+/// MurmurHash2A implementation of the 32-bit hashing algorithm.
+/// The seed is always 0 in this version.
+pub fn murmur2a_32<T: AsRef<[u8]>>(v: T) -> u32 {
+    murmur2a_32_with_seed(v, 0)
+}
+
+#[inline(always)]
+fn finish(mut h: u32, tail: &[u8], length: u64) -> u32 {
+    h = mix(h, get_tail(tail));
+    h = mix(h, length as u32);
+    finalize(h)
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    #[test]
+    fn compliance_test() {
+        assert_eq!(crate::murmur::murmur2a_32(""), 0);
+        assert_eq!(crate::murmur::murmur2a_32("a"), 0x0803888b);
+        assert_eq!(crate::murmur::murmur2a_32("abc"), 0x11589f67);
+        assert_eq!(crate::murmur::murmur2a_32("hello world"), 0x9dfc8997);
+        assert_eq!(
+            crate::murmur::murmur2a_32_with_seed("", 0x9747b28c),
+            0xe37c4f59
+        );
+        assert_eq!(
+            crate::murmur::murmur2a_32_with_seed("abcd", 0x9747b28c),
+            0xbfd2bf11
+        );
+        assert_eq!(
+            crate::murmur::murmur2a_32_with_seed(
+                "The quick brown fox jumps over the lazy dog",
+                0x9747b28c
+            ),
+            0xe5809c92
+        );
+    }
+
+    #[test]
+    fn streaming_test() {
+        let input: Vec<u8> = (0..=255).collect();
+        for len in 0..input.len() {
+            let expected = crate::murmur::murmur2a_32_with_seed(&input[..len], 4919) as u64;
+            for split in [1, 3, 4, 7] {
+                let mut hasher = crate::murmur::Murmur2AHasher32::with_seed(4919);
+                for chunk in input[..len].chunks(split) {
+                    hasher.write(chunk);
+                }
+                assert_eq!(hasher.finish(), expected);
+            }
+        }
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn fasthash_interop_test() {
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let seed = 4919;
+        assert_eq!(
+            crate::murmur::murmur2a_32_with_seed(input, seed),
+            fasthash::murmur2::hash32_with_seed(input, seed)
+        );
+    }
+}
@@ -1,145 +1,237 @@
 use std::hash::{BuildHasher, Hasher};
 
+use crate::{HashAlgorithm, Hasher128};
+
 /// Hasher for MurmurHash3 32-bit implementation of the 128-bit hashing algorithm.
 #[derive(Default)]
 pub struct Murmur3Hasher128 {
-    buffer: Vec<u8>,
+    h: [u32; 4],
+    tail: [u8; 16],
+    tail_len: usize,
+    length: u64,
+}
+
+impl Murmur3Hasher128 {
+    /// Create a hasher which uses the given seed.
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            h: [seed; 4],
+            ..Self::default()
+        }
+    }
 }
 
 impl Hasher for Murmur3Hasher128 {
     fn write(&mut self, bytes: &[u8]) {
-        self.buffer.extend(bytes)
+        let mut data = bytes;
+        self.length += data.len() as u64;
+        // complete a previously started block
+        if self.tail_len != 0 {
+            let fill = (16 - self.tail_len).min(data.len());
+            self.tail[self.tail_len..self.tail_len + fill].copy_from_slice(&data[..fill]);
+            self.tail_len += fill;
+            data = &data[fill..];
+            if self.tail_len < 16 {
+                return;
+            }
+            body(&mut self.h, &self.tail, 0);
+            self.tail_len = 0;
+        }
+        // process full blocks directly from the input
+        let n_blocks = data.len() / 16;
+        for i in 0..n_blocks {
+            body(&mut self.h, data, i * 16);
+        }
+        // keep the remainder for the next write
+        let rest = &data[n_blocks * 16..];
+        self.tail[..rest.len()].copy_from_slice(rest);
+        self.tail_len = rest.len();
     }
 
     fn finish(&self) -> u64 {
-        hash128(&self.buffer) as u64
+        self.finish128() as u64
+    }
+}
+
+impl Hasher128 for Murmur3Hasher128 {
+    fn finish128(&self) -> u128 {
+        finalize(self.h, &self.tail[..self.tail_len], self.length)
     }
 }
 
 /// Hash builder for MurmurHash3 32-bit implementation of the 128-bit hashing algorithm.
-#[derive(Default)]
-pub struct Murmur3Hash128 {}
+#[derive(Clone, Copy, Default)]
+pub struct Murmur3Hash128 {
+    seed: u32,
+}
+
+impl Murmur3Hash128 {
+    /// Create a hash builder whose hashers use the given seed.
+    pub fn with_seed(seed: u32) -> Self {
+        Self { seed }
+    }
+}
 
 impl BuildHasher for Murmur3Hash128 {
     type Hasher = Murmur3Hasher128;
 
     fn build_hasher(&self) -> Self::Hasher {
-        Self::Hasher::default()
+        Self::Hasher::with_seed(self.seed)
     }
 }
 
+crate::random::random_state! {
+    /// Randomly seeded hash builder for MurmurHash3 32-bit implementation of the 128-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct Murmur3RandomState128(Murmur3Hash128::with_seed(seed: u32));
+}
+
 // The code below is adapted from C++ code with the following disclaimer
 //-----------------------------------------------------------------------------
 // MurmurHash3 was written by Austin Appleby, and is placed in the public
 // domain. The author hereby disclaims copyright to this source code.
 
+const C1: u32 = 0x239b961b;
+const C2: u32 = 0xab0e9789;
+const C3: u32 = 0x38b34ae5;
+const C4: u32 = 0xa1e38b93;
+
+const D1: u32 = 0x561ccd1b;
+const D2: u32 = 0x0bcaa747;
+const D3: u32 = 0x96cd1c35;
+const D4: u32 = 0x32ac3b17;
+
+/// MurmurHash3 32-bit implementation of the 128-bit hashing algorithm.
+pub struct Murmur3x86_128;
+
+impl HashAlgorithm for Murmur3x86_128 {
+    const NAME: &'static str = "murmur3_x86_128";
+    type Seed = u32;
+    type Output = u128;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hash128(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hash128_with_seed(v, seed)
+    }
+}
+
 /// MurmurHash3 32-bit implementation of the 128-bit hashing algorithm.
 /// This version allows you to specify a seed.
 pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u128 {
     let data = v.as_ref();
     let n_blocks = data.len() / 16;
 
-    const C1: u32 = 0x239b961b;
-    const C2: u32 = 0xab0e9789;
-    const C3: u32 = 0x38b34ae5;
-    const C4: u32 = 0xa1e38b93;
-
-    const D1: u32 = 0x561ccd1b;
-    const D2: u32 = 0x0bcaa747;
-    const D3: u32 = 0x96cd1c35;
-    const D4: u32 = 0x32ac3b17;
-
-    let mut h1: u32 = seed;
-    let mut h2: u32 = seed;
-    let mut h3: u32 = seed;
-    let mut h4: u32 = seed;
+    let mut h = [seed; 4];
 
     // body
     for i in 0..n_blocks {
-        let mut k1 = get_u32(data, (i + 0) * 4);
-        let mut k2 = get_u32(data, (i + 1) * 4);
-        let mut k3 = get_u32(data, (i + 2) * 4);
-        let mut k4 = get_u32(data, (i + 3) * 4);
-
-        k1 = k1.wrapping_mul(C1);
-        k1 = k1.rotate_left(15);
-        k1 = k1.wrapping_mul(C2);
-        h1 ^= k1;
-        h1 = h1.rotate_left(19);
-        h1 = h1.wrapping_add(h2);
-        h1 = (h1.wrapping_mul(5)).wrapping_add(D1);
-
-        k2 = k2.wrapping_mul(C2);
-        k2 = k2.rotate_left(16);
-        k2 = k2.wrapping_mul(C3);
-        h2 ^= k2;
-        h2 = h2.rotate_left(17);
-        h2 = h2.wrapping_add(h3);
-        h2 = (h2.wrapping_mul(5)).wrapping_add(D2);
-
-        k3 = k3.wrapping_mul(C3);
-        k3 = k3.rotate_left(17);
-        k3 = k3.wrapping_mul(C4);
-        h3 ^= k3;
-        h3 = h3.rotate_left(15);
-        h3 = h3.wrapping_add(h4);
-        h3 = (h3.wrapping_mul(5)).wrapping_add(D3);
-
-        k4 = k4.wrapping_mul(C4);
-        k4 = k4.rotate_left(18);
-        k4 = k4.wrapping_mul(C1);
-        h4 ^= k4;
-        h4 = h4.rotate_left(13);
-        h4 = h4.wrapping_add(h1);
-        h4 = (h4.wrapping_mul(5)).wrapping_add(D4);
+        body(&mut h, data, i * 16);
     }
 
+    finalize(h, &data[n_blocks * 16..], data.len() as u64)
+}
+
+/// MurmurHash3 32-bit implementation of the 128-bit hashing algorithm.
+/// The seed is always 0 in this version.
+pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
+    hash128_with_seed(v, 0)
+}
+
+#[inline(always)]
+fn body(h: &mut [u32; 4], data: &[u8], i: usize) {
+    let [mut h1, mut h2, mut h3, mut h4] = *h;
+
+    let mut k1 = get_u32(data, i);
+    let mut k2 = get_u32(data, i + 4);
+    let mut k3 = get_u32(data, i + 8);
+    let mut k4 = get_u32(data, i + 12);
+
+    k1 = k1.wrapping_mul(C1);
+    k1 = k1.rotate_left(15);
+    k1 = k1.wrapping_mul(C2);
+    h1 ^= k1;
+    h1 = h1.rotate_left(19);
+    h1 = h1.wrapping_add(h2);
+    h1 = (h1.wrapping_mul(5)).wrapping_add(D1);
+
+    k2 = k2.wrapping_mul(C2);
+    k2 = k2.rotate_left(16);
+    k2 = k2.wrapping_mul(C3);
+    h2 ^= k2;
+    h2 = h2.rotate_left(17);
+    h2 = h2.wrapping_add(h3);
+    h2 = (h2.wrapping_mul(5)).wrapping_add(D2);
+
+    k3 = k3.wrapping_mul(C3);
+    k3 = k3.rotate_left(17);
+    k3 = k3.wrapping_mul(C4);
+    h3 ^= k3;
+    h3 = h3.rotate_left(15);
+    h3 = h3.wrapping_add(h4);
+    h3 = (h3.wrapping_mul(5)).wrapping_add(D3);
+
+    k4 = k4.wrapping_mul(C4);
+    k4 = k4.rotate_left(18);
+    k4 = k4.wrapping_mul(C1);
+    h4 ^= k4;
+    h4 = h4.rotate_left(13);
+    h4 = h4.wrapping_add(h1);
+    h4 = (h4.wrapping_mul(5)).wrapping_add(D4);
+
+    *h = [h1, h2, h3, h4];
+}
+
+#[inline(always)]
+fn finalize(h: [u32; 4], tail: &[u8], length: u64) -> u128 {
+    let [mut h1, mut h2, mut h3, mut h4] = h;
+
     // tail
-    //let tail = data.clone();
-    let tail_num = n_blocks * 16;
     let mut k1 = 0;
     let mut k2 = 0;
     let mut k3 = 0;
     let mut k4 = 0;
-    for i in (1..=data.len() & 15).rev() {
+    for i in (1..=tail.len() & 15).rev() {
         match i {
-            15 => k4 ^= (data[tail_num + 14] as u32) << 16,
-            14 => k4 ^= (data[tail_num + 13] as u32) << 8,
+            15 => k4 ^= (tail[14] as u32) << 16,
+            14 => k4 ^= (tail[13] as u32) << 8,
             13 => {
-                k4 ^= (data[tail_num + 12] as u32) << 0;
+                k4 ^= tail[12] as u32;
                 k4 = k4.wrapping_mul(C4);
                 k4 = k4.rotate_left(18);
                 k4 = k4.wrapping_mul(C1);
                 h4 ^= k4;
             }
 
-            12 => k3 ^= (data[tail_num + 11] as u32) << 24,
-            11 => k3 ^= (data[tail_num + 10] as u32) << 16,
-            10 => k3 ^= (data[tail_num + 9] as u32) << 8,
+            12 => k3 ^= (tail[11] as u32) << 24,
+            11 => k3 ^= (tail[10] as u32) << 16,
+            10 => k3 ^= (tail[9] as u32) << 8,
             9 => {
-                k3 ^= (data[tail_num + 8] as u32) << 0;
+                k3 ^= tail[8] as u32;
                 k3 = k3.wrapping_mul(C3);
                 k3 = k3.rotate_left(17);
                 k3 = k3.wrapping_mul(C4);
                 h3 ^= k3;
             }
 
-            8 => k2 ^= (data[tail_num + 7] as u32) << 24,
-            7 => k2 ^= (data[tail_num + 6] as u32) << 16,
-            6 => k2 ^= (data[tail_num + 5] as u32) << 8,
+            8 => k2 ^= (tail[7] as u32) << 24,
+            7 => k2 ^= (tail[6] as u32) << 16,
+            6 => k2 ^= (tail[5] as u32) << 8,
             5 => {
-                k2 ^= (data[tail_num + 4] as u32) << 0;
+                k2 ^= tail[4] as u32;
                 k2 = k2.wrapping_mul(C2);
                 k2 = k2.rotate_left(16);
                 k2 = k2.wrapping_mul(C3);
                 h2 ^= k2;
             }
 
-            4 => k1 ^= (data[tail_num + 3] as u32) << 24,
-            3 => k1 ^= (data[tail_num + 2] as u32) << 16,
-            2 => k1 ^= (data[tail_num + 1] as u32) << 8,
+            4 => k1 ^= (tail[3] as u32) << 24,
+            3 => k1 ^= (tail[2] as u32) << 16,
+            2 => k1 ^= (tail[1] as u32) << 8,
             1 => {
-                k1 ^= data[tail_num] as u32;
+                k1 ^= tail[0] as u32;
                 k1 = k1.wrapping_mul(C1);
                 k1 = k1.rotate_left(15);
                 k1 = k1.wrapping_mul(C2);
@@ -150,10 +242,10 @@ pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u128 {
     }
 
     // finalization
-    h1 ^= data.len() as u32;
-    h2 ^= data.len() as u32;
-    h3 ^= data.len() as u32;
-    h4 ^= data.len() as u32;
+    h1 ^= length as u32;
+    h2 ^= length as u32;
+    h3 ^= length as u32;
+    h4 ^= length as u32;
 
     h1 = h1.wrapping_add(h2).wrapping_add(h3).wrapping_add(h4);
     h2 = h2.wrapping_add(h1);
@@ -173,12 +265,6 @@ pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u128 {
     (h1 as u128) << 96 | (h2 as u128) << 64 | (h3 as u128) << 32 | (h4 as u128)
 }
 
-/// MurmurHash3 32-bit implementation of the 128-bit hashing algorithm.
-/// The seed is always 0 in this version.
-pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
-    hash128_with_seed(v, 0)
-}
-
 #[inline(always)]
 fn get_u32(data: &[u8], i: usize) -> u32 {
     let buf = [data[i], data[i + 1], data[i + 2], data[i + 3]];
@@ -198,6 +284,9 @@ fn fmix32(h: u32) -> u32 {
 
 #[cfg(test)]
 mod test {
+    use crate::Hasher128;
+    use std::hash::Hasher;
+
     #[test]
     fn compliance_test() {
         assert_eq!(
@@ -205,4 +294,64 @@ mod test {
             0x4da5b4125adab9dc7d30c1c10bb975f7
         );
     }
+
+    #[test]
+    fn streaming_test() {
+        let input: Vec<u8> = (0..=255).collect();
+        for len in 0..input.len() {
+            let expected = crate::murmur::murmur3_128::hash128(&input[..len]);
+            for split in [1, 7, 16, 33] {
+                let mut hasher = crate::murmur::Murmur3Hasher128::default();
+                for chunk in input[..len].chunks(split) {
+                    hasher.write(chunk);
+                }
+                assert_eq!(hasher.finish(), expected as u64);
+                assert_eq!(hasher.finish128(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn seeded_builder_test() {
+        use std::hash::BuildHasher;
+        let mut hasher = crate::murmur::Murmur3Hash128::with_seed(4919).build_hasher();
+        hasher.write(b"StandardBlockEntityDescriptorV4");
+        assert_eq!(
+            hasher.finish(),
+            0x4da5b4125adab9dc7d30c1c10bb975f7u128 as u64
+        );
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn fasthash_interop_test() {
+        use fasthash::FastHash;
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let seed = 4919;
+        // fasthash returns the digest bytes as a little-endian integer
+        let expected = fasthash::murmur3::Hash128_x86::hash_with_seed(input, seed);
+        let expected = expected.to_le_bytes();
+        let expected = u128::from_be_bytes([
+            expected[3],
+            expected[2],
+            expected[1],
+            expected[0],
+            expected[7],
+            expected[6],
+            expected[5],
+            expected[4],
+            expected[11],
+            expected[10],
+            expected[9],
+            expected[8],
+            expected[15],
+            expected[14],
+            expected[13],
+            expected[12],
+        ]);
+        assert_eq!(
+            crate::murmur::murmur3_128::hash128_with_seed(input, seed),
+            expected
+        );
+    }
 }
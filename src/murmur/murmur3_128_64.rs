@@ -1,105 +1,231 @@
 use std::hash::{BuildHasher, Hasher};
 
+use crate::{HashAlgorithm, Hasher128};
+
 /// Hasher for MurmurHash3 64-bit implementation of the 128-bit hashing algorithm.
 #[derive(Default)]
 pub struct Murmur3Hasher128x64 {
-    buffer: Vec<u8>,
+    h: [u64; 2],
+    tail: [u8; 16],
+    tail_len: usize,
+    length: u64,
+}
+
+impl Murmur3Hasher128x64 {
+    /// Create a hasher which uses the given seed.
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            h: [seed as u64; 2],
+            ..Self::default()
+        }
+    }
 }
 
 impl Hasher for Murmur3Hasher128x64 {
     fn write(&mut self, bytes: &[u8]) {
-        self.buffer.extend(bytes)
+        let mut data = bytes;
+        self.length += data.len() as u64;
+        // complete a previously started block
+        if self.tail_len != 0 {
+            let fill = (16 - self.tail_len).min(data.len());
+            self.tail[self.tail_len..self.tail_len + fill].copy_from_slice(&data[..fill]);
+            self.tail_len += fill;
+            data = &data[fill..];
+            if self.tail_len < 16 {
+                return;
+            }
+            body(&mut self.h, &self.tail, 0);
+            self.tail_len = 0;
+        }
+        // process full blocks directly from the input
+        let n_blocks = data.len() / 16;
+        for i in 0..n_blocks {
+            body(&mut self.h, data, i * 16);
+        }
+        // keep the remainder for the next write
+        let rest = &data[n_blocks * 16..];
+        self.tail[..rest.len()].copy_from_slice(rest);
+        self.tail_len = rest.len();
     }
 
     fn finish(&self) -> u64 {
-        hash128_x64(&self.buffer) as u64
+        self.finish128() as u64
+    }
+}
+
+impl Hasher128 for Murmur3Hasher128x64 {
+    fn finish128(&self) -> u128 {
+        finalize(self.h, &self.tail[..self.tail_len], self.length)
     }
 }
 
 /// Hash builder for MurmurHash3 64-bit implementation of the 128-bit hashing algorithm.
-#[derive(Default)]
-pub struct Murmur3Hash128x64 {}
+#[derive(Clone, Copy, Default)]
+pub struct Murmur3Hash128x64 {
+    seed: u32,
+}
+
+impl Murmur3Hash128x64 {
+    /// Create a hash builder whose hashers use the given seed.
+    pub fn with_seed(seed: u32) -> Self {
+        Self { seed }
+    }
+}
 
 impl BuildHasher for Murmur3Hash128x64 {
     type Hasher = Murmur3Hasher128x64;
 
     fn build_hasher(&self) -> Self::Hasher {
-        Self::Hasher::default()
+        Self::Hasher::with_seed(self.seed)
     }
 }
 
+crate::random::random_state! {
+    /// Randomly seeded hash builder for MurmurHash3 64-bit implementation of the 128-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct Murmur3RandomState128x64(Murmur3Hash128x64::with_seed(seed: u32));
+}
+
 // The code below is adapted from C++ code with the following disclaimer
 //-----------------------------------------------------------------------------
 // MurmurHash3 was written by Austin Appleby, and is placed in the public
 // domain. The author hereby disclaims copyright to this source code.
 
+const C1: u64 = 0x87c37b91114253d5;
+const C2: u64 = 0x4cf5ad432745937f;
+
+const D1: u32 = 0x52dce729;
+const D2: u32 = 0x38495ab5;
+
+/// MurmurHash3 64-bit implementation of the 128-bit hashing algorithm.
+pub struct Murmur3x64_128;
+
+impl HashAlgorithm for Murmur3x64_128 {
+    const NAME: &'static str = "murmur3_x64_128";
+    type Seed = u32;
+    type Output = u128;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hash128_x64(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hash128_x64_with_seed(v, seed)
+    }
+}
+
 /// MurmurHash3 64-bit implementation of the 128-bit hashing algorithm.
 /// This version allows you to specify a seed.
 pub fn hash128_x64_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u128 {
     let data = v.as_ref();
     let n_blocks = data.len() / 16;
 
-    const C1: u64 = 0x87c37b91114253d5;
-    const C2: u64 = 0x4cf5ad432745937f;
+    let mut h = [seed as u64; 2];
+
+    // body
+    for i in 0..n_blocks {
+        body(&mut h, data, i * 16);
+    }
+
+    finalize(h, &data[n_blocks * 16..], data.len() as u64)
+}
+
+/// MurmurHash3 64-bit implementation of the 128-bit hashing algorithm.
+/// The seed is always 0 in this version.
+pub fn hash128_x64<T: AsRef<[u8]>>(v: T) -> u128 {
+    hash128_x64_with_seed(v, 0)
+}
+
+/// Like `hash128_x64_with_seed()`, but the tail bytes are sign-extended,
+/// as in Java ports which read them as `byte`.
+pub(crate) fn hash128_x64_sign_extended_with_seed(data: &[u8], seed: u32) -> u128 {
+    hash_with(data, seed as u64, |byte| byte as i8 as u64)
+}
+
+/// Like `hash128_x64_with_seed()`, but with a 64-bit seed,
+/// as in Java ports which take an `int` seed and sign-extend it to initialize the `long` state.
+pub(crate) fn hash128_x64_with_seed64(data: &[u8], seed: u64) -> u128 {
+    hash_with(data, seed, |byte| byte as u64)
+}
 
-    const D1: u32 = 0x52dce729;
-    const D2: u32 = 0x38495ab5;
+#[inline(always)]
+fn hash_with(data: &[u8], seed: u64, widen: impl Fn(u8) -> u64) -> u128 {
+    let n_blocks = data.len() / 16;
 
-    let mut h1: u64 = seed as u64;
-    let mut h2: u64 = seed as u64;
+    let mut h = [seed; 2];
 
     // body
     for i in 0..n_blocks {
-        let mut k1 = get_u64(data, (i + 0) * 8);
-        let mut k2 = get_u64(data, (i + 1) * 8);
-
-        k1 = k1.wrapping_mul(C1);
-        k1 = k1.rotate_left(31);
-        k1 = k1.wrapping_mul(C2);
-        h1 ^= k1;
-        h1 = h1.rotate_left(27);
-        h1 = h1.wrapping_add(h2);
-        h1 = (h1.wrapping_mul(5)).wrapping_add(D1 as u64);
-
-        k2 = k2.wrapping_mul(C2);
-        k2 = k2.rotate_left(33);
-        k2 = k2.wrapping_mul(C1);
-        h2 ^= k2;
-        h2 = h2.rotate_left(31);
-        h2 = h2.wrapping_add(h1);
-        h2 = (h2.wrapping_mul(5)).wrapping_add(D2 as u64);
+        body(&mut h, data, i * 16);
     }
 
+    finalize_with(h, &data[n_blocks * 16..], data.len() as u64, widen)
+}
+
+#[inline(always)]
+fn body(h: &mut [u64; 2], data: &[u8], i: usize) {
+    let [mut h1, mut h2] = *h;
+
+    let mut k1 = get_u64(data, i);
+    let mut k2 = get_u64(data, i + 8);
+
+    k1 = k1.wrapping_mul(C1);
+    k1 = k1.rotate_left(31);
+    k1 = k1.wrapping_mul(C2);
+    h1 ^= k1;
+    h1 = h1.rotate_left(27);
+    h1 = h1.wrapping_add(h2);
+    h1 = (h1.wrapping_mul(5)).wrapping_add(D1 as u64);
+
+    k2 = k2.wrapping_mul(C2);
+    k2 = k2.rotate_left(33);
+    k2 = k2.wrapping_mul(C1);
+    h2 ^= k2;
+    h2 = h2.rotate_left(31);
+    h2 = h2.wrapping_add(h1);
+    h2 = (h2.wrapping_mul(5)).wrapping_add(D2 as u64);
+
+    *h = [h1, h2];
+}
+
+#[inline(always)]
+fn finalize(h: [u64; 2], tail: &[u8], length: u64) -> u128 {
+    finalize_with(h, tail, length, |byte| byte as u64)
+}
+
+// `widen` converts a tail byte to the 64-bit value which is shifted into place
+#[inline(always)]
+fn finalize_with(h: [u64; 2], tail: &[u8], length: u64, widen: impl Fn(u8) -> u64) -> u128 {
+    let [mut h1, mut h2] = h;
+
     // tail
-    //let tail = data.clone();
-    let tail_num = n_blocks * 16;
     let mut k1 = 0;
     let mut k2 = 0;
-    for i in (1..=data.len() & 15).rev() {
+    for i in (1..=tail.len() & 15).rev() {
         match i {
-            15 => k2 ^= (data[tail_num + 14] as u64) << 48,
-            14 => k2 ^= (data[tail_num + 13] as u64) << 40,
-            13 => k2 ^= (data[tail_num + 12] as u64) << 32,
-            12 => k2 ^= (data[tail_num + 11] as u64) << 24,
-            11 => k2 ^= (data[tail_num + 10] as u64) << 16,
-            10 => k2 ^= (data[tail_num + 9] as u64) << 8,
+            15 => k2 ^= widen(tail[14]) << 48,
+            14 => k2 ^= widen(tail[13]) << 40,
+            13 => k2 ^= widen(tail[12]) << 32,
+            12 => k2 ^= widen(tail[11]) << 24,
+            11 => k2 ^= widen(tail[10]) << 16,
+            10 => k2 ^= widen(tail[9]) << 8,
             9 => {
-                k2 ^= (data[tail_num + 8] as u64) << 0;
+                k2 ^= widen(tail[8]);
                 k2 = k2.wrapping_mul(C2);
                 k2 = k2.rotate_left(33);
                 k2 = k2.wrapping_mul(C1);
                 h2 ^= k2;
             }
 
-            8 => k1 ^= (data[tail_num + 7] as u64) << 56,
-            7 => k1 ^= (data[tail_num + 6] as u64) << 48,
-            6 => k1 ^= (data[tail_num + 5] as u64) << 40,
-            5 => k1 ^= (data[tail_num + 4] as u64) << 32,
-            4 => k1 ^= (data[tail_num + 3] as u64) << 24,
-            3 => k1 ^= (data[tail_num + 2] as u64) << 16,
-            2 => k1 ^= (data[tail_num + 1] as u64) << 8,
+            8 => k1 ^= widen(tail[7]) << 56,
+            7 => k1 ^= widen(tail[6]) << 48,
+            6 => k1 ^= widen(tail[5]) << 40,
+            5 => k1 ^= widen(tail[4]) << 32,
+            4 => k1 ^= widen(tail[3]) << 24,
+            3 => k1 ^= widen(tail[2]) << 16,
+            2 => k1 ^= widen(tail[1]) << 8,
             1 => {
-                k1 ^= data[tail_num] as u64;
+                k1 ^= widen(tail[0]);
                 k1 = k1.wrapping_mul(C1);
                 k1 = k1.rotate_left(31);
                 k1 = k1.wrapping_mul(C2);
@@ -110,8 +236,8 @@ pub fn hash128_x64_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u128 {
     }
 
     // finalization
-    h1 ^= data.len() as u64;
-    h2 ^= data.len() as u64;
+    h1 ^= length;
+    h2 ^= length;
 
     h1 = h1.wrapping_add(h2);
     h2 = h2.wrapping_add(h1);
@@ -122,17 +248,11 @@ pub fn hash128_x64_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u128 {
     h1 = h1.wrapping_add(h2);
     h2 = h2.wrapping_add(h1);
 
-    (h1 as u128) << 64 | (h2 as u128) << 0
-}
-
-/// MurmurHash3 64-bit implementation of the 128-bit hashing algorithm.
-/// The seed is always 0 in this version.
-pub fn hash128_x64<T: AsRef<[u8]>>(v: T) -> u128 {
-    hash128_x64_with_seed(v, 0)
+    (h1 as u128) << 64 | (h2 as u128)
 }
 
 #[inline(always)]
-fn get_u64(data: &[u8], i: usize) -> u64 {
+pub(super) fn get_u64(data: &[u8], i: usize) -> u64 {
     let buf = [
         data[i],
         data[i + 1],
@@ -159,6 +279,9 @@ fn fmix64(h: u64) -> u64 {
 
 #[cfg(test)]
 mod test {
+    use crate::Hasher128;
+    use std::hash::Hasher;
+
     #[test]
     fn compliance_test() {
         assert_eq!(
@@ -169,4 +292,45 @@ mod test {
             0xb15ad2fb6e6b679225e57206d95bdb79
         );
     }
+
+    #[test]
+    fn streaming_test() {
+        let input: Vec<u8> = (0..=255).collect();
+        for len in 0..input.len() {
+            let expected = crate::murmur::murmur3_128_64::hash128_x64(&input[..len]);
+            for split in [1, 7, 16, 33] {
+                let mut hasher = crate::murmur::Murmur3Hasher128x64::default();
+                for chunk in input[..len].chunks(split) {
+                    hasher.write(chunk);
+                }
+                assert_eq!(hasher.finish(), expected as u64);
+                assert_eq!(hasher.finish128(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn seeded_builder_test() {
+        use std::hash::BuildHasher;
+        let mut hasher = crate::murmur::Murmur3Hash128x64::with_seed(4919).build_hasher();
+        hasher.write(b"StandardBlockEntityDescriptorV4");
+        assert_eq!(
+            hasher.finish(),
+            0xb15ad2fb6e6b679225e57206d95bdb79u128 as u64
+        );
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn fasthash_interop_test() {
+        use fasthash::FastHash;
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let seed = 4919;
+        // fasthash returns the digest bytes as a little-endian integer, h1 first
+        let expected = fasthash::murmur3::Hash128_x64::hash_with_seed(input, seed);
+        assert_eq!(
+            crate::murmur::murmur3_128_64::hash128_x64_with_seed(input, seed),
+            expected.rotate_left(64)
+        );
+    }
 }
@@ -1,73 +1,195 @@
 use std::hash::{BuildHasher, Hasher};
 
+use crate::HashAlgorithm;
+
 /// Hasher for MurmurHash3 32-bit implementation of the 32-bit hashing algorithm.
 #[derive(Default)]
 pub struct Murmur3Hasher32 {
-    buffer: Vec<u8>,
+    h1: u32,
+    tail: [u8; 4],
+    tail_len: usize,
+    length: u64,
+}
+
+impl Murmur3Hasher32 {
+    /// Create a hasher which uses the given seed.
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            h1: seed,
+            ..Self::default()
+        }
+    }
 }
 
 impl Hasher for Murmur3Hasher32 {
     fn write(&mut self, bytes: &[u8]) {
-        self.buffer.extend(bytes)
+        let mut data = bytes;
+        self.length += data.len() as u64;
+        // complete a previously started block
+        if self.tail_len != 0 {
+            let fill = (4 - self.tail_len).min(data.len());
+            self.tail[self.tail_len..self.tail_len + fill].copy_from_slice(&data[..fill]);
+            self.tail_len += fill;
+            data = &data[fill..];
+            if self.tail_len < 4 {
+                return;
+            }
+            self.h1 = body(self.h1, get_u32(&self.tail, 0));
+            self.tail_len = 0;
+        }
+        // process full blocks directly from the input
+        let n_blocks = data.len() / 4;
+        for i in 0..n_blocks {
+            self.h1 = body(self.h1, get_u32(data, i * 4));
+        }
+        // keep the remainder for the next write
+        let rest = &data[n_blocks * 4..];
+        self.tail[..rest.len()].copy_from_slice(rest);
+        self.tail_len = rest.len();
     }
 
     fn finish(&self) -> u64 {
-        hash32(&self.buffer) as u64
+        finalize(self.h1, &self.tail[..self.tail_len], self.length) as u64
     }
 }
 
 /// Hash builder for MurmurHash3 32-bit implementation of the 32-bit hashing algorithm.
-#[derive(Default)]
-pub struct Murmur3Hash32 {}
+#[derive(Clone, Copy, Default)]
+pub struct Murmur3Hash32 {
+    seed: u32,
+}
+
+impl Murmur3Hash32 {
+    /// Create a hash builder whose hashers use the given seed.
+    pub fn with_seed(seed: u32) -> Self {
+        Self { seed }
+    }
+}
 
 impl BuildHasher for Murmur3Hash32 {
     type Hasher = Murmur3Hasher32;
 
     fn build_hasher(&self) -> Self::Hasher {
-        Self::Hasher::default()
+        Self::Hasher::with_seed(self.seed)
     }
 }
 
+crate::random::random_state! {
+    /// Randomly seeded hash builder for MurmurHash3 32-bit implementation of the 32-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct Murmur3RandomState32(Murmur3Hash32::with_seed(seed: u32));
+}
+
 // The code below is adapted from C++ code with the following disclaimer
 //-----------------------------------------------------------------------------
 // MurmurHash3 was written by Austin Appleby, and is placed in the public
 // domain. The author hereby disclaims copyright to this source code.
 
+const C1: u32 = 0xcc9e2d51;
+const C2: u32 = 0x1b873593;
+const D: u32 = 0xe6546b64;
+
+/// MurmurHash3 32-bit implementation of the 32-bit hashing algorithm.
+pub struct Murmur3x86_32;
+
+impl HashAlgorithm for Murmur3x86_32 {
+    const NAME: &'static str = "murmur3_x86_32";
+    type Seed = u32;
+    type Output = u32;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hash32(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hash32_with_seed(v, seed)
+    }
+}
+
 /// MurmurHash3 32-bit implementation of the 32-bit hashing algorithm.
 /// This version allows you to specify a seed.
 pub fn hash32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
-    let data = v.as_ref().clone();
+    let data = v.as_ref();
     let n_blocks = data.len() / 4;
 
-    const C1: u32 = 0xcc9e2d51;
-    const C2: u32 = 0x1b873593;
-    const D: u32 = 0xe6546b64;
+    let mut h1: u32 = seed;
+
+    // body
+    for i in 0..n_blocks {
+        h1 = body(h1, get_u32(data, i * 4));
+    }
+
+    finalize(h1, &data[n_blocks * 4..], data.len() as u64)
+}
+
+/// MurmurHash3 32-bit implementation of the 32-bit hashing algorithm.
+/// The seed is always 0 in this version.
+pub fn hash32<T: AsRef<[u8]>>(v: T) -> u32 {
+    hash32_with_seed(v, 0)
+}
+
+/// Like `hash32_with_seed()`, but the tail bytes are sign-extended,
+/// as in Java ports which read them as `byte`.
+pub(crate) fn hash32_sign_extended_with_seed(data: &[u8], seed: u32) -> u32 {
+    let n_blocks = data.len() / 4;
 
     let mut h1: u32 = seed;
 
     // body
     for i in 0..n_blocks {
-        let mut k1 = get_u32(data, i * 4);
+        h1 = body(h1, get_u32(data, i * 4));
+    }
 
-        k1 = k1.wrapping_mul(C1);
-        k1 = k1.rotate_left(15);
-        k1 = k1.wrapping_mul(C2);
+    finalize_with(h1, &data[n_blocks * 4..], data.len() as u64, |byte| {
+        byte as i8 as u32
+    })
+}
+
+/// Like `hash32_with_seed()`, but every tail byte is sign-extended and mixed in as a block of its own,
+/// as in Spark's `Murmur3_x86_32.hashUnsafeBytes()`.
+pub(crate) fn hash32_bytewise_tail_with_seed(data: &[u8], seed: u32) -> u32 {
+    let n_blocks = data.len() / 4;
 
-        h1 ^= k1;
-        h1 = h1.rotate_left(13);
-        h1 = (h1.wrapping_mul(5)).wrapping_add(D);
+    let mut h1: u32 = seed;
+
+    // body
+    for i in 0..n_blocks {
+        h1 = body(h1, get_u32(data, i * 4));
+    }
+    for &byte in &data[n_blocks * 4..] {
+        h1 = body(h1, byte as i8 as u32);
     }
 
+    finalize(h1, &[], data.len() as u64)
+}
+
+#[inline(always)]
+fn body(mut h1: u32, mut k1: u32) -> u32 {
+    k1 = k1.wrapping_mul(C1);
+    k1 = k1.rotate_left(15);
+    k1 = k1.wrapping_mul(C2);
+
+    h1 ^= k1;
+    h1 = h1.rotate_left(13);
+    (h1.wrapping_mul(5)).wrapping_add(D)
+}
+
+#[inline(always)]
+fn finalize(h1: u32, tail: &[u8], length: u64) -> u32 {
+    finalize_with(h1, tail, length, |byte| byte as u32)
+}
+
+// `widen` converts a tail byte to the 32-bit value which is shifted into place
+#[inline(always)]
+fn finalize_with(mut h1: u32, tail: &[u8], length: u64, widen: impl Fn(u8) -> u32) -> u32 {
     // tail
-    let tail = data.clone();
-    let tail_num = n_blocks * 4;
     let mut k1 = 0;
-    for i in (1..=data.len() & 3).rev() {
+    for i in (1..=tail.len() & 3).rev() {
         match i {
-            3 => k1 ^= (tail[tail_num + 2] as u32) << 16,
-            2 => k1 ^= (tail[tail_num + 1] as u32) << 8,
+            3 => k1 ^= widen(tail[2]) << 16,
+            2 => k1 ^= widen(tail[1]) << 8,
             1 => {
-                k1 ^= tail[tail_num] as u32;
+                k1 ^= widen(tail[0]);
                 k1 = k1.wrapping_mul(C1);
                 k1 = k1.rotate_left(15);
                 k1 = k1.wrapping_mul(C2);
@@ -78,20 +200,12 @@ pub fn hash32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
     }
 
     // finalization
-    h1 ^= data.len() as u32;
-    h1 = fmix32(h1);
-
-    h1
-}
-
-/// MurmurHash3 32-bit implementation of the 32-bit hashing algorithm.
-/// The seed is always 0 in this version.
-pub fn hash32<T: AsRef<[u8]>>(v: T) -> u32 {
-    hash32_with_seed(v, 0)
+    h1 ^= length as u32;
+    fmix32(h1)
 }
 
 #[inline(always)]
-fn get_u32(data: &[u8], i: usize) -> u32 {
+pub(super) fn get_u32(data: &[u8], i: usize) -> u32 {
     let buf = [data[i], data[i + 1], data[i + 2], data[i + 3]];
     u32::from_le_bytes(buf)
 }
@@ -109,6 +223,8 @@ fn fmix32(h: u32) -> u32 {
 
 #[cfg(test)]
 mod test {
+    use std::hash::Hasher;
+
     #[test]
     fn compliance_test() {
         assert_eq!(
@@ -116,4 +232,50 @@ mod test {
             1357220432
         );
     }
+
+    #[test]
+    fn hash_algorithm_test() {
+        use crate::murmur::Murmur3x86_32;
+        use crate::HashAlgorithm;
+        assert_eq!(Murmur3x86_32::NAME, "murmur3_x86_32");
+        assert_eq!(
+            Murmur3x86_32::hash_with_seed("StandardBlockEntityDescriptorV4", 4919),
+            1357220432
+        );
+        assert_eq!(Murmur3x86_32::hash("abc"), crate::murmur::hash32("abc"));
+    }
+
+    #[test]
+    fn streaming_test() {
+        let input: Vec<u8> = (0..=255).collect();
+        for len in 0..input.len() {
+            let expected = crate::murmur::murmur3_32::hash32(&input[..len]) as u64;
+            for split in [1, 3, 4, 7] {
+                let mut hasher = crate::murmur::Murmur3Hasher32::default();
+                for chunk in input[..len].chunks(split) {
+                    hasher.write(chunk);
+                }
+                assert_eq!(hasher.finish(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn seeded_builder_test() {
+        use std::hash::BuildHasher;
+        let mut hasher = crate::murmur::Murmur3Hash32::with_seed(4919).build_hasher();
+        hasher.write(b"StandardBlockEntityDescriptorV4");
+        assert_eq!(hasher.finish(), 1357220432);
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn fasthash_interop_test() {
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let seed = 4919;
+        assert_eq!(
+            crate::murmur::murmur3_32::hash32_with_seed(input, seed),
+            fasthash::murmur3::hash32_with_seed(input, seed)
+        );
+    }
 }
@@ -0,0 +1,125 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+// std's RandomState keys are drawn from OS entropy once per thread and advanced for every new
+// instance, so hashing nothing with a fresh one yields a new unpredictable value each call.
+pub(crate) fn random_u64() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+pub(crate) fn random_u128() -> u128 {
+    (random_u64() as u128) << 64 | (random_u64() as u128)
+}
+
+// Seeds and keys which can be drawn from OS entropy, for `random_state!`.
+pub(crate) trait Random {
+    fn random() -> Self;
+}
+
+impl Random for u32 {
+    fn random() -> Self {
+        random_u64() as u32
+    }
+}
+
+impl Random for u64 {
+    fn random() -> Self {
+        random_u64()
+    }
+}
+
+impl Random for u128 {
+    fn random() -> Self {
+        random_u128()
+    }
+}
+
+impl Random for [u64; 4] {
+    fn random() -> Self {
+        [random_u64(), random_u64(), random_u64(), random_u64()]
+    }
+}
+
+// Defines a randomly seeded hash builder, like `std::collections::hash_map::RandomState`,
+// which wraps a hash builder and passes random arguments to one of its constructors:
+//
+//     random_state! {
+//         /// Randomly seeded hash builder for ...
+//         #[derive(Clone, Copy)]
+//         pub struct CityRandomState64(CityHash64::with_seeds(seed0: u64, seed1: u64));
+//     }
+macro_rules! random_state {
+    (
+        $(#[$attr:meta])*
+        pub struct $name:ident($builder:ident::$ctor:ident($($arg:ident: $ty:ty),+));
+    ) => {
+        $(#[$attr])*
+        ///
+        #[doc = concat!(
+            "Every instance passes values drawn from OS entropy to `",
+            stringify!($ctor),
+            "()`, see [Random seeds](crate#random-seeds).",
+        )]
+        pub struct $name {
+            builder: $builder,
+        }
+
+        impl $name {
+            /// Create a hash builder seeded from OS entropy.
+            pub fn new() -> Self {
+                Self::$ctor($(<$ty as crate::random::Random>::random()),+)
+            }
+
+            #[doc = concat!(
+                "Create a hash builder like `",
+                stringify!($builder),
+                "::",
+                stringify!($ctor),
+                "()`, for reproducible results.",
+            )]
+            pub fn $ctor($($arg: $ty),+) -> Self {
+                Self {
+                    builder: $builder::$ctor($($arg),+),
+                }
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl std::hash::BuildHasher for $name {
+            type Hasher = <$builder as std::hash::BuildHasher>::Hasher;
+
+            fn build_hasher(&self) -> Self::Hasher {
+                std::hash::BuildHasher::build_hasher(&self.builder)
+            }
+        }
+    };
+}
+
+pub(crate) use random_state;
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn random_test() {
+        assert_ne!(crate::random::random_u64(), crate::random::random_u64());
+        assert_ne!(crate::random::random_u128(), crate::random::random_u128());
+    }
+
+    #[test]
+    fn random_state_test() {
+        use std::hash::BuildHasher;
+        let a = crate::city::CityRandomState64::new();
+        let b = crate::city::CityRandomState64::default();
+        assert_ne!(a.hash_one("highhash"), b.hash_one("highhash"));
+        assert_eq!(a.hash_one("highhash"), a.hash_one("highhash"));
+        assert_eq!(
+            crate::city::CityRandomState64::with_seeds(1, 2).hash_one("highhash"),
+            crate::city::CityHash64::with_seeds(1, 2).hash_one("highhash")
+        );
+    }
+}
@@ -0,0 +1,218 @@
+//! Runtime selection of hashing algorithms by their canonical name.
+//!
+//! ```
+//! let algorithm = highhash::registry::lookup("murmur3_x86_32").unwrap();
+//! assert_eq!(algorithm.bits(), 32);
+//! assert_eq!(algorithm.hash(b"abc"), highhash::murmur::hash32("abc") as u128);
+//! ```
+use std::error::Error;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+
+use crate::{
+    city, farm, fnv, highway, jenkins, metro, murmur, sip, spooky, wy, xxhash, HashAlgorithm,
+};
+
+/// A hashing algorithm selected at runtime.
+#[derive(Clone, Copy)]
+pub struct Algorithm {
+    name: &'static str,
+    bits: u32,
+    hash: fn(&[u8]) -> u128,
+    hasher: fn() -> Box<dyn Hasher + Send + Sync>,
+}
+
+impl Algorithm {
+    const fn new<A, B>() -> Self
+    where
+        A: HashAlgorithm,
+        A::Output: Into<u128>,
+        B: BuildHasher + Default,
+        B::Hasher: Send + Sync + 'static,
+    {
+        Algorithm {
+            name: A::NAME,
+            bits: (std::mem::size_of::<A::Output>() * 8) as u32,
+            hash: one_shot::<A>,
+            hasher: boxed_hasher::<B>,
+        }
+    }
+
+    /// Canonical name of the algorithm.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Width of the hash values produced by the algorithm, in bits.
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Hash the data without a seed.
+    /// Hash values narrower than 128 bits are zero-extended.
+    pub fn hash(&self, data: &[u8]) -> u128 {
+        (self.hash)(data)
+    }
+
+    /// Create a streaming hasher for the algorithm.
+    /// For algorithms wider than 64 bits, `finish()` returns the lower 64 bits of the hash.
+    pub fn hasher(&self) -> Box<dyn Hasher + Send + Sync> {
+        (self.hasher)()
+    }
+}
+
+impl fmt::Debug for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Algorithm")
+            .field("name", &self.name)
+            .field("bits", &self.bits)
+            .finish()
+    }
+}
+
+fn one_shot<A>(data: &[u8]) -> u128
+where
+    A: HashAlgorithm,
+    A::Output: Into<u128>,
+{
+    A::hash(data).into()
+}
+
+fn boxed_hasher<B>() -> Box<dyn Hasher + Send + Sync>
+where
+    B: BuildHasher + Default,
+    B::Hasher: Send + Sync + 'static,
+{
+    Box::new(B::default().build_hasher())
+}
+
+const ALGORITHMS: &[Algorithm] = &[
+    Algorithm::new::<city::City32, city::CityHash32>(),
+    Algorithm::new::<city::City64, city::CityHash64>(),
+    Algorithm::new::<city::City128, city::CityHash128>(),
+    Algorithm::new::<city::CityCrc128, city::CityCrcHash128>(),
+    Algorithm::new::<farm::Farm32, farm::FarmHash32>(),
+    Algorithm::new::<farm::Farm64, farm::FarmHash64>(),
+    Algorithm::new::<farm::Farm128, farm::FarmHash128>(),
+    Algorithm::new::<fnv::Fnv1_32, fnv::Fnv1Hash32>(),
+    Algorithm::new::<fnv::Fnv1a32, fnv::Fnv1aHash32>(),
+    Algorithm::new::<fnv::Fnv1_64, fnv::Fnv1Hash64>(),
+    Algorithm::new::<fnv::Fnv1a64, fnv::Fnv1aHash64>(),
+    Algorithm::new::<fnv::Fnv1_128, fnv::Fnv1Hash128>(),
+    Algorithm::new::<fnv::Fnv1a128, fnv::Fnv1aHash128>(),
+    Algorithm::new::<highway::Highway64, highway::HighwayHash64>(),
+    Algorithm::new::<highway::Highway128, highway::HighwayHash128>(),
+    Algorithm::new::<jenkins::Lookup2, jenkins::Lookup2Hash32>(),
+    Algorithm::new::<jenkins::Lookup3, jenkins::Lookup3Hash32>(),
+    Algorithm::new::<jenkins::Lookup3Big, jenkins::Lookup3BigHash32>(),
+    Algorithm::new::<jenkins::Lookup3_64, jenkins::Lookup3Hash64>(),
+    Algorithm::new::<jenkins::OneAtATime, jenkins::OneAtATimeHash32>(),
+    Algorithm::new::<metro::Metro64_1, metro::MetroHash64_1>(),
+    Algorithm::new::<metro::Metro64_2, metro::MetroHash64_2>(),
+    Algorithm::new::<metro::Metro128_1, metro::MetroHash128_1>(),
+    Algorithm::new::<metro::Metro128_2, metro::MetroHash128_2>(),
+    Algorithm::new::<murmur::Murmur2_32, murmur::Murmur2Hash32>(),
+    Algorithm::new::<murmur::Murmur2A32, murmur::Murmur2AHash32>(),
+    Algorithm::new::<murmur::MurmurNeutral2_32, murmur::Murmur2Hash32>(),
+    Algorithm::new::<murmur::MurmurAligned2_32, murmur::Murmur2Hash32>(),
+    Algorithm::new::<murmur::Murmur2_64A, murmur::Murmur2Hash64A>(),
+    Algorithm::new::<murmur::Murmur2_64B, murmur::Murmur2Hash64B>(),
+    Algorithm::new::<murmur::Murmur3x86_32, murmur::Murmur3Hash32>(),
+    Algorithm::new::<murmur::Murmur3x86_128, murmur::Murmur3Hash128>(),
+    Algorithm::new::<murmur::Murmur3x64_128, murmur::Murmur3Hash128x64>(),
+    Algorithm::new::<sip::HalfSip24_32, sip::HalfSip24Hash32>(),
+    Algorithm::new::<sip::HalfSip24_64, sip::HalfSip24Hash64>(),
+    Algorithm::new::<sip::Sip13_64, sip::Sip13Hash64>(),
+    Algorithm::new::<sip::Sip13_128, sip::Sip13Hash128>(),
+    Algorithm::new::<sip::Sip24_64, sip::Sip24Hash64>(),
+    Algorithm::new::<sip::Sip24_128, sip::Sip24Hash128>(),
+    Algorithm::new::<spooky::Spooky32, spooky::SpookyHash32>(),
+    Algorithm::new::<spooky::Spooky64, spooky::SpookyHash64>(),
+    Algorithm::new::<spooky::Spooky128, spooky::SpookyHash128>(),
+    Algorithm::new::<wy::Wyhash, wy::WyHash64>(),
+    Algorithm::new::<wy::Rapidhash, wy::RapidHash64>(),
+    Algorithm::new::<xxhash::Xxh32, xxhash::XxHash32>(),
+    Algorithm::new::<xxhash::Xxh64, xxhash::XxHash64>(),
+    Algorithm::new::<xxhash::Xxh3_64, xxhash::Xxh3Hash64>(),
+    Algorithm::new::<xxhash::Xxh3_128, xxhash::Xxh3Hash128>(),
+];
+
+/// All algorithms known to the registry.
+pub fn algorithms() -> &'static [Algorithm] {
+    ALGORITHMS
+}
+
+/// Find an algorithm by its canonical name, as given by `HashAlgorithm::NAME`.
+pub fn lookup(name: &str) -> Result<Algorithm, UnknownAlgorithm> {
+    ALGORITHMS
+        .iter()
+        .find(|algorithm| algorithm.name == name)
+        .copied()
+        .ok_or_else(|| UnknownAlgorithm {
+            name: name.to_owned(),
+        })
+}
+
+/// Error returned when looking up an algorithm name which is not in the registry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownAlgorithm {
+    name: String,
+}
+
+impl UnknownAlgorithm {
+    /// The name which was looked up.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Display for UnknownAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown hashing algorithm '{}'", self.name)
+    }
+}
+
+impl Error for UnknownAlgorithm {}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn lookup_test() {
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let algorithm = super::lookup("city64").unwrap();
+        assert_eq!(algorithm.name(), "city64");
+        assert_eq!(algorithm.bits(), 64);
+        assert_eq!(
+            algorithm.hash(input.as_bytes()),
+            crate::city::hash64(input) as u128
+        );
+        let algorithm = super::lookup("murmur3_x64_128").unwrap();
+        assert_eq!(algorithm.bits(), 128);
+        assert_eq!(
+            algorithm.hash(input.as_bytes()),
+            crate::murmur::hash128_x64(input)
+        );
+    }
+
+    #[test]
+    fn hasher_test() {
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        for algorithm in super::algorithms() {
+            let mut hasher = algorithm.hasher();
+            hasher.write(input.as_bytes());
+            assert_eq!(
+                hasher.finish(),
+                algorithm.hash(input.as_bytes()) as u64,
+                "{}",
+                algorithm.name()
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_test() {
+        let error = super::lookup("md5").unwrap_err();
+        assert_eq!(error.name(), "md5");
+        assert_eq!(error.to_string(), "unknown hashing algorithm 'md5'");
+    }
+}
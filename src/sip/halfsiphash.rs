@@ -0,0 +1,407 @@
+use std::hash::{BuildHasher, Hasher};
+
+use crate::HashAlgorithm;
+
+/// Hasher for HalfSipHash-2-4 implementation of the 32-bit hashing algorithm.
+#[derive(Clone, Copy)]
+pub struct HalfSip24Hasher32 {
+    state: State,
+}
+
+impl HalfSip24Hasher32 {
+    /// Create a hasher which uses the given 64-bit key, like `halfsiphash24_with_key()`.
+    pub fn with_key(key: u64) -> Self {
+        Self {
+            state: State::new(key, false),
+        }
+    }
+
+    /// Create a hasher which uses the key made of the two given halves.
+    /// `k0` holds the first 4 bytes of the key and `k1` the last 4 bytes, both little-endian.
+    pub fn with_keys(k0: u32, k1: u32) -> Self {
+        Self::with_key(to_key(k0, k1))
+    }
+}
+
+impl Default for HalfSip24Hasher32 {
+    fn default() -> Self {
+        Self::with_key(0)
+    }
+}
+
+impl Hasher for HalfSip24Hasher32 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.state.write(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        self.state.finish32() as u64
+    }
+}
+
+/// Hash builder for HalfSipHash-2-4 implementation of the 32-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct HalfSip24Hash32 {
+    key: u64,
+}
+
+impl HalfSip24Hash32 {
+    /// Create a hash builder whose hashers use the given 64-bit key, like `halfsiphash24_with_key()`.
+    pub fn with_key(key: u64) -> Self {
+        Self { key }
+    }
+
+    /// Create a hash builder whose hashers use the key made of the two given halves,
+    /// see `HalfSip24Hasher32::with_keys()`.
+    pub fn with_keys(k0: u32, k1: u32) -> Self {
+        Self::with_key(to_key(k0, k1))
+    }
+}
+
+impl BuildHasher for HalfSip24Hash32 {
+    type Hasher = HalfSip24Hasher32;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_key(self.key)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly keyed hash builder for HalfSipHash-2-4 implementation of the 32-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct HalfSip24RandomState32(HalfSip24Hash32::with_key(key: u64));
+}
+
+/// Hasher for HalfSipHash-2-4 implementation of the 64-bit hashing algorithm.
+#[derive(Clone, Copy)]
+pub struct HalfSip24Hasher64 {
+    state: State,
+}
+
+impl HalfSip24Hasher64 {
+    /// Create a hasher which uses the given 64-bit key, like `halfsiphash24_64_with_key()`.
+    pub fn with_key(key: u64) -> Self {
+        Self {
+            state: State::new(key, true),
+        }
+    }
+
+    /// Create a hasher which uses the key made of the two given halves.
+    /// `k0` holds the first 4 bytes of the key and `k1` the last 4 bytes, both little-endian.
+    pub fn with_keys(k0: u32, k1: u32) -> Self {
+        Self::with_key(to_key(k0, k1))
+    }
+}
+
+impl Default for HalfSip24Hasher64 {
+    fn default() -> Self {
+        Self::with_key(0)
+    }
+}
+
+impl Hasher for HalfSip24Hasher64 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.state.write(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        self.state.finish64()
+    }
+}
+
+/// Hash builder for HalfSipHash-2-4 implementation of the 64-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct HalfSip24Hash64 {
+    key: u64,
+}
+
+impl HalfSip24Hash64 {
+    /// Create a hash builder whose hashers use the given 64-bit key, like `halfsiphash24_64_with_key()`.
+    pub fn with_key(key: u64) -> Self {
+        Self { key }
+    }
+
+    /// Create a hash builder whose hashers use the key made of the two given halves,
+    /// see `HalfSip24Hasher64::with_keys()`.
+    pub fn with_keys(k0: u32, k1: u32) -> Self {
+        Self::with_key(to_key(k0, k1))
+    }
+}
+
+impl BuildHasher for HalfSip24Hash64 {
+    type Hasher = HalfSip24Hasher64;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_key(self.key)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly keyed hash builder for HalfSipHash-2-4 implementation of the 64-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct HalfSip24RandomState64(HalfSip24Hash64::with_key(key: u64));
+}
+
+// The code below is adapted from C code with the following license
+//-----------------------------------------------------------------------------
+// HalfSipHash reference C implementation
+//
+// Copyright (c) 2012-2021 Jean-Philippe Aumasson
+// <jeanphilippe.aumasson@gmail.com>
+// Copyright (c) 2012-2014 Daniel J. Bernstein <djb@cr.yp.to>
+//
+// To the extent possible under law, the author(s) have dedicated all copyright
+// and related and neighboring rights to this software to the public domain
+// worldwide. This software is distributed without any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication along
+// with this software. If not, see
+// <http://creativecommons.org/publicdomain/zero/1.0/>.
+
+/// HalfSipHash-2-4 implementation of the 32-bit hashing algorithm.
+pub struct HalfSip24_32;
+
+impl HashAlgorithm for HalfSip24_32 {
+    const NAME: &'static str = "halfsiphash24";
+    type Seed = u64;
+    type Output = u32;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        halfsiphash24(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        halfsiphash24_with_key(v, seed)
+    }
+}
+
+/// HalfSipHash-2-4 implementation of the 32-bit hashing algorithm.
+/// This version allows you to specify the 64-bit key.
+///
+/// The key is the little-endian reading of the 8 key bytes of the reference implementation.
+pub fn halfsiphash24_with_key<T: AsRef<[u8]>>(v: T, key: u64) -> u32 {
+    let mut state = State::new(key, false);
+    state.write(v.as_ref());
+    state.finish32()
+}
+
+/// HalfSipHash-2-4 implementation of the 32-bit hashing algorithm.
+/// The key is always 0 in this version.
+pub fn halfsiphash24<T: AsRef<[u8]>>(v: T) -> u32 {
+    halfsiphash24_with_key(v, 0)
+}
+
+/// HalfSipHash-2-4 implementation of the 64-bit hashing algorithm.
+pub struct HalfSip24_64;
+
+impl HashAlgorithm for HalfSip24_64 {
+    const NAME: &'static str = "halfsiphash24_64";
+    type Seed = u64;
+    type Output = u64;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        halfsiphash24_64(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        halfsiphash24_64_with_key(v, seed)
+    }
+}
+
+/// HalfSipHash-2-4 implementation of the 64-bit hashing algorithm.
+/// This version allows you to specify the 64-bit key.
+///
+/// The key is the little-endian reading of the 8 key bytes of the reference implementation.
+pub fn halfsiphash24_64_with_key<T: AsRef<[u8]>>(v: T, key: u64) -> u64 {
+    let mut state = State::new(key, true);
+    state.write(v.as_ref());
+    state.finish64()
+}
+
+/// HalfSipHash-2-4 implementation of the 64-bit hashing algorithm.
+/// The key is always 0 in this version.
+pub fn halfsiphash24_64<T: AsRef<[u8]>>(v: T) -> u64 {
+    halfsiphash24_64_with_key(v, 0)
+}
+
+// the key bytes of the reference implementation, read as a little-endian integer
+#[inline(always)]
+fn to_key(k0: u32, k1: u32) -> u64 {
+    (k1 as u64) << 32 | k0 as u64
+}
+
+/// Streaming state of HalfSipHash-2-4, shared by the 32-bit and 64-bit variants.
+#[derive(Clone, Copy)]
+struct State {
+    v: [u32; 4],
+    tail: [u8; 4],
+    tail_len: usize,
+    length: u64,
+}
+
+impl State {
+    // `wide` selects the initialization of the 64-bit variant
+    fn new(key: u64, wide: bool) -> Self {
+        let k0 = key as u32;
+        let k1 = (key >> 32) as u32;
+        let mut v = [k0, k1, k0 ^ 0x6c796765, k1 ^ 0x74656462];
+        if wide {
+            v[1] ^= 0xee;
+        }
+        Self {
+            v,
+            tail: [0; 4],
+            tail_len: 0,
+            length: 0,
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        let mut data = bytes;
+        self.length += data.len() as u64;
+        // complete a previously started word
+        if self.tail_len != 0 {
+            let fill = (4 - self.tail_len).min(data.len());
+            self.tail[self.tail_len..self.tail_len + fill].copy_from_slice(&data[..fill]);
+            self.tail_len += fill;
+            data = &data[fill..];
+            if self.tail_len < 4 {
+                return;
+            }
+            let m = u32::from_le_bytes(self.tail);
+            self.compress(m);
+            self.tail_len = 0;
+        }
+        // process full words directly from the input
+        let mut chunks = data.chunks_exact(4);
+        for chunk in &mut chunks {
+            self.compress(u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]));
+        }
+        // keep the remainder for the next write
+        let rest = chunks.remainder();
+        self.tail[..rest.len()].copy_from_slice(rest);
+        self.tail_len = rest.len();
+    }
+
+    fn finish32(&self) -> u32 {
+        let mut state = *self;
+        state.compress(self.last_word());
+        state.v[2] ^= 0xff;
+        state.rounds(4);
+        state.v[1] ^ state.v[3]
+    }
+
+    fn finish64(&self) -> u64 {
+        let mut state = *self;
+        state.compress(self.last_word());
+        state.v[2] ^= 0xee;
+        state.rounds(4);
+        let low = state.v[1] ^ state.v[3];
+        state.v[1] ^= 0xdd;
+        state.rounds(4);
+        let high = state.v[1] ^ state.v[3];
+        (high as u64) << 32 | low as u64
+    }
+
+    // the remaining bytes, with the input length in the top byte
+    #[inline(always)]
+    fn last_word(&self) -> u32 {
+        let mut buf = [0; 4];
+        buf[..self.tail_len].copy_from_slice(&self.tail[..self.tail_len]);
+        buf[3] = self.length as u8;
+        u32::from_le_bytes(buf)
+    }
+
+    #[inline(always)]
+    fn compress(&mut self, m: u32) {
+        self.v[3] ^= m;
+        self.rounds(2);
+        self.v[0] ^= m;
+    }
+
+    #[inline(always)]
+    fn rounds(&mut self, n: usize) {
+        let [mut v0, mut v1, mut v2, mut v3] = self.v;
+        for _ in 0..n {
+            v0 = v0.wrapping_add(v1);
+            v1 = v1.rotate_left(5);
+            v1 ^= v0;
+            v0 = v0.rotate_left(16);
+            v2 = v2.wrapping_add(v3);
+            v3 = v3.rotate_left(8);
+            v3 ^= v2;
+            v0 = v0.wrapping_add(v3);
+            v3 = v3.rotate_left(7);
+            v3 ^= v0;
+            v2 = v2.wrapping_add(v1);
+            v1 = v1.rotate_left(13);
+            v1 ^= v2;
+            v2 = v2.rotate_left(16);
+        }
+        self.v = [v0, v1, v2, v3];
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    // the reference implementation hashes {0, 1, 2, ...} with the key {0, 1, ..., 7}
+    const KEY: u64 = 0x0706050403020100;
+
+    #[test]
+    fn compliance_test() {
+        let input: Vec<u8> = (0..8).collect();
+        let expected_32: [[u8; 4]; 8] = [
+            [0xa9, 0x35, 0x9f, 0x5b],
+            [0x27, 0x47, 0x5a, 0xb8],
+            [0xfa, 0x62, 0xa6, 0x03],
+            [0x8a, 0xfe, 0xe7, 0x04],
+            [0x2a, 0x6e, 0x46, 0x89],
+            [0xc5, 0xfa, 0xb6, 0x69],
+            [0x58, 0x63, 0xfc, 0x23],
+            [0x8b, 0xcf, 0x63, 0xc5],
+        ];
+        for (len, hash) in expected_32.iter().enumerate() {
+            assert_eq!(
+                crate::sip::halfsiphash::halfsiphash24_with_key(&input[..len], KEY),
+                u32::from_le_bytes(*hash)
+            );
+        }
+        let expected_64: [[u8; 8]; 4] = [
+            [0x21, 0x8d, 0x1f, 0x59, 0xb9, 0xb8, 0x3c, 0xc8],
+            [0xbe, 0x55, 0x24, 0x12, 0xf8, 0x38, 0x73, 0x15],
+            [0x06, 0x4f, 0x39, 0xef, 0x7c, 0x50, 0xeb, 0x57],
+            [0xce, 0x0f, 0x1a, 0x45, 0xf7, 0x06, 0x06, 0x79],
+        ];
+        for (len, hash) in expected_64.iter().enumerate() {
+            assert_eq!(
+                crate::sip::halfsiphash::halfsiphash24_64_with_key(&input[..len], KEY),
+                u64::from_le_bytes(*hash)
+            );
+        }
+    }
+
+    #[test]
+    fn seeded_builder_test() {
+        use std::hash::BuildHasher;
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let mut hasher =
+            crate::sip::HalfSip24Hash32::with_keys(0x03020100, 0x07060504).build_hasher();
+        for chunk in input.as_bytes().chunks(3) {
+            hasher.write(chunk);
+        }
+        assert_eq!(
+            hasher.finish(),
+            crate::sip::halfsiphash::halfsiphash24_with_key(input, KEY) as u64
+        );
+        let mut hasher = crate::sip::HalfSip24Hash64::with_key(KEY).build_hasher();
+        for chunk in input.as_bytes().chunks(5) {
+            hasher.write(chunk);
+        }
+        assert_eq!(
+            hasher.finish(),
+            crate::sip::halfsiphash::halfsiphash24_64_with_key(input, KEY)
+        );
+    }
+}
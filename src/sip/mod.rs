@@ -0,0 +1,23 @@
+//! SipHash and HalfSipHash keyed hash functions by Jean-Philippe Aumasson and Daniel J. Bernstein.
+//!
+//! Unlike the other algorithms in this crate, these are designed as pseudorandom functions:
+//! as long as the key is secret, an attacker cannot predict hash values or craft collisions.
+mod halfsiphash;
+mod siphash;
+mod siphash_128;
+mod siphash_64;
+
+pub use halfsiphash::{
+    halfsiphash24, halfsiphash24_64, halfsiphash24_64_with_key, halfsiphash24_with_key,
+    HalfSip24Hash32, HalfSip24Hash64, HalfSip24Hasher32, HalfSip24Hasher64, HalfSip24RandomState32,
+    HalfSip24RandomState64, HalfSip24_32, HalfSip24_64,
+};
+pub use siphash_128::{
+    siphash13_128, siphash13_128_with_key, siphash24_128, siphash24_128_with_key, Sip13Hash128,
+    Sip13Hasher128, Sip13RandomState128, Sip13_128, Sip24Hash128, Sip24Hasher128,
+    Sip24RandomState128, Sip24_128,
+};
+pub use siphash_64::{
+    siphash13, siphash13_with_key, siphash24, siphash24_with_key, Sip13Hash64, Sip13Hasher64,
+    Sip13RandomState64, Sip13_64, Sip24Hash64, Sip24Hasher64, Sip24RandomState64, Sip24_64,
+};
@@ -0,0 +1,152 @@
+// The code below is adapted from C code with the following license
+//-----------------------------------------------------------------------------
+// SipHash reference C implementation
+//
+// Copyright (c) 2012-2021 Jean-Philippe Aumasson
+// <jeanphilippe.aumasson@gmail.com>
+// Copyright (c) 2012-2014 Daniel J. Bernstein <djb@cr.yp.to>
+//
+// To the extent possible under law, the author(s) have dedicated all copyright
+// and related and neighboring rights to this software to the public domain
+// worldwide. This software is distributed without any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication along
+// with this software. If not, see
+// <http://creativecommons.org/publicdomain/zero/1.0/>.
+
+/// Streaming state of SipHash-c-d, shared by the 64-bit and 128-bit variants.
+#[derive(Clone, Copy)]
+pub(super) struct State {
+    v: [u64; 4],
+    tail: [u8; 8],
+    tail_len: usize,
+    length: u64,
+    c_rounds: usize,
+    d_rounds: usize,
+}
+
+impl State {
+    /// The key is read as 16 little-endian bytes, as in the reference implementation.
+    /// `wide` selects the initialization of the 128-bit variant.
+    pub(super) fn new(key: u128, c_rounds: usize, d_rounds: usize, wide: bool) -> Self {
+        let k0 = key as u64;
+        let k1 = (key >> 64) as u64;
+        let mut v = [
+            k0 ^ 0x736f6d6570736575,
+            k1 ^ 0x646f72616e646f6d,
+            k0 ^ 0x6c7967656e657261,
+            k1 ^ 0x7465646279746573,
+        ];
+        if wide {
+            v[1] ^= 0xee;
+        }
+        Self {
+            v,
+            tail: [0; 8],
+            tail_len: 0,
+            length: 0,
+            c_rounds,
+            d_rounds,
+        }
+    }
+
+    pub(super) fn write(&mut self, bytes: &[u8]) {
+        let mut data = bytes;
+        self.length += data.len() as u64;
+        // complete a previously started word
+        if self.tail_len != 0 {
+            let fill = (8 - self.tail_len).min(data.len());
+            self.tail[self.tail_len..self.tail_len + fill].copy_from_slice(&data[..fill]);
+            self.tail_len += fill;
+            data = &data[fill..];
+            if self.tail_len < 8 {
+                return;
+            }
+            let m = u64::from_le_bytes(self.tail);
+            self.compress(m);
+            self.tail_len = 0;
+        }
+        // process full words directly from the input
+        let mut chunks = data.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.compress(get_u64(chunk, 0));
+        }
+        // keep the remainder for the next write
+        let rest = chunks.remainder();
+        self.tail[..rest.len()].copy_from_slice(rest);
+        self.tail_len = rest.len();
+    }
+
+    pub(super) fn finish64(&self) -> u64 {
+        let mut state = *self;
+        state.compress(self.last_word());
+        state.v[2] ^= 0xff;
+        state.rounds(self.d_rounds);
+        state.v[0] ^ state.v[1] ^ state.v[2] ^ state.v[3]
+    }
+
+    pub(super) fn finish128(&self) -> u128 {
+        let mut state = *self;
+        state.compress(self.last_word());
+        state.v[2] ^= 0xee;
+        state.rounds(self.d_rounds);
+        let low = state.v[0] ^ state.v[1] ^ state.v[2] ^ state.v[3];
+        state.v[1] ^= 0xdd;
+        state.rounds(self.d_rounds);
+        let high = state.v[0] ^ state.v[1] ^ state.v[2] ^ state.v[3];
+        (high as u128) << 64 | low as u128
+    }
+
+    // the remaining bytes, with the input length in the top byte
+    #[inline(always)]
+    fn last_word(&self) -> u64 {
+        let mut buf = [0; 8];
+        buf[..self.tail_len].copy_from_slice(&self.tail[..self.tail_len]);
+        buf[7] = self.length as u8;
+        u64::from_le_bytes(buf)
+    }
+
+    #[inline(always)]
+    fn compress(&mut self, m: u64) {
+        self.v[3] ^= m;
+        self.rounds(self.c_rounds);
+        self.v[0] ^= m;
+    }
+
+    #[inline(always)]
+    fn rounds(&mut self, n: usize) {
+        let [mut v0, mut v1, mut v2, mut v3] = self.v;
+        for _ in 0..n {
+            v0 = v0.wrapping_add(v1);
+            v1 = v1.rotate_left(13);
+            v1 ^= v0;
+            v0 = v0.rotate_left(32);
+            v2 = v2.wrapping_add(v3);
+            v3 = v3.rotate_left(16);
+            v3 ^= v2;
+            v0 = v0.wrapping_add(v3);
+            v3 = v3.rotate_left(21);
+            v3 ^= v0;
+            v2 = v2.wrapping_add(v1);
+            v1 = v1.rotate_left(17);
+            v1 ^= v2;
+            v2 = v2.rotate_left(32);
+        }
+        self.v = [v0, v1, v2, v3];
+    }
+}
+
+#[inline(always)]
+fn get_u64(data: &[u8], i: usize) -> u64 {
+    let buf = [
+        data[i],
+        data[i + 1],
+        data[i + 2],
+        data[i + 3],
+        data[i + 4],
+        data[i + 5],
+        data[i + 6],
+        data[i + 7],
+    ];
+    u64::from_le_bytes(buf)
+}
@@ -0,0 +1,311 @@
+use std::hash::{BuildHasher, Hasher};
+
+use super::siphash::State;
+use crate::{HashAlgorithm, Hasher128};
+
+/// Hasher for SipHash-1-3 implementation of the 128-bit hashing algorithm.
+#[derive(Clone, Copy)]
+pub struct Sip13Hasher128 {
+    state: State,
+}
+
+impl Sip13Hasher128 {
+    /// Create a hasher which uses the given 128-bit key, like `siphash13_128_with_key()`.
+    pub fn with_key(key: u128) -> Self {
+        Self {
+            state: State::new(key, 1, 3, true),
+        }
+    }
+
+    /// Create a hasher which uses the key made of the two given halves.
+    /// `k0` holds the first 8 bytes of the key and `k1` the last 8 bytes, both little-endian.
+    pub fn with_keys(k0: u64, k1: u64) -> Self {
+        Self::with_key(to_key(k0, k1))
+    }
+}
+
+impl Default for Sip13Hasher128 {
+    fn default() -> Self {
+        Self::with_key(0)
+    }
+}
+
+impl Hasher for Sip13Hasher128 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.state.write(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        self.finish128() as u64
+    }
+}
+
+impl Hasher128 for Sip13Hasher128 {
+    fn finish128(&self) -> u128 {
+        self.state.finish128()
+    }
+}
+
+/// Hash builder for SipHash-1-3 implementation of the 128-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct Sip13Hash128 {
+    key: u128,
+}
+
+impl Sip13Hash128 {
+    /// Create a hash builder whose hashers use the given 128-bit key, like `siphash13_128_with_key()`.
+    pub fn with_key(key: u128) -> Self {
+        Self { key }
+    }
+
+    /// Create a hash builder whose hashers use the key made of the two given halves,
+    /// see `Sip13Hasher128::with_keys()`.
+    pub fn with_keys(k0: u64, k1: u64) -> Self {
+        Self::with_key(to_key(k0, k1))
+    }
+}
+
+impl BuildHasher for Sip13Hash128 {
+    type Hasher = Sip13Hasher128;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_key(self.key)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly keyed hash builder for SipHash-1-3 implementation of the 128-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct Sip13RandomState128(Sip13Hash128::with_key(key: u128));
+}
+
+/// Hasher for SipHash-2-4 implementation of the 128-bit hashing algorithm.
+#[derive(Clone, Copy)]
+pub struct Sip24Hasher128 {
+    state: State,
+}
+
+impl Sip24Hasher128 {
+    /// Create a hasher which uses the given 128-bit key, like `siphash24_128_with_key()`.
+    pub fn with_key(key: u128) -> Self {
+        Self {
+            state: State::new(key, 2, 4, true),
+        }
+    }
+
+    /// Create a hasher which uses the key made of the two given halves.
+    /// `k0` holds the first 8 bytes of the key and `k1` the last 8 bytes, both little-endian.
+    pub fn with_keys(k0: u64, k1: u64) -> Self {
+        Self::with_key(to_key(k0, k1))
+    }
+}
+
+impl Default for Sip24Hasher128 {
+    fn default() -> Self {
+        Self::with_key(0)
+    }
+}
+
+impl Hasher for Sip24Hasher128 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.state.write(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        self.finish128() as u64
+    }
+}
+
+impl Hasher128 for Sip24Hasher128 {
+    fn finish128(&self) -> u128 {
+        self.state.finish128()
+    }
+}
+
+/// Hash builder for SipHash-2-4 implementation of the 128-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct Sip24Hash128 {
+    key: u128,
+}
+
+impl Sip24Hash128 {
+    /// Create a hash builder whose hashers use the given 128-bit key, like `siphash24_128_with_key()`.
+    pub fn with_key(key: u128) -> Self {
+        Self { key }
+    }
+
+    /// Create a hash builder whose hashers use the key made of the two given halves,
+    /// see `Sip24Hasher128::with_keys()`.
+    pub fn with_keys(k0: u64, k1: u64) -> Self {
+        Self::with_key(to_key(k0, k1))
+    }
+}
+
+impl BuildHasher for Sip24Hash128 {
+    type Hasher = Sip24Hasher128;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_key(self.key)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly keyed hash builder for SipHash-2-4 implementation of the 128-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct Sip24RandomState128(Sip24Hash128::with_key(key: u128));
+}
+
+/// SipHash-1-3 implementation of the 128-bit hashing algorithm.
+pub struct Sip13_128;
+
+impl HashAlgorithm for Sip13_128 {
+    const NAME: &'static str = "siphash13_128";
+    type Seed = u128;
+    type Output = u128;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        siphash13_128(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        siphash13_128_with_key(v, seed)
+    }
+}
+
+/// SipHash-1-3 implementation of the 128-bit hashing algorithm.
+/// This version allows you to specify the 128-bit key.
+///
+/// The key is the little-endian reading of the 16 key bytes of the reference implementation.
+pub fn siphash13_128_with_key<T: AsRef<[u8]>>(v: T, key: u128) -> u128 {
+    let mut hasher = Sip13Hasher128::with_key(key);
+    hasher.write(v.as_ref());
+    hasher.finish128()
+}
+
+/// SipHash-1-3 implementation of the 128-bit hashing algorithm.
+/// The key is always 0 in this version.
+pub fn siphash13_128<T: AsRef<[u8]>>(v: T) -> u128 {
+    siphash13_128_with_key(v, 0)
+}
+
+/// SipHash-2-4 implementation of the 128-bit hashing algorithm.
+pub struct Sip24_128;
+
+impl HashAlgorithm for Sip24_128 {
+    const NAME: &'static str = "siphash24_128";
+    type Seed = u128;
+    type Output = u128;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        siphash24_128(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        siphash24_128_with_key(v, seed)
+    }
+}
+
+/// SipHash-2-4 implementation of the 128-bit hashing algorithm.
+/// This version allows you to specify the 128-bit key.
+///
+/// The key is the little-endian reading of the 16 key bytes of the reference implementation.
+pub fn siphash24_128_with_key<T: AsRef<[u8]>>(v: T, key: u128) -> u128 {
+    let mut hasher = Sip24Hasher128::with_key(key);
+    hasher.write(v.as_ref());
+    hasher.finish128()
+}
+
+/// SipHash-2-4 implementation of the 128-bit hashing algorithm.
+/// The key is always 0 in this version.
+pub fn siphash24_128<T: AsRef<[u8]>>(v: T) -> u128 {
+    siphash24_128_with_key(v, 0)
+}
+
+// the key bytes of the reference implementation, read as a little-endian integer
+#[inline(always)]
+fn to_key(k0: u64, k1: u64) -> u128 {
+    (k1 as u128) << 64 | k0 as u128
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    use crate::Hasher128;
+
+    // the reference implementation hashes {0, 1, 2, ...} with the key {0, 1, ..., 15}
+    const KEY: u128 = 0x0f0e0d0c0b0a09080706050403020100;
+
+    #[test]
+    fn compliance_test() {
+        let input: Vec<u8> = (0..64).collect();
+        // test vectors from the reference implementation
+        let expected: [[u8; 16]; 3] = [
+            [
+                0xa3, 0x81, 0x7f, 0x04, 0xba, 0x25, 0xa8, 0xe6, 0x6d, 0xf6, 0x72, 0x14, 0xc7, 0x55,
+                0x02, 0x93,
+            ],
+            [
+                0xda, 0x87, 0xc1, 0xd8, 0x6b, 0x99, 0xaf, 0x44, 0x34, 0x76, 0x59, 0x11, 0x9b, 0x22,
+                0xfc, 0x45,
+            ],
+            [
+                0x81, 0x77, 0x22, 0x8d, 0xa4, 0xa4, 0x5d, 0xc7, 0xfc, 0xa3, 0x8b, 0xde, 0xf6, 0x0a,
+                0xff, 0xe4,
+            ],
+        ];
+        for (len, hash) in expected.iter().enumerate() {
+            assert_eq!(
+                crate::sip::siphash_128::siphash24_128_with_key(&input[..len], KEY),
+                u128::from_le_bytes(*hash)
+            );
+        }
+        // SipHash-1-3 has no official test vectors, these match the `siphasher` crate
+        let expected = [
+            (0, 0x013030dd6adb62fdbea58827b2bc7ee7),
+            (1, 0x63f02f2bcc73055ea8edd36004376ffc),
+            (7, 0x77ab4808c82e2fa6c3e0aaf223b98410),
+            (8, 0x99c7f935ab164f72b4dae3d5e1fe12aa),
+            (15, 0x09017e1eeccd21296c52bdb205557ec1),
+            (63, 0xad6052a70a6b9f076f42fe4ee300584c),
+        ];
+        for &(len, hash) in expected.iter() {
+            assert_eq!(
+                crate::sip::siphash_128::siphash13_128_with_key(&input[..len], KEY),
+                hash
+            );
+        }
+    }
+
+    #[test]
+    fn streaming_test() {
+        let input: Vec<u8> = (0..100).collect();
+        for split in 0..input.len() {
+            let mut hasher = crate::sip::Sip13Hasher128::with_key(KEY);
+            hasher.write(&input[..split]);
+            hasher.write(&input[split..]);
+            assert_eq!(
+                hasher.finish128(),
+                crate::sip::siphash_128::siphash13_128_with_key(&input, KEY)
+            );
+        }
+    }
+
+    #[test]
+    fn seeded_builder_test() {
+        use std::hash::BuildHasher;
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let mut hasher = crate::sip::Sip24Hash128::with_key(KEY).build_hasher();
+        for chunk in input.as_bytes().chunks(7) {
+            hasher.write(chunk);
+        }
+        assert_eq!(
+            hasher.finish128(),
+            crate::sip::siphash_128::siphash24_128_with_key(input, KEY)
+        );
+        assert_eq!(
+            hasher.finish(),
+            crate::sip::siphash_128::siphash24_128_with_key(input, KEY) as u64
+        );
+    }
+}
@@ -0,0 +1,291 @@
+use std::hash::{BuildHasher, Hasher};
+
+use super::siphash::State;
+use crate::HashAlgorithm;
+
+/// Hasher for SipHash-1-3 implementation of the 64-bit hashing algorithm.
+#[derive(Clone, Copy)]
+pub struct Sip13Hasher64 {
+    state: State,
+}
+
+impl Sip13Hasher64 {
+    /// Create a hasher which uses the given 128-bit key, like `siphash13_with_key()`.
+    pub fn with_key(key: u128) -> Self {
+        Self {
+            state: State::new(key, 1, 3, false),
+        }
+    }
+
+    /// Create a hasher which uses the key made of the two given halves.
+    /// `k0` holds the first 8 bytes of the key and `k1` the last 8 bytes, both little-endian.
+    pub fn with_keys(k0: u64, k1: u64) -> Self {
+        Self::with_key(to_key(k0, k1))
+    }
+}
+
+impl Default for Sip13Hasher64 {
+    fn default() -> Self {
+        Self::with_key(0)
+    }
+}
+
+impl Hasher for Sip13Hasher64 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.state.write(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        self.state.finish64()
+    }
+}
+
+/// Hash builder for SipHash-1-3 implementation of the 64-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct Sip13Hash64 {
+    key: u128,
+}
+
+impl Sip13Hash64 {
+    /// Create a hash builder whose hashers use the given 128-bit key, like `siphash13_with_key()`.
+    pub fn with_key(key: u128) -> Self {
+        Self { key }
+    }
+
+    /// Create a hash builder whose hashers use the key made of the two given halves,
+    /// see `Sip13Hasher64::with_keys()`.
+    pub fn with_keys(k0: u64, k1: u64) -> Self {
+        Self::with_key(to_key(k0, k1))
+    }
+}
+
+impl BuildHasher for Sip13Hash64 {
+    type Hasher = Sip13Hasher64;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_key(self.key)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly keyed hash builder for SipHash-1-3 implementation of the 64-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct Sip13RandomState64(Sip13Hash64::with_key(key: u128));
+}
+
+/// Hasher for SipHash-2-4 implementation of the 64-bit hashing algorithm.
+#[derive(Clone, Copy)]
+pub struct Sip24Hasher64 {
+    state: State,
+}
+
+impl Sip24Hasher64 {
+    /// Create a hasher which uses the given 128-bit key, like `siphash24_with_key()`.
+    pub fn with_key(key: u128) -> Self {
+        Self {
+            state: State::new(key, 2, 4, false),
+        }
+    }
+
+    /// Create a hasher which uses the key made of the two given halves.
+    /// `k0` holds the first 8 bytes of the key and `k1` the last 8 bytes, both little-endian.
+    pub fn with_keys(k0: u64, k1: u64) -> Self {
+        Self::with_key(to_key(k0, k1))
+    }
+}
+
+impl Default for Sip24Hasher64 {
+    fn default() -> Self {
+        Self::with_key(0)
+    }
+}
+
+impl Hasher for Sip24Hasher64 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.state.write(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        self.state.finish64()
+    }
+}
+
+/// Hash builder for SipHash-2-4 implementation of the 64-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct Sip24Hash64 {
+    key: u128,
+}
+
+impl Sip24Hash64 {
+    /// Create a hash builder whose hashers use the given 128-bit key, like `siphash24_with_key()`.
+    pub fn with_key(key: u128) -> Self {
+        Self { key }
+    }
+
+    /// Create a hash builder whose hashers use the key made of the two given halves,
+    /// see `Sip24Hasher64::with_keys()`.
+    pub fn with_keys(k0: u64, k1: u64) -> Self {
+        Self::with_key(to_key(k0, k1))
+    }
+}
+
+impl BuildHasher for Sip24Hash64 {
+    type Hasher = Sip24Hasher64;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_key(self.key)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly keyed hash builder for SipHash-2-4 implementation of the 64-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct Sip24RandomState64(Sip24Hash64::with_key(key: u128));
+}
+
+/// SipHash-1-3 implementation of the 64-bit hashing algorithm.
+pub struct Sip13_64;
+
+impl HashAlgorithm for Sip13_64 {
+    const NAME: &'static str = "siphash13";
+    type Seed = u128;
+    type Output = u64;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        siphash13(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        siphash13_with_key(v, seed)
+    }
+}
+
+/// SipHash-1-3 implementation of the 64-bit hashing algorithm.
+/// This version allows you to specify the 128-bit key.
+///
+/// The key is the little-endian reading of the 16 key bytes of the reference implementation.
+pub fn siphash13_with_key<T: AsRef<[u8]>>(v: T, key: u128) -> u64 {
+    let mut hasher = Sip13Hasher64::with_key(key);
+    hasher.write(v.as_ref());
+    hasher.finish()
+}
+
+/// SipHash-1-3 implementation of the 64-bit hashing algorithm.
+/// The key is always 0 in this version.
+pub fn siphash13<T: AsRef<[u8]>>(v: T) -> u64 {
+    siphash13_with_key(v, 0)
+}
+
+/// SipHash-2-4 implementation of the 64-bit hashing algorithm.
+pub struct Sip24_64;
+
+impl HashAlgorithm for Sip24_64 {
+    const NAME: &'static str = "siphash24";
+    type Seed = u128;
+    type Output = u64;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        siphash24(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        siphash24_with_key(v, seed)
+    }
+}
+
+/// SipHash-2-4 implementation of the 64-bit hashing algorithm.
+/// This version allows you to specify the 128-bit key.
+///
+/// The key is the little-endian reading of the 16 key bytes of the reference implementation.
+pub fn siphash24_with_key<T: AsRef<[u8]>>(v: T, key: u128) -> u64 {
+    let mut hasher = Sip24Hasher64::with_key(key);
+    hasher.write(v.as_ref());
+    hasher.finish()
+}
+
+/// SipHash-2-4 implementation of the 64-bit hashing algorithm.
+/// The key is always 0 in this version.
+pub fn siphash24<T: AsRef<[u8]>>(v: T) -> u64 {
+    siphash24_with_key(v, 0)
+}
+
+// the key bytes of the reference implementation, read as a little-endian integer
+#[inline(always)]
+fn to_key(k0: u64, k1: u64) -> u128 {
+    (k1 as u128) << 64 | k0 as u128
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    // the reference implementation hashes {0, 1, 2, ...} with the key {0, 1, ..., 15}
+    const KEY: u128 = 0x0f0e0d0c0b0a09080706050403020100;
+
+    #[test]
+    fn compliance_test() {
+        let input: Vec<u8> = (0..64).collect();
+        // test vectors from the reference implementation
+        let expected = [
+            (0, 0x726fdb47dd0e0e31),
+            (1, 0x74f839c593dc67fd),
+            (2, 0x0d6c8009d9a94f5a),
+        ];
+        for &(len, hash) in expected.iter() {
+            assert_eq!(
+                crate::sip::siphash_64::siphash24_with_key(&input[..len], KEY),
+                hash
+            );
+        }
+        // SipHash-1-3 has no official test vectors, these match the `siphasher` crate
+        let expected = [
+            (0, 0xabac0158050fc4dc),
+            (1, 0xc9f49bf37d57ca93),
+            (7, 0xd3927d989bb11140),
+            (8, 0x369095118d299a8e),
+            (15, 0xd320d86d2a519956),
+            (63, 0x9d199062b7bbb3a8),
+        ];
+        for &(len, hash) in expected.iter() {
+            assert_eq!(
+                crate::sip::siphash_64::siphash13_with_key(&input[..len], KEY),
+                hash
+            );
+        }
+    }
+
+    #[test]
+    fn streaming_test() {
+        let input: Vec<u8> = (0..100).collect();
+        for split in 0..input.len() {
+            let mut hasher = crate::sip::Sip24Hasher64::with_key(KEY);
+            hasher.write(&input[..split]);
+            hasher.write(&input[split..]);
+            assert_eq!(
+                hasher.finish(),
+                crate::sip::siphash_64::siphash24_with_key(&input, KEY)
+            );
+        }
+    }
+
+    #[test]
+    fn seeded_builder_test() {
+        use std::hash::BuildHasher;
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let mut hasher = crate::sip::Sip13Hash64::with_keys(0x0706050403020100, 0x0f0e0d0c0b0a0908)
+            .build_hasher();
+        for chunk in input.as_bytes().chunks(7) {
+            hasher.write(chunk);
+        }
+        assert_eq!(
+            hasher.finish(),
+            crate::sip::siphash_64::siphash13_with_key(input, KEY)
+        );
+        let mut hasher = crate::sip::Sip24Hash64::with_key(KEY).build_hasher();
+        hasher.write(input.as_bytes());
+        assert_eq!(
+            hasher.finish(),
+            crate::sip::siphash_64::siphash24_with_key(input, KEY)
+        );
+    }
+}
@@ -0,0 +1,15 @@
+//! SpookyHash V2 algorithm by Bob Jenkins.
+mod spooky_128;
+mod spooky_32;
+mod spooky_64;
+
+pub use spooky_128::{
+    hash128, hash128_with_seed, hash128_with_seeds, Spooky128, SpookyHash128, SpookyHasher128,
+    SpookyRandomState128,
+};
+pub use spooky_32::{
+    hash32, hash32_with_seed, Spooky32, SpookyHash32, SpookyHasher32, SpookyRandomState32,
+};
+pub use spooky_64::{
+    hash64, hash64_with_seed, Spooky64, SpookyHash64, SpookyHasher64, SpookyRandomState64,
+};
@@ -0,0 +1,483 @@
+use std::hash::{BuildHasher, Hasher};
+
+use crate::{HashAlgorithm, Hasher128};
+
+/// Hasher for SpookyHash V2 implementation of the 128-bit hashing algorithm.
+///
+/// This is the incremental interface of SpookyHash: `with_seeds()` corresponds to `Init`,
+/// `write()` to `Update` and `finish128()` to `Final`.
+/// The input is processed as it is written, keeping at most 192 bytes of it in memory.
+#[derive(Clone)]
+pub struct SpookyHasher128 {
+    state: SpookyState,
+}
+
+impl SpookyHasher128 {
+    /// Create a hasher which uses the given seed, like `hash128_with_seed()`.
+    pub fn with_seed(seed: u128) -> Self {
+        Self::with_seeds(seed as u64, (seed >> 64) as u64)
+    }
+
+    /// Create a hasher which uses the given two seeds, like `hash128_with_seeds()`.
+    pub fn with_seeds(seed1: u64, seed2: u64) -> Self {
+        Self {
+            state: SpookyState::new(seed1, seed2),
+        }
+    }
+}
+
+impl Default for SpookyHasher128 {
+    fn default() -> Self {
+        Self::with_seed(0)
+    }
+}
+
+impl Hasher for SpookyHasher128 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.state.update(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        self.finish128() as u64
+    }
+}
+
+impl Hasher128 for SpookyHasher128 {
+    fn finish128(&self) -> u128 {
+        let (hash1, hash2) = self.state.finish();
+        to_u128(hash1, hash2)
+    }
+}
+
+/// Hash builder for SpookyHash V2 implementation of the 128-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct SpookyHash128 {
+    seed: u128,
+}
+
+impl SpookyHash128 {
+    /// Create a hash builder whose hashers use the given seed, like `hash128_with_seed()`.
+    pub fn with_seed(seed: u128) -> Self {
+        Self { seed }
+    }
+
+    /// Create a hash builder whose hashers use the given two seeds, like `hash128_with_seeds()`.
+    pub fn with_seeds(seed1: u64, seed2: u64) -> Self {
+        Self::with_seed(to_u128(seed1, seed2))
+    }
+}
+
+impl BuildHasher for SpookyHash128 {
+    type Hasher = SpookyHasher128;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_seed(self.seed)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for SpookyHash V2 implementation of the 128-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct SpookyRandomState128(SpookyHash128::with_seed(seed: u128));
+}
+
+// The code below is adapted from C++ code with the following disclaimer
+//-----------------------------------------------------------------------------
+// SpookyHash: a 128-bit noncryptographic hash function
+// By Bob Jenkins, public domain
+
+// number of u64's in internal state
+const NUM_VARS: usize = 12;
+// size of the internal state
+const BLOCK_SIZE: usize = NUM_VARS * 8;
+// size of buffer of unhashed data, in bytes
+const BUF_SIZE: usize = 2 * BLOCK_SIZE;
+// a constant which is not zero, is odd, and is a not-very-regular mix of 1's and 0's
+const SC_CONST: u64 = 0xdeadbeefdeadbeef;
+
+/// SpookyHash V2 implementation of the 128-bit hashing algorithm.
+pub struct Spooky128;
+
+impl HashAlgorithm for Spooky128 {
+    const NAME: &'static str = "spooky128";
+    type Seed = u128;
+    type Output = u128;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hash128(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hash128_with_seed(v, seed)
+    }
+}
+
+/// SpookyHash V2 implementation of the 128-bit hashing algorithm.
+/// This version allows you to specify the two 64-bit seeds separately.
+///
+/// `seed1` and `seed2` are the values of `hash1` and `hash2` passed to the reference `Hash128`.
+/// On return those hold the two halves of the hash, which are the lower and upper 64 bits of the
+/// result respectively.
+pub fn hash128_with_seeds<T: AsRef<[u8]>>(v: T, seed1: u64, seed2: u64) -> u128 {
+    let (hash1, hash2) = hash128_parts(v.as_ref(), seed1, seed2);
+    to_u128(hash1, hash2)
+}
+
+/// SpookyHash V2 implementation of the 128-bit hashing algorithm.
+/// This version allows you to specify a seed.
+///
+/// The lower 64 bits of the seed are used as `seed1` and the upper 64 bits as `seed2`,
+/// see `hash128_with_seeds()`.
+pub fn hash128_with_seed<T: AsRef<[u8]>>(v: T, seed: u128) -> u128 {
+    hash128_with_seeds(v, seed as u64, (seed >> 64) as u64)
+}
+
+/// SpookyHash V2 implementation of the 128-bit hashing algorithm.
+/// The seed is always 0 in this version.
+pub fn hash128<T: AsRef<[u8]>>(v: T) -> u128 {
+    hash128_with_seed(v, 0)
+}
+
+pub(super) fn hash128_parts(data: &[u8], seed1: u64, seed2: u64) -> (u64, u64) {
+    if data.len() < BUF_SIZE {
+        return short(data, seed1, seed2);
+    }
+
+    let mut h = init(seed1, seed2);
+    let n_blocks = data.len() / BLOCK_SIZE;
+    for i in 0..n_blocks {
+        mix(&mut h, data, i * BLOCK_SIZE);
+    }
+    end_tail(&mut h, &data[n_blocks * BLOCK_SIZE..]);
+    (h[0], h[1])
+}
+
+/// State of the incremental SpookyHash V2 computation, shared by the hashers of all widths.
+#[derive(Clone)]
+pub(super) struct SpookyState {
+    // unhashed data, for partial messages
+    data: [u8; BUF_SIZE],
+    // internal state of the hash; holds the two seeds while the message is still short
+    h: [u64; NUM_VARS],
+    // total length of the input so far
+    length: u64,
+    // length of unhashed data stashed in `data`
+    remainder: usize,
+}
+
+impl SpookyState {
+    pub(super) fn new(seed1: u64, seed2: u64) -> Self {
+        let mut h = [0; NUM_VARS];
+        h[0] = seed1;
+        h[1] = seed2;
+        Self {
+            data: [0; BUF_SIZE],
+            h,
+            length: 0,
+            remainder: 0,
+        }
+    }
+
+    pub(super) fn update(&mut self, mut message: &[u8]) {
+        let new_length = self.remainder + message.len();
+
+        // is this message fragment too short? If it is, stuff it away.
+        if new_length < BUF_SIZE {
+            self.data[self.remainder..new_length].copy_from_slice(message);
+            self.length += message.len() as u64;
+            self.remainder = new_length;
+            return;
+        }
+
+        // init the variables
+        let mut h = if self.length < BUF_SIZE as u64 {
+            init(self.h[0], self.h[1])
+        } else {
+            self.h
+        };
+        self.length += message.len() as u64;
+
+        // if we've got anything stuffed away, use it now
+        if self.remainder != 0 {
+            let prefix = BUF_SIZE - self.remainder;
+            self.data[self.remainder..].copy_from_slice(&message[..prefix]);
+            mix(&mut h, &self.data, 0);
+            mix(&mut h, &self.data, BLOCK_SIZE);
+            message = &message[prefix..];
+        }
+
+        // handle all whole blocks of BLOCK_SIZE bytes
+        let n_blocks = message.len() / BLOCK_SIZE;
+        for i in 0..n_blocks {
+            mix(&mut h, message, i * BLOCK_SIZE);
+        }
+
+        // stuff away the last few bytes
+        let rest = &message[n_blocks * BLOCK_SIZE..];
+        self.data[..rest.len()].copy_from_slice(rest);
+        self.remainder = rest.len();
+        self.h = h;
+    }
+
+    pub(super) fn finish(&self) -> (u64, u64) {
+        // init the variables
+        if self.length < BUF_SIZE as u64 {
+            return short(&self.data[..self.remainder], self.h[0], self.h[1]);
+        }
+
+        let mut h = self.h;
+        let mut rest = &self.data[..self.remainder];
+        if rest.len() >= BLOCK_SIZE {
+            // self.data can contain two blocks; handle any whole first block
+            mix(&mut h, rest, 0);
+            rest = &rest[BLOCK_SIZE..];
+        }
+        end_tail(&mut h, rest);
+        (h[0], h[1])
+    }
+}
+
+#[inline(always)]
+fn to_u128(hash1: u64, hash2: u64) -> u128 {
+    (hash2 as u128) << 64 | hash1 as u128
+}
+
+#[inline(always)]
+fn init(seed1: u64, seed2: u64) -> [u64; NUM_VARS] {
+    [
+        seed1, seed2, SC_CONST, seed1, seed2, SC_CONST, seed1, seed2, SC_CONST, seed1, seed2,
+        SC_CONST,
+    ]
+}
+
+// This is used if the input is 96 bytes long or longer.
+//
+// The internal state is fully overwritten every 96 bytes.
+// Every input bit appears to cause at least 128 bits of entropy
+// before 96 other bytes are combined, when run forward or backward.
+#[inline(always)]
+fn mix(h: &mut [u64; NUM_VARS], data: &[u8], i: usize) {
+    const ROTATIONS: [u32; NUM_VARS] = [11, 32, 43, 31, 17, 28, 39, 57, 55, 54, 22, 46];
+    for (j, &r) in ROTATIONS.iter().enumerate() {
+        h[j] = h[j].wrapping_add(fetch64(data, i + j * 8));
+        h[(j + 2) % NUM_VARS] ^= h[(j + 10) % NUM_VARS];
+        h[(j + 11) % NUM_VARS] ^= h[j];
+        h[j] = h[j].rotate_left(r);
+        h[(j + 11) % NUM_VARS] = h[(j + 11) % NUM_VARS].wrapping_add(h[(j + 1) % NUM_VARS]);
+    }
+}
+
+// Mix all 12 inputs together so that h0, h1 are a hash of them all.
+//
+// For two inputs differing in just the input bits where "differ" means xor or subtraction
+// (and a bit of either of those, with the rest of the bits being the same),
+// for all 768 bits in the state and for both directions of the computation,
+// at least 128 bits of the result are affected.
+#[inline(always)]
+fn end_partial(h: &mut [u64; NUM_VARS]) {
+    const ROTATIONS: [u32; NUM_VARS] = [44, 15, 34, 21, 38, 33, 10, 13, 38, 53, 42, 54];
+    for (j, &r) in ROTATIONS.iter().enumerate() {
+        h[(j + 11) % NUM_VARS] = h[(j + 11) % NUM_VARS].wrapping_add(h[(j + 1) % NUM_VARS]);
+        h[(j + 2) % NUM_VARS] ^= h[(j + 11) % NUM_VARS];
+        h[(j + 1) % NUM_VARS] = h[(j + 1) % NUM_VARS].rotate_left(r);
+    }
+}
+
+#[inline(always)]
+fn end(h: &mut [u64; NUM_VARS], data: &[u8; BLOCK_SIZE]) {
+    for (j, v) in h.iter_mut().enumerate() {
+        *v = v.wrapping_add(fetch64(data, j * 8));
+    }
+    end_partial(h);
+    end_partial(h);
+    end_partial(h);
+}
+
+// pad the last partial block with zeros, ending in its length, and finish the hash
+#[inline(always)]
+fn end_tail(h: &mut [u64; NUM_VARS], rest: &[u8]) {
+    let mut block = [0; BLOCK_SIZE];
+    block[..rest.len()].copy_from_slice(rest);
+    block[BLOCK_SIZE - 1] = rest.len() as u8;
+    end(h, &block);
+}
+
+// The goal is for each bit of the input to expand into 128 bits of apparent entropy
+// before it is fully overwritten.
+#[inline(always)]
+fn short_mix(h: &mut [u64; 4]) {
+    const ROTATIONS: [u32; 12] = [50, 52, 30, 41, 54, 48, 38, 37, 62, 34, 5, 36];
+    for (j, &r) in ROTATIONS.iter().enumerate() {
+        let (a, b, c) = ((j + 2) % 4, (j + 3) % 4, j % 4);
+        h[a] = h[a].rotate_left(r);
+        h[a] = h[a].wrapping_add(h[b]);
+        h[c] ^= h[a];
+    }
+}
+
+// Mix all 4 inputs together so that h0, h1 are a hash of them all.
+#[inline(always)]
+fn short_end(h: &mut [u64; 4]) {
+    const ROTATIONS: [u32; 11] = [15, 52, 26, 51, 28, 9, 47, 54, 32, 25, 63];
+    for (j, &r) in ROTATIONS.iter().enumerate() {
+        let (a, b) = ((j + 3) % 4, (j + 2) % 4);
+        h[a] ^= h[b];
+        h[b] = h[b].rotate_left(r);
+        h[a] = h[a].wrapping_add(h[b]);
+    }
+}
+
+// short is used for messages under 192 bytes in length.
+// short has a low startup cost, the normal mode is good for long keys,
+// the cost crossover is at about 192 bytes.
+fn short(data: &[u8], seed1: u64, seed2: u64) -> (u64, u64) {
+    let mut h = [seed1, seed2, SC_CONST, SC_CONST];
+
+    let mut rest = data;
+    if data.len() > 15 {
+        // handle all complete sets of 32 bytes
+        let mut chunks = data.chunks_exact(32);
+        for chunk in &mut chunks {
+            h[2] = h[2].wrapping_add(fetch64(chunk, 0));
+            h[3] = h[3].wrapping_add(fetch64(chunk, 8));
+            short_mix(&mut h);
+            h[0] = h[0].wrapping_add(fetch64(chunk, 16));
+            h[1] = h[1].wrapping_add(fetch64(chunk, 24));
+        }
+        rest = chunks.remainder();
+
+        // handle the case of 16+ remaining bytes.
+        if rest.len() >= 16 {
+            h[2] = h[2].wrapping_add(fetch64(rest, 0));
+            h[3] = h[3].wrapping_add(fetch64(rest, 8));
+            short_mix(&mut h);
+            rest = &rest[16..];
+        }
+    }
+
+    // handle the last 0..15 bytes, and its length
+    h[3] = h[3].wrapping_add((data.len() as u64) << 56);
+    if rest.is_empty() {
+        h[2] = h[2].wrapping_add(SC_CONST);
+        h[3] = h[3].wrapping_add(SC_CONST);
+    } else {
+        let (low, high) = rest.split_at(rest.len().min(8));
+        h[2] = h[2].wrapping_add(fetch_partial64(low));
+        h[3] = h[3].wrapping_add(fetch_partial64(high));
+    }
+    short_end(&mut h);
+    (h[0], h[1])
+}
+
+#[inline(always)]
+fn fetch64(data: &[u8], i: usize) -> u64 {
+    let buf = [
+        data[i],
+        data[i + 1],
+        data[i + 2],
+        data[i + 3],
+        data[i + 4],
+        data[i + 5],
+        data[i + 6],
+        data[i + 7],
+    ];
+    u64::from_le_bytes(buf)
+}
+
+// read up to 8 bytes as a little-endian integer, zero-padded
+#[inline(always)]
+fn fetch_partial64(data: &[u8]) -> u64 {
+    let mut buf = [0; 8];
+    buf[..data.len()].copy_from_slice(data);
+    u64::from_le_bytes(buf)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Hasher128;
+    use std::hash::Hasher;
+
+    #[test]
+    fn compliance_test() {
+        // from the SpookyHash V2 test suite: Hash32 of buf[i] = i + 128 with seed 0
+        let expected = [
+            0x6bf50919, 0x70de1d26, 0xa2b37298, 0x35bc5fbf, 0x8223b279, 0x5bcb315e, 0x53fe88a1,
+            0xf9f1a233, 0xee193982, 0x54f86f29, 0xc8772d36, 0x9ed60886, 0x5f23d1da, 0x1ed9f474,
+            0xf2ef0c89, 0x83ec01f9, 0xf274736c, 0x7e9ac0df, 0xc7aed250, 0xb1015811, 0xe23470f5,
+            0x48ac20c4, 0xe2ab3cd5, 0x608f8363, 0xd0639e68, 0xc4e8e7ab, 0x863c7c5b, 0x4ea63579,
+            0x99ae8622, 0x170c658b, 0x149ba493, 0x027bca7c,
+        ];
+        let input: Vec<u8> = (0..expected.len()).map(|i| (i + 128) as u8).collect();
+        for (len, &hash) in expected.iter().enumerate() {
+            assert_eq!(crate::spooky::hash32(&input[..len]), hash, "{}", len);
+        }
+    }
+
+    #[test]
+    fn long_message_test() {
+        // computed with the SpookyHash V2 reference implementation
+        let input: Vec<u8> = (0..1024u32).map(|i| (i * 7 + 3) as u8).collect();
+        let expected: [(usize, u64, u64); 11] = [
+            (0, 0x57632db7d0e8da32, 0xa9236a7cc29d5c57),
+            (1, 0x4f7b9f3c3b3aa924, 0xc604ac26d6913aed),
+            (31, 0x82d40da89ea165b9, 0xe12b012ca169000e),
+            (32, 0x7a085decfbc5c516, 0x890297bdc67ffe29),
+            (191, 0x483ed39bc64d0a68, 0xe6d835cb27960811),
+            (192, 0xf88588c3bf15dc1c, 0x6dd2e8a52c6c9541),
+            (193, 0x941ec6521efeeb09, 0x64d20a4786412747),
+            (287, 0x34985a3a45a739f7, 0xea115fffeb79c7ff),
+            (288, 0x22b0c6f0097a5c16, 0xbc6b4f695b22634e),
+            (300, 0x18ea2b7509b5b9e5, 0x78ccf76a9a7b4b16),
+            (1023, 0x239c83efb7f5da2f, 0x558dfca08705ccf0),
+        ];
+        for &(len, hash1, hash2) in expected.iter() {
+            assert_eq!(
+                crate::spooky::spooky_128::hash128_with_seeds(&input[..len], 999, 12345),
+                (hash2 as u128) << 64 | hash1 as u128,
+                "{}",
+                len
+            );
+        }
+    }
+
+    #[test]
+    fn verification_test() {
+        // SMHasher verification value of the 32-bit variant
+        let key: Vec<u8> = (0..=255).collect();
+        let mut hashes = Vec::new();
+        for i in 0..256 {
+            let hash = crate::spooky::hash32_with_seed(&key[..i], 256 - i as u32);
+            hashes.extend(&hash.to_le_bytes());
+        }
+        assert_eq!(crate::spooky::hash32(&hashes), 0xa48be265);
+    }
+
+    #[test]
+    fn streaming_test() {
+        let input: Vec<u8> = (0..1024u32).map(|i| (i * 7 + 3) as u8).collect();
+        for len in 0..input.len() {
+            let expected = crate::spooky::spooky_128::hash128_with_seeds(&input[..len], 1, 2);
+            for split in [1, 15, 96, 100, 191, 192, 193] {
+                let mut hasher = crate::spooky::SpookyHasher128::with_seeds(1, 2);
+                for chunk in input[..len].chunks(split) {
+                    hasher.write(chunk);
+                }
+                assert_eq!(hasher.finish(), expected as u64);
+                assert_eq!(hasher.finish128(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn seeded_builder_test() {
+        use std::hash::BuildHasher;
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let seed = 12345 << 64 | 999;
+        let mut hasher = crate::spooky::SpookyHash128::with_seed(seed).build_hasher();
+        hasher.write(input.as_bytes());
+        assert_eq!(
+            hasher.finish128(),
+            crate::spooky::spooky_128::hash128_with_seeds(input, 999, 12345)
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use std::hash::{BuildHasher, Hasher};
+
+use super::spooky_128::{hash128_parts, SpookyState};
+use crate::HashAlgorithm;
+
+/// Hasher for SpookyHash V2 implementation of the 32-bit hashing algorithm.
+///
+/// The input is processed as it is written, like `SpookyHasher128`.
+#[derive(Clone)]
+pub struct SpookyHasher32 {
+    state: SpookyState,
+}
+
+impl SpookyHasher32 {
+    /// Create a hasher which uses the given seed, like `hash32_with_seed()`.
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            state: SpookyState::new(seed as u64, seed as u64),
+        }
+    }
+}
+
+impl Default for SpookyHasher32 {
+    fn default() -> Self {
+        Self::with_seed(0)
+    }
+}
+
+impl Hasher for SpookyHasher32 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.state.update(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        self.state.finish().0 as u32 as u64
+    }
+}
+
+/// Hash builder for SpookyHash V2 implementation of the 32-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct SpookyHash32 {
+    seed: u32,
+}
+
+impl SpookyHash32 {
+    /// Create a hash builder whose hashers use the given seed, like `hash32_with_seed()`.
+    pub fn with_seed(seed: u32) -> Self {
+        Self { seed }
+    }
+}
+
+impl BuildHasher for SpookyHash32 {
+    type Hasher = SpookyHasher32;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_seed(self.seed)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for SpookyHash V2 implementation of the 32-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct SpookyRandomState32(SpookyHash32::with_seed(seed: u32));
+}
+
+/// SpookyHash V2 implementation of the 32-bit hashing algorithm.
+pub struct Spooky32;
+
+impl HashAlgorithm for Spooky32 {
+    const NAME: &'static str = "spooky32";
+    type Seed = u32;
+    type Output = u32;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hash32(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hash32_with_seed(v, seed)
+    }
+}
+
+/// SpookyHash V2 implementation of the 32-bit hashing algorithm.
+/// This version allows you to specify a seed.
+///
+/// This is the lowest 32 bits of the 128-bit hash using `seed` as both of its seeds.
+pub fn hash32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
+    hash128_parts(v.as_ref(), seed as u64, seed as u64).0 as u32
+}
+
+/// SpookyHash V2 implementation of the 32-bit hashing algorithm.
+/// The seed is always 0 in this version.
+pub fn hash32<T: AsRef<[u8]>>(v: T) -> u32 {
+    hash32_with_seed(v, 0)
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    #[test]
+    fn compliance_test() {
+        let input: Vec<u8> = (0..512u32).map(|i| (i * 7 + 3) as u8).collect();
+        for len in [0, 15, 16, 191, 192, 193, 288, 512] {
+            assert_eq!(
+                crate::spooky::spooky_32::hash32_with_seed(&input[..len], 4919),
+                crate::spooky::hash128_with_seeds(&input[..len], 4919, 4919) as u32
+            );
+        }
+    }
+
+    #[test]
+    fn streaming_test() {
+        let input: Vec<u8> = (0..=255).collect();
+        for len in 0..input.len() {
+            let expected = crate::spooky::spooky_32::hash32_with_seed(&input[..len], 4919);
+            for split in [1, 3, 32, 97] {
+                let mut hasher = crate::spooky::SpookyHasher32::with_seed(4919);
+                for chunk in input[..len].chunks(split) {
+                    hasher.write(chunk);
+                }
+                assert_eq!(hasher.finish(), expected as u64);
+            }
+        }
+    }
+}
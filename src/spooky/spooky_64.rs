@@ -0,0 +1,126 @@
+use std::hash::{BuildHasher, Hasher};
+
+use super::spooky_128::{hash128_parts, SpookyState};
+use crate::HashAlgorithm;
+
+/// Hasher for SpookyHash V2 implementation of the 64-bit hashing algorithm.
+///
+/// The input is processed as it is written, like `SpookyHasher128`.
+#[derive(Clone)]
+pub struct SpookyHasher64 {
+    state: SpookyState,
+}
+
+impl SpookyHasher64 {
+    /// Create a hasher which uses the given seed, like `hash64_with_seed()`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            state: SpookyState::new(seed, seed),
+        }
+    }
+}
+
+impl Default for SpookyHasher64 {
+    fn default() -> Self {
+        Self::with_seed(0)
+    }
+}
+
+impl Hasher for SpookyHasher64 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.state.update(bytes)
+    }
+
+    fn finish(&self) -> u64 {
+        self.state.finish().0
+    }
+}
+
+/// Hash builder for SpookyHash V2 implementation of the 64-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct SpookyHash64 {
+    seed: u64,
+}
+
+impl SpookyHash64 {
+    /// Create a hash builder whose hashers use the given seed, like `hash64_with_seed()`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl BuildHasher for SpookyHash64 {
+    type Hasher = SpookyHasher64;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_seed(self.seed)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for SpookyHash V2 implementation of the 64-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct SpookyRandomState64(SpookyHash64::with_seed(seed: u64));
+}
+
+/// SpookyHash V2 implementation of the 64-bit hashing algorithm.
+pub struct Spooky64;
+
+impl HashAlgorithm for Spooky64 {
+    const NAME: &'static str = "spooky64";
+    type Seed = u64;
+    type Output = u64;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hash64(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hash64_with_seed(v, seed)
+    }
+}
+
+/// SpookyHash V2 implementation of the 64-bit hashing algorithm.
+/// This version allows you to specify a seed.
+///
+/// This is the lower half of the 128-bit hash using `seed` as both of its seeds.
+pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
+    hash128_parts(v.as_ref(), seed, seed).0
+}
+
+/// SpookyHash V2 implementation of the 64-bit hashing algorithm.
+/// The seed is always 0 in this version.
+pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
+    hash64_with_seed(v, 0)
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    #[test]
+    fn compliance_test() {
+        let input: Vec<u8> = (0..512u32).map(|i| (i * 7 + 3) as u8).collect();
+        for len in [0, 15, 16, 191, 192, 193, 288, 512] {
+            assert_eq!(
+                crate::spooky::spooky_64::hash64_with_seed(&input[..len], 4919),
+                crate::spooky::hash128_with_seeds(&input[..len], 4919, 4919) as u64
+            );
+        }
+    }
+
+    #[test]
+    fn streaming_test() {
+        let input: Vec<u8> = (0..=255).collect();
+        for len in 0..input.len() {
+            let expected = crate::spooky::spooky_64::hash64_with_seed(&input[..len], 4919);
+            for split in [1, 3, 32, 97] {
+                let mut hasher = crate::spooky::SpookyHasher64::with_seed(4919);
+                for chunk in input[..len].chunks(split) {
+                    hasher.write(chunk);
+                }
+                assert_eq!(hasher.finish(), expected);
+            }
+        }
+    }
+}
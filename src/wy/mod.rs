@@ -0,0 +1,13 @@
+//! wyhash algorithm (final4) by Wang Yi, and the original rapidhash by Nicolas De Carli
+//! which is derived from it.
+//!
+//! Their hashers process every write as it comes instead of buffering the input,
+//! which makes them a good fit for hash tables with short keys.
+mod rapidhash;
+mod wyhash;
+
+pub use rapidhash::{
+    rapidhash, rapidhash_with_seed, RapidHash64, RapidHasher64, RapidRandomState64, Rapidhash,
+    RAPID_SEED,
+};
+pub use wyhash::{wyhash, wyhash_with_seed, WyHash64, WyHasher64, WyRandomState64, Wyhash};
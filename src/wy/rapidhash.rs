@@ -0,0 +1,229 @@
+use std::hash::{BuildHasher, Hasher};
+
+use super::wyhash::{mix, mum, read32, read64};
+use crate::HashAlgorithm;
+
+/// Hasher for rapidhash implementation of the 64-bit hashing algorithm.
+///
+/// Nothing is buffered: every `write()` is hashed immediately, using the hash of the previous
+/// writes as its seed.
+/// This keeps hashing of short keys cheap, but it means the result only matches `rapidhash_with_seed()`
+/// when the input is written all at once, and it depends on how the input is split across writes.
+/// That is fine for hash tables, since a type's `Hash` implementation always issues the same writes.
+#[derive(Clone, Copy)]
+pub struct RapidHasher64 {
+    seed: u64,
+    hash: Option<u64>,
+}
+
+impl RapidHasher64 {
+    /// Create a hasher which uses the given seed.
+    pub fn with_seed(seed: u64) -> Self {
+        Self { seed, hash: None }
+    }
+}
+
+impl Default for RapidHasher64 {
+    fn default() -> Self {
+        Self::with_seed(RAPID_SEED)
+    }
+}
+
+impl Hasher for RapidHasher64 {
+    fn write(&mut self, bytes: &[u8]) {
+        let seed = self.hash.unwrap_or(self.seed);
+        self.hash = Some(rapidhash_with_seed(bytes, seed));
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+            .unwrap_or_else(|| rapidhash_with_seed([], self.seed))
+    }
+}
+
+/// Hash builder for rapidhash implementation of the 64-bit hashing algorithm.
+#[derive(Clone, Copy)]
+pub struct RapidHash64 {
+    seed: u64,
+}
+
+impl RapidHash64 {
+    /// Create a hash builder whose hashers use the given seed.
+    pub fn with_seed(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl Default for RapidHash64 {
+    fn default() -> Self {
+        Self::with_seed(RAPID_SEED)
+    }
+}
+
+impl BuildHasher for RapidHash64 {
+    type Hasher = RapidHasher64;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_seed(self.seed)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for rapidhash implementation of the 64-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct RapidRandomState64(RapidHash64::with_seed(seed: u64));
+}
+
+// The code below is adapted from C code with the following license
+//-----------------------------------------------------------------------------
+// rapidhash - Very fast, high quality, platform-independent hashing algorithm.
+// Copyright (C) 2024 Nicolas De Carli
+//
+// Based on 'wyhash', by Wang Yi <godspeed_china@yeah.net>
+//
+// BSD 2-Clause License (https://www.opensource.org/licenses/bsd-license.php)
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are
+// met:
+//
+//    * Redistributions of source code must retain the above copyright
+//      notice, this list of conditions and the following disclaimer.
+//    * Redistributions in binary form must reproduce the above
+//      copyright notice, this list of conditions and the following disclaimer
+//      in the documentation and/or other materials provided with the
+//      distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+// OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+// LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+// DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+// THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+// (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+// default secret parameters
+const SECRET: [u64; 3] = [0x2d358dccaa6c78a5, 0x8bb84b93962eacc9, 0x4b33a62ed433d4a3];
+
+/// Seed used by `rapidhash()`, as in the reference implementation.
+pub const RAPID_SEED: u64 = 0xbdd89aa982704029;
+
+/// rapidhash implementation of the 64-bit hashing algorithm.
+pub struct Rapidhash;
+
+impl HashAlgorithm for Rapidhash {
+    const NAME: &'static str = "rapidhash";
+    type Seed = u64;
+    type Output = u64;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        rapidhash(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        rapidhash_with_seed(v, seed)
+    }
+}
+
+/// rapidhash implementation of the 64-bit hashing algorithm.
+/// This version allows you to specify a seed.
+pub fn rapidhash_with_seed<T: AsRef<[u8]>>(v: T, mut seed: u64) -> u64 {
+    let data = v.as_ref();
+    let len = data.len();
+    seed ^= mix(seed ^ SECRET[0], SECRET[1]) ^ len as u64;
+
+    let (a, b) = if len <= 16 {
+        if len >= 4 {
+            let last = len - 4;
+            // 0 for lengths below 8, 4 otherwise
+            let delta = (len & 24) >> (len >> 3);
+            (
+                read32(data, 0) << 32 | read32(data, last),
+                read32(data, delta) << 32 | read32(data, last - delta),
+            )
+        } else if len > 0 {
+            (read_small(data), 0)
+        } else {
+            (0, 0)
+        }
+    } else {
+        let mut p = data;
+        if p.len() > 48 {
+            let mut see1 = seed;
+            let mut see2 = seed;
+            while p.len() >= 48 {
+                seed = mix(read64(p, 0) ^ SECRET[0], read64(p, 8) ^ seed);
+                see1 = mix(read64(p, 16) ^ SECRET[1], read64(p, 24) ^ see1);
+                see2 = mix(read64(p, 32) ^ SECRET[2], read64(p, 40) ^ see2);
+                p = &p[48..];
+            }
+            seed ^= see1 ^ see2;
+        }
+        if p.len() > 16 {
+            seed = mix(read64(p, 0) ^ SECRET[2], read64(p, 8) ^ seed ^ SECRET[1]);
+            if p.len() > 32 {
+                seed = mix(read64(p, 16) ^ SECRET[2], read64(p, 24) ^ seed);
+            }
+        }
+        // the last 16 bytes of the input, which may overlap the ones already mixed in
+        (read64(data, len - 16), read64(data, len - 8))
+    };
+
+    let (a, b) = mum(a ^ SECRET[1], b ^ seed);
+    mix(a ^ SECRET[0] ^ len as u64, b ^ SECRET[1])
+}
+
+/// rapidhash implementation of the 64-bit hashing algorithm.
+/// This version uses `RAPID_SEED` as the seed, like the reference implementation.
+pub fn rapidhash<T: AsRef<[u8]>>(v: T) -> u64 {
+    rapidhash_with_seed(v, RAPID_SEED)
+}
+
+// 1 to 3 bytes: the first, middle and last byte
+#[inline(always)]
+fn read_small(data: &[u8]) -> u64 {
+    let k = data.len();
+    (data[0] as u64) << 56 | (data[k >> 1] as u64) << 32 | data[k - 1] as u64
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    #[test]
+    fn compliance_test() {
+        assert_eq!(crate::wy::rapidhash::rapidhash(""), 6516417773221693515);
+        assert_eq!(crate::wy::rapidhash::rapidhash("\n"), 5006746792674864303);
+        assert_eq!(
+            crate::wy::rapidhash::rapidhash("something\n"),
+            15965596575264898037
+        );
+        assert_eq!(
+            crate::wy::rapidhash::rapidhash("abcdefghijklmnopqrstuvwxyz01234567890123456789\n"),
+            10644405912457645442
+        );
+        assert_eq!(
+            crate::wy::rapidhash::rapidhash("abcdefghijklmnopqrstuvwxyz012345678901234567890abcdefghijklmnopqrstuvwxyz012345678901234567890abcdefghijklmnopqrstuvwxyz012345678901234567890\n"),
+            7545813847373533788
+        );
+    }
+
+    #[test]
+    fn hasher_test() {
+        use std::hash::BuildHasher;
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let mut hasher = crate::wy::RapidHash64::default().build_hasher();
+        hasher.write(input.as_bytes());
+        assert_eq!(hasher.finish(), crate::wy::rapidhash::rapidhash(input));
+
+        let hasher = crate::wy::RapidHasher64::with_seed(4919);
+        assert_eq!(
+            hasher.finish(),
+            crate::wy::rapidhash::rapidhash_with_seed("", 4919)
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use std::hash::{BuildHasher, Hasher};
+
+use crate::HashAlgorithm;
+
+/// Hasher for wyhash implementation of the 64-bit hashing algorithm.
+///
+/// Nothing is buffered: every `write()` is hashed immediately, using the hash of the previous
+/// writes as its seed.
+/// This keeps hashing of short keys cheap, but it means the result only matches `wyhash_with_seed()`
+/// when the input is written all at once, and it depends on how the input is split across writes.
+/// That is fine for hash tables, since a type's `Hash` implementation always issues the same writes.
+#[derive(Clone, Copy, Default)]
+pub struct WyHasher64 {
+    seed: u64,
+    hash: Option<u64>,
+}
+
+impl WyHasher64 {
+    /// Create a hasher which uses the given seed.
+    pub fn with_seed(seed: u64) -> Self {
+        Self { seed, hash: None }
+    }
+}
+
+impl Hasher for WyHasher64 {
+    fn write(&mut self, bytes: &[u8]) {
+        let seed = self.hash.unwrap_or(self.seed);
+        self.hash = Some(wyhash_with_seed(bytes, seed));
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash.unwrap_or_else(|| wyhash_with_seed([], self.seed))
+    }
+}
+
+/// Hash builder for wyhash implementation of the 64-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct WyHash64 {
+    seed: u64,
+}
+
+impl WyHash64 {
+    /// Create a hash builder whose hashers use the given seed.
+    pub fn with_seed(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl BuildHasher for WyHash64 {
+    type Hasher = WyHasher64;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_seed(self.seed)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for wyhash implementation of the 64-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct WyRandomState64(WyHash64::with_seed(seed: u64));
+}
+
+// The code below is adapted from C code with the following disclaimer
+//-----------------------------------------------------------------------------
+// This is free and unencumbered software released into the public domain under The Unlicense
+// (http://unlicense.org/)
+// main repo: https://github.com/wangyi-fudan/wyhash
+// author: 王一 Wang Yi <godspeed_china@yeah.net>
+// contributors: Reini Urban, Dietrich Epp, Joshua Haberman, Tommy Ettinger, Daniel Lemire,
+// Otmar Ertl, cocowalla, leo-yuriev, Diego Barrios Romero, paulie-g, dumblob, Yann Collet,
+// ivte-ms, hyb, James Z.M. Gao, easyaspi314 (Devin), TheOneric
+
+// default secret parameters
+const SECRET: [u64; 4] = [
+    0xa0761d6478bd642f,
+    0xe7037ed1a0b428db,
+    0x8ebc6af09c88c6e3,
+    0x589965cc75374cc3,
+];
+
+/// wyhash implementation of the 64-bit hashing algorithm, version final4.
+pub struct Wyhash;
+
+impl HashAlgorithm for Wyhash {
+    const NAME: &'static str = "wyhash";
+    type Seed = u64;
+    type Output = u64;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        wyhash(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        wyhash_with_seed(v, seed)
+    }
+}
+
+/// wyhash implementation of the 64-bit hashing algorithm, version final4.
+/// This version allows you to specify a seed.
+pub fn wyhash_with_seed<T: AsRef<[u8]>>(v: T, mut seed: u64) -> u64 {
+    let data = v.as_ref();
+    let len = data.len();
+    seed ^= mix(seed ^ SECRET[0], SECRET[1]);
+
+    let (a, b) = if len <= 16 {
+        if len >= 4 {
+            let delta = (len >> 3) << 2;
+            (
+                read32(data, 0) << 32 | read32(data, delta),
+                read32(data, len - 4) << 32 | read32(data, len - 4 - delta),
+            )
+        } else if len > 0 {
+            (read3(data), 0)
+        } else {
+            (0, 0)
+        }
+    } else {
+        let mut p = data;
+        if p.len() > 48 {
+            let mut see1 = seed;
+            let mut see2 = seed;
+            while p.len() > 48 {
+                seed = mix(read64(p, 0) ^ SECRET[1], read64(p, 8) ^ seed);
+                see1 = mix(read64(p, 16) ^ SECRET[2], read64(p, 24) ^ see1);
+                see2 = mix(read64(p, 32) ^ SECRET[3], read64(p, 40) ^ see2);
+                p = &p[48..];
+            }
+            seed ^= see1 ^ see2;
+        }
+        while p.len() > 16 {
+            seed = mix(read64(p, 0) ^ SECRET[1], read64(p, 8) ^ seed);
+            p = &p[16..];
+        }
+        // the last 16 bytes of the input, which may overlap the ones already mixed in
+        (read64(data, len - 16), read64(data, len - 8))
+    };
+
+    let (a, b) = mum(a ^ SECRET[1], b ^ seed);
+    mix(a ^ SECRET[0] ^ len as u64, b ^ SECRET[1])
+}
+
+/// wyhash implementation of the 64-bit hashing algorithm, version final4.
+/// The seed is always 0 in this version.
+pub fn wyhash<T: AsRef<[u8]>>(v: T) -> u64 {
+    wyhash_with_seed(v, 0)
+}
+
+// 128-bit multiply, returning the low and high halves of the product
+#[inline(always)]
+pub(super) fn mum(a: u64, b: u64) -> (u64, u64) {
+    let r = a as u128 * b as u128;
+    (r as u64, (r >> 64) as u64)
+}
+
+// 128-bit multiply, folding the product into 64 bits
+#[inline(always)]
+pub(super) fn mix(a: u64, b: u64) -> u64 {
+    let (low, high) = mum(a, b);
+    low ^ high
+}
+
+#[inline(always)]
+pub(super) fn read64(data: &[u8], i: usize) -> u64 {
+    let buf = [
+        data[i],
+        data[i + 1],
+        data[i + 2],
+        data[i + 3],
+        data[i + 4],
+        data[i + 5],
+        data[i + 6],
+        data[i + 7],
+    ];
+    u64::from_le_bytes(buf)
+}
+
+#[inline(always)]
+pub(super) fn read32(data: &[u8], i: usize) -> u64 {
+    let buf = [data[i], data[i + 1], data[i + 2], data[i + 3]];
+    u32::from_le_bytes(buf) as u64
+}
+
+// 1 to 3 bytes: the first, middle and last byte
+#[inline(always)]
+fn read3(data: &[u8]) -> u64 {
+    let k = data.len();
+    (data[0] as u64) << 16 | (data[k >> 1] as u64) << 8 | data[k - 1] as u64
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    #[test]
+    fn compliance_test() {
+        // test vectors from the reference implementation, which uses the index as the seed
+        let expected = [
+            ("", 0x0409638ee2bde459),
+            ("a", 0xa8412d091b5fe0a9),
+            ("abc", 0x32dd92e4b2915153),
+            ("message digest", 0x8619124089a3a16b),
+            ("abcdefghijklmnopqrstuvwxyz", 0x7a43afb61d7f5f40),
+            (
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                0xff42329b90e50d58,
+            ),
+            (
+                "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                0xc39cab13b115aad3,
+            ),
+        ];
+        for (seed, &(input, hash)) in expected.iter().enumerate() {
+            assert_eq!(
+                crate::wy::wyhash::wyhash_with_seed(input, seed as u64),
+                hash,
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn hasher_test() {
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let mut hasher = crate::wy::WyHasher64::with_seed(4919);
+        hasher.write(input.as_bytes());
+        assert_eq!(
+            hasher.finish(),
+            crate::wy::wyhash::wyhash_with_seed(input, 4919)
+        );
+
+        let hasher = crate::wy::WyHasher64::with_seed(4919);
+        assert_eq!(
+            hasher.finish(),
+            crate::wy::wyhash::wyhash_with_seed("", 4919)
+        );
+
+        // every write is chained into the next one
+        let mut hasher = crate::wy::WyHasher64::with_seed(4919);
+        hasher.write(b"abc");
+        hasher.write(b"def");
+        let first = crate::wy::wyhash::wyhash_with_seed("abc", 4919);
+        assert_eq!(
+            hasher.finish(),
+            crate::wy::wyhash::wyhash_with_seed("def", first)
+        );
+    }
+}
@@ -0,0 +1,18 @@
+//! xxHash algorithm by Yann Collet.
+mod xxh3;
+mod xxh32;
+mod xxh3_128;
+mod xxh3_64;
+mod xxh64;
+
+pub use xxh3::XXH3_SECRET_SIZE_MIN;
+pub use xxh32::{hash32, hash32_with_seed, XxHash32, XxHasher32, XxRandomState32, Xxh32};
+pub use xxh3_128::{
+    xxh3_128, xxh3_128_with_secret, xxh3_128_with_seed, Xxh3Hash128, Xxh3Hasher128,
+    Xxh3RandomState128, Xxh3_128,
+};
+pub use xxh3_64::{
+    xxh3_64, xxh3_64_with_secret, xxh3_64_with_seed, Xxh3Hash64, Xxh3Hasher64, Xxh3RandomState64,
+    Xxh3_64,
+};
+pub use xxh64::{hash64, hash64_with_seed, XxHash64, XxHasher64, XxRandomState64, Xxh64};
@@ -0,0 +1,429 @@
+// Internals shared by the 64-bit and 128-bit variants of XXH3.
+
+use std::sync::Arc;
+
+// The code below is adapted from C code with the following license
+//-----------------------------------------------------------------------------
+// xxHash - Extremely Fast Hash algorithm
+// Copyright (C) 2012-2020 Yann Collet
+//
+// BSD 2-Clause License (https://www.opensource.org/licenses/bsd-license.php)
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are
+// met:
+//
+//    * Redistributions of source code must retain the above copyright
+//      notice, this list of conditions and the following disclaimer.
+//    * Redistributions in binary form must reproduce the above
+//      copyright notice, this list of conditions and the following disclaimer
+//      in the documentation and/or other materials provided with the
+//      distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+// OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+// LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+// DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+// THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+// (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+pub(super) const PRIME32_1: u64 = 0x9e3779b1;
+pub(super) const PRIME32_2: u64 = 0x85ebca77;
+pub(super) const PRIME32_3: u64 = 0xc2b2ae3d;
+
+pub(super) const PRIME64_1: u64 = 0x9e3779b185ebca87;
+pub(super) const PRIME64_2: u64 = 0xc2b2ae3d27d4eb4f;
+pub(super) const PRIME64_3: u64 = 0x165667b19e3779f9;
+pub(super) const PRIME64_4: u64 = 0x85ebca77c2b2ae63;
+pub(super) const PRIME64_5: u64 = 0x27d4eb2f165667c5;
+
+pub(super) const PRIME_MX1: u64 = 0x165667919e3779f9;
+pub(super) const PRIME_MX2: u64 = 0x9fb21c651e98df25;
+
+/// Minimum length of a custom XXH3 secret, in bytes.
+pub const XXH3_SECRET_SIZE_MIN: usize = 136;
+
+pub(super) const MIDSIZE_MAX: usize = 240;
+pub(super) const MIDSIZE_STARTOFFSET: usize = 3;
+pub(super) const MIDSIZE_LASTOFFSET: usize = 17;
+
+const STRIPE_LEN: usize = 64;
+const SECRET_CONSUME_RATE: usize = 8;
+const SECRET_LASTACC_START: usize = 7;
+const SECRET_MERGEACCS_START: usize = 11;
+const SECRET_DEFAULT_SIZE: usize = 192;
+const BUFFER_SIZE: usize = 256;
+
+const INIT_ACC: [u64; 8] = [
+    PRIME32_3, PRIME64_1, PRIME64_2, PRIME64_3, PRIME64_4, PRIME32_2, PRIME64_5, PRIME32_1,
+];
+
+// pseudorandom secret taken directly from FARSH
+pub(super) const DEFAULT_SECRET: [u8; SECRET_DEFAULT_SIZE] = [
+    0xb8, 0xfe, 0x6c, 0x39, 0x23, 0xa4, 0x4b, 0xbe, 0x7c, 0x01, 0x81, 0x2c, 0xf7, 0x21, 0xad, 0x1c,
+    0xde, 0xd4, 0x6d, 0xe9, 0x83, 0x90, 0x97, 0xdb, 0x72, 0x40, 0xa4, 0xa4, 0xb7, 0xb3, 0x67, 0x1f,
+    0xcb, 0x79, 0xe6, 0x4e, 0xcc, 0xc0, 0xe5, 0x78, 0x82, 0x5a, 0xd0, 0x7d, 0xcc, 0xff, 0x72, 0x21,
+    0xb8, 0x08, 0x46, 0x74, 0xf7, 0x43, 0x24, 0x8e, 0xe0, 0x35, 0x90, 0xe6, 0x81, 0x3a, 0x26, 0x4c,
+    0x3c, 0x28, 0x52, 0xbb, 0x91, 0xc3, 0x00, 0xcb, 0x88, 0xd0, 0x65, 0x8b, 0x1b, 0x53, 0x2e, 0xa3,
+    0x71, 0x64, 0x48, 0x97, 0xa2, 0x0d, 0xf9, 0x4e, 0x38, 0x19, 0xef, 0x46, 0xa9, 0xde, 0xac, 0xd8,
+    0xa8, 0xfa, 0x76, 0x3f, 0xe3, 0x9c, 0x34, 0x3f, 0xf9, 0xdc, 0xbb, 0xc7, 0xc7, 0x0b, 0x4f, 0x1d,
+    0x8a, 0x51, 0xe0, 0x4b, 0xcd, 0xb4, 0x59, 0x31, 0xc8, 0x9f, 0x7e, 0xc9, 0xd9, 0x78, 0x73, 0x64,
+    0xea, 0xc5, 0xac, 0x83, 0x34, 0xd3, 0xeb, 0xc3, 0xc5, 0x81, 0xa0, 0xff, 0xfa, 0x13, 0x63, 0xeb,
+    0x17, 0x0d, 0xdd, 0x51, 0xb7, 0xf0, 0xda, 0x49, 0xd3, 0x16, 0x55, 0x26, 0x29, 0xd4, 0x68, 0x9e,
+    0x2b, 0x16, 0xbe, 0x58, 0x7d, 0x47, 0xa1, 0xfc, 0x8f, 0xf8, 0xb8, 0xd1, 0x7a, 0xd0, 0x31, 0xce,
+    0x45, 0xcb, 0x3a, 0x8f, 0x95, 0x16, 0x04, 0x28, 0xaf, 0xd7, 0xfb, 0xca, 0xbb, 0x4b, 0x40, 0x7e,
+];
+
+// Secret used by a hash: the default one, one derived from a seed, or one given by the caller.
+#[derive(Clone)]
+pub(super) enum Secret {
+    Default,
+    Seeded(u64, Box<[u8; SECRET_DEFAULT_SIZE]>),
+    Custom(Arc<[u8]>),
+}
+
+impl Secret {
+    pub(super) fn with_seed(seed: u64) -> Self {
+        if seed == 0 {
+            Secret::Default
+        } else {
+            Secret::Seeded(seed, Box::new(custom_secret(seed)))
+        }
+    }
+
+    pub(super) fn custom(secret: Arc<[u8]>) -> Self {
+        check_secret(&secret);
+        Secret::Custom(secret)
+    }
+
+    // seed mixed into inputs of up to 240 bytes
+    #[inline(always)]
+    pub(super) fn seed(&self) -> u64 {
+        match self {
+            Secret::Seeded(seed, _) => *seed,
+            _ => 0,
+        }
+    }
+
+    // secret used for inputs of up to 240 bytes
+    #[inline(always)]
+    pub(super) fn short(&self) -> &[u8] {
+        match self {
+            Secret::Custom(secret) => secret,
+            _ => &DEFAULT_SECRET,
+        }
+    }
+
+    // secret used for inputs longer than 240 bytes
+    #[inline(always)]
+    pub(super) fn long(&self) -> &[u8] {
+        match self {
+            Secret::Default => &DEFAULT_SECRET,
+            Secret::Seeded(_, secret) => &secret[..],
+            Secret::Custom(secret) => secret,
+        }
+    }
+}
+
+pub(super) fn check_secret(secret: &[u8]) {
+    assert!(
+        secret.len() >= XXH3_SECRET_SIZE_MIN,
+        "XXH3 secrets must be at least {} bytes long, got {}",
+        XXH3_SECRET_SIZE_MIN,
+        secret.len()
+    );
+}
+
+pub(super) fn custom_secret(seed: u64) -> [u8; SECRET_DEFAULT_SIZE] {
+    let mut secret = [0; SECRET_DEFAULT_SIZE];
+    for i in 0..SECRET_DEFAULT_SIZE / 16 {
+        let lo = read64(&DEFAULT_SECRET, 16 * i).wrapping_add(seed);
+        let hi = read64(&DEFAULT_SECRET, 16 * i + 8).wrapping_sub(seed);
+        secret[16 * i..16 * i + 8].copy_from_slice(&lo.to_le_bytes());
+        secret[16 * i + 8..16 * i + 16].copy_from_slice(&hi.to_le_bytes());
+    }
+    secret
+}
+
+#[inline(always)]
+pub(super) fn read32(data: &[u8], i: usize) -> u32 {
+    let buf = [data[i], data[i + 1], data[i + 2], data[i + 3]];
+    u32::from_le_bytes(buf)
+}
+
+#[inline(always)]
+pub(super) fn read64(data: &[u8], i: usize) -> u64 {
+    let buf = [
+        data[i],
+        data[i + 1],
+        data[i + 2],
+        data[i + 3],
+        data[i + 4],
+        data[i + 5],
+        data[i + 6],
+        data[i + 7],
+    ];
+    u64::from_le_bytes(buf)
+}
+
+#[inline(always)]
+pub(super) fn mul128(a: u64, b: u64) -> (u64, u64) {
+    let product = (a as u128).wrapping_mul(b as u128);
+    (product as u64, (product >> 64) as u64)
+}
+
+#[inline(always)]
+pub(super) fn mul128_fold64(a: u64, b: u64) -> u64 {
+    let (lo, hi) = mul128(a, b);
+    lo ^ hi
+}
+
+#[inline(always)]
+pub(super) fn xxh64_avalanche(mut h64: u64) -> u64 {
+    h64 ^= h64 >> 33;
+    h64 = h64.wrapping_mul(PRIME64_2);
+    h64 ^= h64 >> 29;
+    h64 = h64.wrapping_mul(PRIME64_3);
+    h64 ^= h64 >> 32;
+    h64
+}
+
+#[inline(always)]
+pub(super) fn avalanche(mut h64: u64) -> u64 {
+    h64 ^= h64 >> 37;
+    h64 = h64.wrapping_mul(PRIME_MX1);
+    h64 ^= h64 >> 32;
+    h64
+}
+
+#[inline(always)]
+pub(super) fn mix16(data: &[u8], i: usize, secret: &[u8], s: usize, seed: u64) -> u64 {
+    mul128_fold64(
+        read64(data, i) ^ read64(secret, s).wrapping_add(seed),
+        read64(data, i + 8) ^ read64(secret, s + 8).wrapping_sub(seed),
+    )
+}
+
+#[inline(always)]
+fn accumulate_512(acc: &mut [u64; 8], data: &[u8], i: usize, secret: &[u8], s: usize) {
+    for lane in 0..8 {
+        let data_val = read64(data, i + 8 * lane);
+        let data_key = data_val ^ read64(secret, s + 8 * lane);
+        acc[lane ^ 1] = acc[lane ^ 1].wrapping_add(data_val);
+        acc[lane] = acc[lane].wrapping_add((data_key & 0xffffffff).wrapping_mul(data_key >> 32));
+    }
+}
+
+#[inline(always)]
+fn scramble(acc: &mut [u64; 8], secret: &[u8], s: usize) {
+    for (lane, acc) in acc.iter_mut().enumerate() {
+        let mut acc64 = *acc;
+        acc64 ^= acc64 >> 47;
+        acc64 ^= read64(secret, s + 8 * lane);
+        *acc = acc64.wrapping_mul(PRIME32_1);
+    }
+}
+
+#[inline(always)]
+fn accumulate(acc: &mut [u64; 8], data: &[u8], i: usize, secret: &[u8], n_stripes: usize) {
+    for n in 0..n_stripes {
+        accumulate_512(
+            acc,
+            data,
+            i + n * STRIPE_LEN,
+            secret,
+            n * SECRET_CONSUME_RATE,
+        );
+    }
+}
+
+#[inline(always)]
+fn merge_accs(acc: &[u64; 8], secret: &[u8], s: usize, start: u64) -> u64 {
+    let mut result = start;
+    for i in 0..4 {
+        result = result.wrapping_add(mul128_fold64(
+            acc[2 * i] ^ read64(secret, s + 16 * i),
+            acc[2 * i + 1] ^ read64(secret, s + 16 * i + 8),
+        ));
+    }
+    avalanche(result)
+}
+
+// run the main loop over an input longer than 240 bytes
+pub(super) fn hash_long(data: &[u8], secret: &[u8]) -> [u64; 8] {
+    let mut acc = INIT_ACC;
+    let stripes_per_block = (secret.len() - STRIPE_LEN) / SECRET_CONSUME_RATE;
+    let block_len = STRIPE_LEN * stripes_per_block;
+    let n_blocks = (data.len() - 1) / block_len;
+
+    for n in 0..n_blocks {
+        accumulate(&mut acc, data, n * block_len, secret, stripes_per_block);
+        scramble(&mut acc, secret, secret.len() - STRIPE_LEN);
+    }
+
+    // last partial block
+    let n_stripes = ((data.len() - 1) - block_len * n_blocks) / STRIPE_LEN;
+    accumulate(&mut acc, data, n_blocks * block_len, secret, n_stripes);
+
+    // last stripe
+    accumulate_512(
+        &mut acc,
+        data,
+        data.len() - STRIPE_LEN,
+        secret,
+        secret.len() - STRIPE_LEN - SECRET_LASTACC_START,
+    );
+    acc
+}
+
+pub(super) fn merge_64(acc: &[u64; 8], secret: &[u8], length: u64) -> u64 {
+    merge_accs(
+        acc,
+        secret,
+        SECRET_MERGEACCS_START,
+        length.wrapping_mul(PRIME64_1),
+    )
+}
+
+pub(super) fn merge_128(acc: &[u64; 8], secret: &[u8], length: u64) -> u128 {
+    let low = merge_accs(
+        acc,
+        secret,
+        SECRET_MERGEACCS_START,
+        length.wrapping_mul(PRIME64_1),
+    );
+    let high = merge_accs(
+        acc,
+        secret,
+        secret.len() - STRIPE_LEN - SECRET_MERGEACCS_START,
+        !length.wrapping_mul(PRIME64_2),
+    );
+    (high as u128) << 64 | (low as u128)
+}
+
+// Streaming state shared by both output widths.
+// Input is buffered until more than 256 bytes are available, and the final stripe is always kept
+// back until the hash is finished, as it is processed differently.
+#[derive(Clone)]
+pub(super) struct State {
+    pub(super) secret: Secret,
+    acc: [u64; 8],
+    buffer: [u8; BUFFER_SIZE],
+    buffered: usize,
+    // last stripe consumed, needed when fewer than 64 bytes are buffered at the end
+    last_stripe: [u8; STRIPE_LEN],
+    stripes_so_far: usize,
+    length: u64,
+}
+
+impl State {
+    pub(super) fn new(secret: Secret) -> Self {
+        State {
+            secret,
+            acc: INIT_ACC,
+            buffer: [0; BUFFER_SIZE],
+            buffered: 0,
+            last_stripe: [0; STRIPE_LEN],
+            stripes_so_far: 0,
+            length: 0,
+        }
+    }
+
+    pub(super) fn update(&mut self, bytes: &[u8]) {
+        let mut data = bytes;
+        self.length += data.len() as u64;
+        if self.buffered + data.len() <= BUFFER_SIZE {
+            self.buffer[self.buffered..self.buffered + data.len()].copy_from_slice(data);
+            self.buffered += data.len();
+            return;
+        }
+        // complete and consume the buffer
+        if self.buffered != 0 {
+            let fill = BUFFER_SIZE - self.buffered;
+            self.buffer[self.buffered..].copy_from_slice(&data[..fill]);
+            data = &data[fill..];
+            let buffer = self.buffer;
+            self.consume(&buffer, 0, BUFFER_SIZE / STRIPE_LEN);
+            self.last_stripe
+                .copy_from_slice(&buffer[BUFFER_SIZE - STRIPE_LEN..]);
+            self.buffered = 0;
+        }
+        // consume directly from the input, keeping at least one byte back
+        let mut i = 0;
+        while data.len() - i > BUFFER_SIZE {
+            self.consume(data, i, BUFFER_SIZE / STRIPE_LEN);
+            i += BUFFER_SIZE;
+            self.last_stripe.copy_from_slice(&data[i - STRIPE_LEN..i]);
+        }
+        let rest = &data[i..];
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffered = rest.len();
+    }
+
+    fn consume(&mut self, data: &[u8], i: usize, n_stripes: usize) {
+        let secret = self.secret.long();
+        let stripes_per_block = (secret.len() - STRIPE_LEN) / SECRET_CONSUME_RATE;
+        let until_scramble = stripes_per_block - self.stripes_so_far;
+        let s = self.stripes_so_far * SECRET_CONSUME_RATE;
+        if until_scramble <= n_stripes {
+            // finish the current block, scramble, then start the next one
+            accumulate(&mut self.acc, data, i, &secret[s..], until_scramble);
+            scramble(&mut self.acc, secret, secret.len() - STRIPE_LEN);
+            let rest = n_stripes - until_scramble;
+            accumulate(
+                &mut self.acc,
+                data,
+                i + until_scramble * STRIPE_LEN,
+                secret,
+                rest,
+            );
+            self.stripes_so_far = rest;
+        } else {
+            accumulate(&mut self.acc, data, i, &secret[s..], n_stripes);
+            self.stripes_so_far += n_stripes;
+        }
+    }
+
+    pub(super) fn length(&self) -> u64 {
+        self.length
+    }
+
+    // the whole input, when it was at most 240 bytes long
+    pub(super) fn short_input(&self) -> &[u8] {
+        &self.buffer[..self.buffered]
+    }
+
+    // the accumulators as they would be after `hash_long()`, when more than 240 bytes were written
+    pub(super) fn long_acc(&self) -> [u64; 8] {
+        let mut state = self.clone();
+        let secret = self.secret.long();
+        let last = if state.buffered >= STRIPE_LEN {
+            let n_stripes = (state.buffered - 1) / STRIPE_LEN;
+            let buffer = state.buffer;
+            state.consume(&buffer, 0, n_stripes);
+            let mut last = [0; STRIPE_LEN];
+            last.copy_from_slice(&state.buffer[state.buffered - STRIPE_LEN..state.buffered]);
+            last
+        } else {
+            // combine the end of the last consumed stripe with the buffered bytes
+            let mut last = [0; STRIPE_LEN];
+            let from_previous = STRIPE_LEN - state.buffered;
+            last[..from_previous].copy_from_slice(&state.last_stripe[state.buffered..]);
+            last[from_previous..].copy_from_slice(&state.buffer[..state.buffered]);
+            last
+        };
+        accumulate_512(
+            &mut state.acc,
+            &last,
+            0,
+            secret,
+            secret.len() - STRIPE_LEN - SECRET_LASTACC_START,
+        );
+        state.acc
+    }
+}
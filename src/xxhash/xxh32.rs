@@ -0,0 +1,276 @@
+use std::hash::{BuildHasher, Hasher};
+
+use crate::HashAlgorithm;
+
+/// Hasher for xxHash implementation of the 32-bit hashing algorithm.
+pub struct XxHasher32 {
+    seed: u32,
+    v: [u32; 4],
+    tail: [u8; 16],
+    tail_len: usize,
+    length: u64,
+}
+
+impl XxHasher32 {
+    /// Create a hasher which uses the given seed.
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            seed,
+            v: init(seed),
+            tail: [0; 16],
+            tail_len: 0,
+            length: 0,
+        }
+    }
+}
+
+impl Default for XxHasher32 {
+    fn default() -> Self {
+        Self::with_seed(0)
+    }
+}
+
+impl Hasher for XxHasher32 {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut data = bytes;
+        self.length += data.len() as u64;
+        // complete a previously started stripe
+        if self.tail_len != 0 {
+            let fill = (16 - self.tail_len).min(data.len());
+            self.tail[self.tail_len..self.tail_len + fill].copy_from_slice(&data[..fill]);
+            self.tail_len += fill;
+            data = &data[fill..];
+            if self.tail_len < 16 {
+                return;
+            }
+            stripe(&mut self.v, &self.tail, 0);
+            self.tail_len = 0;
+        }
+        // process full stripes directly from the input
+        let n_stripes = data.len() / 16;
+        for i in 0..n_stripes {
+            stripe(&mut self.v, data, i * 16);
+        }
+        // keep the remainder for the next write
+        let rest = &data[n_stripes * 16..];
+        self.tail[..rest.len()].copy_from_slice(rest);
+        self.tail_len = rest.len();
+    }
+
+    fn finish(&self) -> u64 {
+        let h32 = if self.length >= 16 {
+            converge(&self.v)
+        } else {
+            self.seed.wrapping_add(PRIME32_5)
+        };
+        finalize(h32, &self.tail[..self.tail_len], self.length) as u64
+    }
+}
+
+/// Hash builder for xxHash implementation of the 32-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct XxHash32 {
+    seed: u32,
+}
+
+impl XxHash32 {
+    /// Create a hash builder whose hashers use the given seed.
+    pub fn with_seed(seed: u32) -> Self {
+        Self { seed }
+    }
+}
+
+impl BuildHasher for XxHash32 {
+    type Hasher = XxHasher32;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_seed(self.seed)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for xxHash implementation of the 32-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct XxRandomState32(XxHash32::with_seed(seed: u32));
+}
+
+// The code below is adapted from C code with the following license
+//-----------------------------------------------------------------------------
+// xxHash - Extremely Fast Hash algorithm
+// Copyright (C) 2012-2020 Yann Collet
+//
+// BSD 2-Clause License (https://www.opensource.org/licenses/bsd-license.php)
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are
+// met:
+//
+//    * Redistributions of source code must retain the above copyright
+//      notice, this list of conditions and the following disclaimer.
+//    * Redistributions in binary form must reproduce the above
+//      copyright notice, this list of conditions and the following disclaimer
+//      in the documentation and/or other materials provided with the
+//      distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+// OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+// LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+// DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+// THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+// (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+const PRIME32_1: u32 = 0x9e3779b1;
+const PRIME32_2: u32 = 0x85ebca77;
+const PRIME32_3: u32 = 0xc2b2ae3d;
+const PRIME32_4: u32 = 0x27d4eb2f;
+const PRIME32_5: u32 = 0x165667b1;
+
+/// xxHash implementation of the 32-bit hashing algorithm.
+pub struct Xxh32;
+
+impl HashAlgorithm for Xxh32 {
+    const NAME: &'static str = "xxh32";
+    type Seed = u32;
+    type Output = u32;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hash32(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hash32_with_seed(v, seed)
+    }
+}
+
+/// xxHash implementation of the 32-bit hashing algorithm.
+/// This version allows you to specify a seed.
+pub fn hash32_with_seed<T: AsRef<[u8]>>(v: T, seed: u32) -> u32 {
+    let data = v.as_ref();
+    let n_stripes = data.len() / 16;
+
+    let h32 = if n_stripes != 0 {
+        let mut v = init(seed);
+        for i in 0..n_stripes {
+            stripe(&mut v, data, i * 16);
+        }
+        converge(&v)
+    } else {
+        seed.wrapping_add(PRIME32_5)
+    };
+
+    finalize(h32, &data[n_stripes * 16..], data.len() as u64)
+}
+
+/// xxHash implementation of the 32-bit hashing algorithm.
+/// The seed is always 0 in this version.
+pub fn hash32<T: AsRef<[u8]>>(v: T) -> u32 {
+    hash32_with_seed(v, 0)
+}
+
+#[inline(always)]
+fn init(seed: u32) -> [u32; 4] {
+    [
+        seed.wrapping_add(PRIME32_1).wrapping_add(PRIME32_2),
+        seed.wrapping_add(PRIME32_2),
+        seed,
+        seed.wrapping_sub(PRIME32_1),
+    ]
+}
+
+#[inline(always)]
+fn round(acc: u32, input: u32) -> u32 {
+    acc.wrapping_add(input.wrapping_mul(PRIME32_2))
+        .rotate_left(13)
+        .wrapping_mul(PRIME32_1)
+}
+
+#[inline(always)]
+fn stripe(v: &mut [u32; 4], data: &[u8], i: usize) {
+    v[0] = round(v[0], get_u32(data, i));
+    v[1] = round(v[1], get_u32(data, i + 4));
+    v[2] = round(v[2], get_u32(data, i + 8));
+    v[3] = round(v[3], get_u32(data, i + 12));
+}
+
+#[inline(always)]
+fn converge(v: &[u32; 4]) -> u32 {
+    v[0].rotate_left(1)
+        .wrapping_add(v[1].rotate_left(7))
+        .wrapping_add(v[2].rotate_left(12))
+        .wrapping_add(v[3].rotate_left(18))
+}
+
+#[inline(always)]
+fn finalize(mut h32: u32, tail: &[u8], length: u64) -> u32 {
+    h32 = h32.wrapping_add(length as u32);
+
+    let mut chunks = tail.chunks_exact(4);
+    for chunk in &mut chunks {
+        h32 = h32.wrapping_add(get_u32(chunk, 0).wrapping_mul(PRIME32_3));
+        h32 = h32.rotate_left(17).wrapping_mul(PRIME32_4);
+    }
+    for &byte in chunks.remainder() {
+        h32 = h32.wrapping_add((byte as u32).wrapping_mul(PRIME32_5));
+        h32 = h32.rotate_left(11).wrapping_mul(PRIME32_1);
+    }
+
+    // avalanche
+    h32 ^= h32 >> 15;
+    h32 = h32.wrapping_mul(PRIME32_2);
+    h32 ^= h32 >> 13;
+    h32 = h32.wrapping_mul(PRIME32_3);
+    h32 ^= h32 >> 16;
+    h32
+}
+
+#[inline(always)]
+fn get_u32(data: &[u8], i: usize) -> u32 {
+    let buf = [data[i], data[i + 1], data[i + 2], data[i + 3]];
+    u32::from_le_bytes(buf)
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    #[test]
+    fn compliance_test() {
+        assert_eq!(crate::xxhash::xxh32::hash32(""), 0x02cc5d05);
+        assert_eq!(crate::xxhash::xxh32::hash32("abc"), 0x32d153ff);
+        assert_eq!(
+            crate::xxhash::xxh32::hash32("Nobody inspects the spammish repetition"),
+            0xe2293b2f
+        );
+    }
+
+    #[test]
+    fn streaming_test() {
+        let input: Vec<u8> = (0..=255).collect();
+        for len in 0..input.len() {
+            let expected = crate::xxhash::xxh32::hash32_with_seed(&input[..len], 4919) as u64;
+            for split in [1, 3, 16, 33] {
+                let mut hasher = crate::xxhash::XxHasher32::with_seed(4919);
+                for chunk in input[..len].chunks(split) {
+                    hasher.write(chunk);
+                }
+                assert_eq!(hasher.finish(), expected);
+            }
+        }
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn fasthash_interop_test() {
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let seed = 4919;
+        assert_eq!(
+            crate::xxhash::xxh32::hash32_with_seed(input, seed),
+            fasthash::xx::hash32_with_seed(input, seed)
+        );
+    }
+}
@@ -0,0 +1,475 @@
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Arc;
+
+use super::xxh3::{
+    avalanche, check_secret, custom_secret, hash_long, merge_128, mix16, mul128, read32, read64,
+    xxh64_avalanche, Secret, State, DEFAULT_SECRET, MIDSIZE_LASTOFFSET, MIDSIZE_MAX,
+    MIDSIZE_STARTOFFSET, PRIME32_2, PRIME64_1, PRIME64_2, PRIME64_4, PRIME_MX2,
+    XXH3_SECRET_SIZE_MIN,
+};
+use crate::{HashAlgorithm, Hasher128};
+
+/// Hasher for xxHash implementation of the 128-bit XXH3 hashing algorithm.
+#[derive(Clone)]
+pub struct Xxh3Hasher128 {
+    state: State,
+}
+
+impl Xxh3Hasher128 {
+    /// Create a hasher which uses the given seed.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            state: State::new(Secret::with_seed(seed)),
+        }
+    }
+
+    /// Create a hasher which uses the given secret instead of the default one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the secret is shorter than 136 bytes.
+    pub fn with_secret<S: Into<Arc<[u8]>>>(secret: S) -> Self {
+        Self {
+            state: State::new(Secret::custom(secret.into())),
+        }
+    }
+}
+
+impl Default for Xxh3Hasher128 {
+    fn default() -> Self {
+        Self::with_seed(0)
+    }
+}
+
+impl Hasher for Xxh3Hasher128 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.state.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        self.finish128() as u64
+    }
+}
+
+impl Hasher128 for Xxh3Hasher128 {
+    fn finish128(&self) -> u128 {
+        let secret = &self.state.secret;
+        if self.state.length() > MIDSIZE_MAX as u64 {
+            merge_128(&self.state.long_acc(), secret.long(), self.state.length())
+        } else {
+            hash_short(self.state.short_input(), secret.short(), secret.seed())
+        }
+    }
+}
+
+/// Hash builder for xxHash implementation of the 128-bit XXH3 hashing algorithm.
+#[derive(Clone, Default)]
+pub struct Xxh3Hash128 {
+    seed: u64,
+    secret: Option<Arc<[u8]>>,
+}
+
+impl Xxh3Hash128 {
+    /// Create a hash builder whose hashers use the given seed.
+    pub fn with_seed(seed: u64) -> Self {
+        Self { seed, secret: None }
+    }
+
+    /// Create a hash builder whose hashers use the given secret instead of the default one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the secret is shorter than 136 bytes.
+    pub fn with_secret<S: Into<Arc<[u8]>>>(secret: S) -> Self {
+        let secret = secret.into();
+        check_secret(&secret);
+        Self {
+            seed: 0,
+            secret: Some(secret),
+        }
+    }
+}
+
+impl BuildHasher for Xxh3Hash128 {
+    type Hasher = Xxh3Hasher128;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        match &self.secret {
+            Some(secret) => Self::Hasher::with_secret(secret.clone()),
+            None => Self::Hasher::with_seed(self.seed),
+        }
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for xxHash implementation of the 128-bit XXH3 hashing algorithm.
+    #[derive(Clone)]
+    pub struct Xxh3RandomState128(Xxh3Hash128::with_seed(seed: u64));
+}
+
+// The code below is adapted from C code with the following license
+//-----------------------------------------------------------------------------
+// xxHash - Extremely Fast Hash algorithm
+// Copyright (C) 2012-2020 Yann Collet
+//
+// BSD 2-Clause License (https://www.opensource.org/licenses/bsd-license.php)
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are
+// met:
+//
+//    * Redistributions of source code must retain the above copyright
+//      notice, this list of conditions and the following disclaimer.
+//    * Redistributions in binary form must reproduce the above
+//      copyright notice, this list of conditions and the following disclaimer
+//      in the documentation and/or other materials provided with the
+//      distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+// OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+// LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+// DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+// THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+// (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+/// xxHash implementation of the 128-bit XXH3 hashing algorithm.
+pub struct Xxh3_128;
+
+impl HashAlgorithm for Xxh3_128 {
+    const NAME: &'static str = "xxh3_128";
+    type Seed = u64;
+    type Output = u128;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        xxh3_128(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        xxh3_128_with_seed(v, seed)
+    }
+}
+
+/// xxHash implementation of the 128-bit XXH3 hashing algorithm.
+/// This version allows you to specify a seed.
+///
+/// The upper 64 bits of the result are the high half of the canonical XXH3 128-bit hash.
+pub fn xxh3_128_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u128 {
+    let data = v.as_ref();
+    if data.len() <= MIDSIZE_MAX {
+        hash_short(data, &DEFAULT_SECRET, seed)
+    } else if seed == 0 {
+        merge_128(
+            &hash_long(data, &DEFAULT_SECRET),
+            &DEFAULT_SECRET,
+            data.len() as u64,
+        )
+    } else {
+        let secret = custom_secret(seed);
+        merge_128(&hash_long(data, &secret), &secret, data.len() as u64)
+    }
+}
+
+/// xxHash implementation of the 128-bit XXH3 hashing algorithm.
+/// This version uses the given secret instead of the default one.
+///
+/// # Panics
+///
+/// Panics if the secret is shorter than 136 bytes.
+pub fn xxh3_128_with_secret<T: AsRef<[u8]>>(v: T, secret: &[u8]) -> u128 {
+    check_secret(secret);
+    let data = v.as_ref();
+    if data.len() <= MIDSIZE_MAX {
+        hash_short(data, secret, 0)
+    } else {
+        merge_128(&hash_long(data, secret), secret, data.len() as u64)
+    }
+}
+
+/// xxHash implementation of the 128-bit XXH3 hashing algorithm.
+/// The seed is always 0 in this version.
+pub fn xxh3_128<T: AsRef<[u8]>>(v: T) -> u128 {
+    xxh3_128_with_seed(v, 0)
+}
+
+// hash an input of up to 240 bytes
+fn hash_short(data: &[u8], secret: &[u8], seed: u64) -> u128 {
+    let (low, high) = match data.len() {
+        0..=16 => hash_len_0_to_16(data, secret, seed),
+        17..=128 => hash_len_17_to_128(data, secret, seed),
+        _ => hash_len_129_to_240(data, secret, seed),
+    };
+    (high as u128) << 64 | (low as u128)
+}
+
+#[inline(always)]
+fn hash_len_0_to_16(data: &[u8], secret: &[u8], seed: u64) -> (u64, u64) {
+    let len = data.len();
+    if len > 8 {
+        let bitflipl = (read64(secret, 32) ^ read64(secret, 40)).wrapping_sub(seed);
+        let bitfliph = (read64(secret, 48) ^ read64(secret, 56)).wrapping_add(seed);
+        let input_lo = read64(data, 0);
+        let mut input_hi = read64(data, len - 8);
+        let (mut m_low, mut m_high) = mul128(input_lo ^ input_hi ^ bitflipl, PRIME64_1);
+        m_low = m_low.wrapping_add(((len - 1) as u64) << 54);
+        input_hi ^= bitfliph;
+        m_high = m_high
+            .wrapping_add(input_hi)
+            .wrapping_add((input_hi as u32 as u64).wrapping_mul(PRIME32_2 - 1));
+        m_low ^= m_high.swap_bytes();
+        let (low, mut high) = mul128(m_low, PRIME64_2);
+        high = high.wrapping_add(m_high.wrapping_mul(PRIME64_2));
+        (avalanche(low), avalanche(high))
+    } else if len >= 4 {
+        let seed = seed ^ ((seed as u32).swap_bytes() as u64) << 32;
+        let input_lo = read32(data, 0) as u64;
+        let input_hi = read32(data, len - 4) as u64;
+        let bitflip = (read64(secret, 16) ^ read64(secret, 24)).wrapping_add(seed);
+        let keyed = input_lo.wrapping_add(input_hi << 32) ^ bitflip;
+        let (mut low, mut high) = mul128(keyed, PRIME64_1.wrapping_add((len as u64) << 2));
+        high = high.wrapping_add(low << 1);
+        low ^= high >> 3;
+        low ^= low >> 35;
+        low = low.wrapping_mul(PRIME_MX2);
+        low ^= low >> 28;
+        (low, avalanche(high))
+    } else if len > 0 {
+        let combinedl = (data[0] as u32) << 16
+            | (data[len >> 1] as u32) << 24
+            | (data[len - 1] as u32)
+            | (len as u32) << 8;
+        let combinedh = combinedl.swap_bytes().rotate_left(13);
+        let bitflipl = ((read32(secret, 0) ^ read32(secret, 4)) as u64).wrapping_add(seed);
+        let bitfliph = ((read32(secret, 8) ^ read32(secret, 12)) as u64).wrapping_sub(seed);
+        (
+            xxh64_avalanche(combinedl as u64 ^ bitflipl),
+            xxh64_avalanche(combinedh as u64 ^ bitfliph),
+        )
+    } else {
+        let bitflipl = read64(secret, 64) ^ read64(secret, 72);
+        let bitfliph = read64(secret, 80) ^ read64(secret, 88);
+        (
+            xxh64_avalanche(seed ^ bitflipl),
+            xxh64_avalanche(seed ^ bitfliph),
+        )
+    }
+}
+
+#[inline(always)]
+fn mix32(
+    acc: &mut (u64, u64),
+    data: &[u8],
+    i: usize,
+    j: usize,
+    secret: &[u8],
+    s: usize,
+    seed: u64,
+) {
+    acc.0 = acc.0.wrapping_add(mix16(data, i, secret, s, seed));
+    acc.0 ^= read64(data, j).wrapping_add(read64(data, j + 8));
+    acc.1 = acc.1.wrapping_add(mix16(data, j, secret, s + 16, seed));
+    acc.1 ^= read64(data, i).wrapping_add(read64(data, i + 8));
+}
+
+#[inline(always)]
+fn finalize(acc: (u64, u64), len: usize, seed: u64) -> (u64, u64) {
+    let low = acc.0.wrapping_add(acc.1);
+    let high = acc
+        .0
+        .wrapping_mul(PRIME64_1)
+        .wrapping_add(acc.1.wrapping_mul(PRIME64_4))
+        .wrapping_add((len as u64).wrapping_sub(seed).wrapping_mul(PRIME64_2));
+    (avalanche(low), 0u64.wrapping_sub(avalanche(high)))
+}
+
+#[inline(always)]
+fn hash_len_17_to_128(data: &[u8], secret: &[u8], seed: u64) -> (u64, u64) {
+    let len = data.len();
+    let mut acc = ((len as u64).wrapping_mul(PRIME64_1), 0);
+    if len > 32 {
+        if len > 64 {
+            if len > 96 {
+                mix32(&mut acc, data, 48, len - 64, secret, 96, seed);
+            }
+            mix32(&mut acc, data, 32, len - 48, secret, 64, seed);
+        }
+        mix32(&mut acc, data, 16, len - 32, secret, 32, seed);
+    }
+    mix32(&mut acc, data, 0, len - 16, secret, 0, seed);
+    finalize(acc, len, seed)
+}
+
+#[inline(always)]
+fn hash_len_129_to_240(data: &[u8], secret: &[u8], seed: u64) -> (u64, u64) {
+    let len = data.len();
+    let n_rounds = len / 32;
+    let mut acc = ((len as u64).wrapping_mul(PRIME64_1), 0);
+    for i in 0..4 {
+        mix32(&mut acc, data, 32 * i, 32 * i + 16, secret, 32 * i, seed);
+    }
+    acc = (avalanche(acc.0), avalanche(acc.1));
+    for i in 4..n_rounds {
+        let s = MIDSIZE_STARTOFFSET + 32 * (i - 4);
+        mix32(&mut acc, data, 32 * i, 32 * i + 16, secret, s, seed);
+    }
+    let s = XXH3_SECRET_SIZE_MIN - MIDSIZE_LASTOFFSET - 16;
+    mix32(
+        &mut acc,
+        data,
+        len - 16,
+        len - 32,
+        secret,
+        s,
+        0u64.wrapping_sub(seed),
+    );
+    finalize(acc, len, seed)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Hasher128;
+    use std::hash::Hasher;
+
+    fn input() -> Vec<u8> {
+        (0..3000u32)
+            .map(|i| (i.wrapping_mul(2654435761) >> 13) as u8)
+            .collect()
+    }
+
+    fn secret() -> Vec<u8> {
+        (0..200u32)
+            .map(|i| (i.wrapping_mul(0x9e3779b1) >> 7) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn compliance_test() {
+        assert_eq!(
+            crate::xxhash::xxh3_128(""),
+            0x99aa06d3014798d86001c324468d497f
+        );
+        assert_eq!(
+            crate::xxhash::xxh3_128("a"),
+            0xa96faf705af16834e6c632b61e964e1f
+        );
+        assert_eq!(
+            crate::xxhash::xxh3_128("abc"),
+            0x06b05ab6733a618578af5f94892f3950
+        );
+        assert_eq!(
+            crate::xxhash::xxh3_128("Nobody inspects the spammish repetition"),
+            0xa32c6f55b80b5f449f1a957522431b91
+        );
+    }
+
+    #[test]
+    fn length_classes_test() {
+        let input = input();
+        let secret = secret();
+        // (length, seeded with 4919, with custom secret)
+        let expected = [
+            (
+                0,
+                0x49f9cb18d1ea3a867ef4f70cca5f6ef6,
+                0x3ec53ac376f93c6ebc1aad505a4fd520,
+            ),
+            (
+                3,
+                0x938803da054a0c74442bb292f79092b5,
+                0x2ab6aedb5baa43002637d43fb2365673,
+            ),
+            (
+                8,
+                0xde50212a331aee8c37b6e141f234b8be,
+                0x1d8d6cc1113ea471c01715ee28894c44,
+            ),
+            (
+                16,
+                0xf098c2b253c2534709c35865c2370525,
+                0x9b9124b7848bcff4164a2fc36947b14d,
+            ),
+            (
+                17,
+                0xda6282623db381b0a3df137f9a226243,
+                0x21cd6c0117ab4df043ec75d8f0e00f1b,
+            ),
+            (
+                128,
+                0x9f2ecb24cec76d4b7bf31ac6a8c6de53,
+                0xbcd0ffa29d9c108748011c1d1c815c89,
+            ),
+            (
+                129,
+                0xaf23b69490e88d89af9e983c7f561603,
+                0x07cbdfde0450a49a93432c2bccac5e27,
+            ),
+            (
+                240,
+                0x96b7a97add7da482f2697857a727e4d2,
+                0x1591ee4c3280998e7381f6c491d2f90d,
+            ),
+            (
+                241,
+                0xcf25ca5a0aa9e5d2de35f6a6f512d387,
+                0xac9f92ad6f83214e5889ee05396f864f,
+            ),
+            (
+                1024,
+                0xc45f035bcb1b9e41140f19c9cb1c367e,
+                0xce71c95665723b1d737c18dea1a1bdd9,
+            ),
+            (
+                2999,
+                0x52a5dda50f7dcd56b8a5833a605273ee,
+                0x0abcb0f4314115ca9dbb3d429718ed93,
+            ),
+        ];
+        for &(len, seeded, secreted) in expected.iter() {
+            let data = &input[..len];
+            assert_eq!(crate::xxhash::xxh3_128_with_seed(data, 4919), seeded);
+            assert_eq!(crate::xxhash::xxh3_128_with_secret(data, &secret), secreted);
+        }
+    }
+
+    #[test]
+    fn streaming_test() {
+        let input = input();
+        let secret = secret();
+        for len in (0..input.len()).step_by(7) {
+            let data = &input[..len];
+            for split in [1, 63, 64, 257, 1000] {
+                let mut seeded = crate::xxhash::Xxh3Hasher128::with_seed(4919);
+                let mut secreted = crate::xxhash::Xxh3Hasher128::with_secret(secret.clone());
+                for chunk in data.chunks(split) {
+                    seeded.write(chunk);
+                    secreted.write(chunk);
+                }
+                assert_eq!(
+                    seeded.finish128(),
+                    crate::xxhash::xxh3_128_with_seed(data, 4919)
+                );
+                assert_eq!(
+                    secreted.finish128(),
+                    crate::xxhash::xxh3_128_with_secret(data, &secret)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn builder_test() {
+        use std::hash::BuildHasher;
+
+        let builder = crate::xxhash::Xxh3Hash128::with_secret(secret());
+        let mut hasher = builder.build_hasher();
+        hasher.write(b"abc");
+        assert_eq!(
+            hasher.finish128(),
+            crate::xxhash::xxh3_128_with_secret("abc", &secret())
+        );
+    }
+}
@@ -0,0 +1,361 @@
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Arc;
+
+use super::xxh3::{
+    avalanche, check_secret, custom_secret, hash_long, merge_64, mix16, mul128_fold64, read32,
+    read64, xxh64_avalanche, Secret, State, DEFAULT_SECRET, MIDSIZE_LASTOFFSET, MIDSIZE_MAX,
+    MIDSIZE_STARTOFFSET, PRIME64_1, PRIME_MX2, XXH3_SECRET_SIZE_MIN,
+};
+use crate::HashAlgorithm;
+
+/// Hasher for xxHash implementation of the 64-bit XXH3 hashing algorithm.
+#[derive(Clone)]
+pub struct Xxh3Hasher64 {
+    state: State,
+}
+
+impl Xxh3Hasher64 {
+    /// Create a hasher which uses the given seed.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            state: State::new(Secret::with_seed(seed)),
+        }
+    }
+
+    /// Create a hasher which uses the given secret instead of the default one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the secret is shorter than 136 bytes.
+    pub fn with_secret<S: Into<Arc<[u8]>>>(secret: S) -> Self {
+        Self {
+            state: State::new(Secret::custom(secret.into())),
+        }
+    }
+}
+
+impl Default for Xxh3Hasher64 {
+    fn default() -> Self {
+        Self::with_seed(0)
+    }
+}
+
+impl Hasher for Xxh3Hasher64 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.state.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let secret = &self.state.secret;
+        if self.state.length() > MIDSIZE_MAX as u64 {
+            merge_64(&self.state.long_acc(), secret.long(), self.state.length())
+        } else {
+            hash_short(self.state.short_input(), secret.short(), secret.seed())
+        }
+    }
+}
+
+/// Hash builder for xxHash implementation of the 64-bit XXH3 hashing algorithm.
+#[derive(Clone, Default)]
+pub struct Xxh3Hash64 {
+    seed: u64,
+    secret: Option<Arc<[u8]>>,
+}
+
+impl Xxh3Hash64 {
+    /// Create a hash builder whose hashers use the given seed.
+    pub fn with_seed(seed: u64) -> Self {
+        Self { seed, secret: None }
+    }
+
+    /// Create a hash builder whose hashers use the given secret instead of the default one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the secret is shorter than 136 bytes.
+    pub fn with_secret<S: Into<Arc<[u8]>>>(secret: S) -> Self {
+        let secret = secret.into();
+        check_secret(&secret);
+        Self {
+            seed: 0,
+            secret: Some(secret),
+        }
+    }
+}
+
+impl BuildHasher for Xxh3Hash64 {
+    type Hasher = Xxh3Hasher64;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        match &self.secret {
+            Some(secret) => Self::Hasher::with_secret(secret.clone()),
+            None => Self::Hasher::with_seed(self.seed),
+        }
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for xxHash implementation of the 64-bit XXH3 hashing algorithm.
+    #[derive(Clone)]
+    pub struct Xxh3RandomState64(Xxh3Hash64::with_seed(seed: u64));
+}
+
+// The code below is adapted from C code with the following license
+//-----------------------------------------------------------------------------
+// xxHash - Extremely Fast Hash algorithm
+// Copyright (C) 2012-2020 Yann Collet
+//
+// BSD 2-Clause License (https://www.opensource.org/licenses/bsd-license.php)
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are
+// met:
+//
+//    * Redistributions of source code must retain the above copyright
+//      notice, this list of conditions and the following disclaimer.
+//    * Redistributions in binary form must reproduce the above
+//      copyright notice, this list of conditions and the following disclaimer
+//      in the documentation and/or other materials provided with the
+//      distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+// OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+// LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+// DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+// THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+// (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+/// xxHash implementation of the 64-bit XXH3 hashing algorithm.
+pub struct Xxh3_64;
+
+impl HashAlgorithm for Xxh3_64 {
+    const NAME: &'static str = "xxh3_64";
+    type Seed = u64;
+    type Output = u64;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        xxh3_64(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        xxh3_64_with_seed(v, seed)
+    }
+}
+
+/// xxHash implementation of the 64-bit XXH3 hashing algorithm.
+/// This version allows you to specify a seed.
+pub fn xxh3_64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
+    let data = v.as_ref();
+    if data.len() <= MIDSIZE_MAX {
+        hash_short(data, &DEFAULT_SECRET, seed)
+    } else if seed == 0 {
+        merge_64(
+            &hash_long(data, &DEFAULT_SECRET),
+            &DEFAULT_SECRET,
+            data.len() as u64,
+        )
+    } else {
+        let secret = custom_secret(seed);
+        merge_64(&hash_long(data, &secret), &secret, data.len() as u64)
+    }
+}
+
+/// xxHash implementation of the 64-bit XXH3 hashing algorithm.
+/// This version uses the given secret instead of the default one.
+///
+/// # Panics
+///
+/// Panics if the secret is shorter than 136 bytes.
+pub fn xxh3_64_with_secret<T: AsRef<[u8]>>(v: T, secret: &[u8]) -> u64 {
+    check_secret(secret);
+    let data = v.as_ref();
+    if data.len() <= MIDSIZE_MAX {
+        hash_short(data, secret, 0)
+    } else {
+        merge_64(&hash_long(data, secret), secret, data.len() as u64)
+    }
+}
+
+/// xxHash implementation of the 64-bit XXH3 hashing algorithm.
+/// The seed is always 0 in this version.
+pub fn xxh3_64<T: AsRef<[u8]>>(v: T) -> u64 {
+    xxh3_64_with_seed(v, 0)
+}
+
+// hash an input of up to 240 bytes
+fn hash_short(data: &[u8], secret: &[u8], seed: u64) -> u64 {
+    match data.len() {
+        0..=16 => hash_len_0_to_16(data, secret, seed),
+        17..=128 => hash_len_17_to_128(data, secret, seed),
+        _ => hash_len_129_to_240(data, secret, seed),
+    }
+}
+
+#[inline(always)]
+fn hash_len_0_to_16(data: &[u8], secret: &[u8], seed: u64) -> u64 {
+    let len = data.len();
+    if len > 8 {
+        let bitflip1 = (read64(secret, 24) ^ read64(secret, 32)).wrapping_add(seed);
+        let bitflip2 = (read64(secret, 40) ^ read64(secret, 48)).wrapping_sub(seed);
+        let input_lo = read64(data, 0) ^ bitflip1;
+        let input_hi = read64(data, len - 8) ^ bitflip2;
+        let acc = (len as u64)
+            .wrapping_add(input_lo.swap_bytes())
+            .wrapping_add(input_hi)
+            .wrapping_add(mul128_fold64(input_lo, input_hi));
+        avalanche(acc)
+    } else if len >= 4 {
+        let seed = seed ^ ((seed as u32).swap_bytes() as u64) << 32;
+        let input1 = read32(data, 0) as u64;
+        let input2 = read32(data, len - 4) as u64;
+        let bitflip = (read64(secret, 8) ^ read64(secret, 16)).wrapping_sub(seed);
+        let keyed = input2.wrapping_add(input1 << 32) ^ bitflip;
+        rrmxmx(keyed, len as u64)
+    } else if len > 0 {
+        let combined = (data[0] as u32) << 16
+            | (data[len >> 1] as u32) << 24
+            | (data[len - 1] as u32)
+            | (len as u32) << 8;
+        let bitflip = ((read32(secret, 0) ^ read32(secret, 4)) as u64).wrapping_add(seed);
+        xxh64_avalanche(combined as u64 ^ bitflip)
+    } else {
+        xxh64_avalanche(seed ^ read64(secret, 56) ^ read64(secret, 64))
+    }
+}
+
+#[inline(always)]
+fn hash_len_17_to_128(data: &[u8], secret: &[u8], seed: u64) -> u64 {
+    let len = data.len();
+    let mut acc = (len as u64).wrapping_mul(PRIME64_1);
+    if len > 32 {
+        if len > 64 {
+            if len > 96 {
+                acc = acc.wrapping_add(mix16(data, 48, secret, 96, seed));
+                acc = acc.wrapping_add(mix16(data, len - 64, secret, 112, seed));
+            }
+            acc = acc.wrapping_add(mix16(data, 32, secret, 64, seed));
+            acc = acc.wrapping_add(mix16(data, len - 48, secret, 80, seed));
+        }
+        acc = acc.wrapping_add(mix16(data, 16, secret, 32, seed));
+        acc = acc.wrapping_add(mix16(data, len - 32, secret, 48, seed));
+    }
+    acc = acc.wrapping_add(mix16(data, 0, secret, 0, seed));
+    acc = acc.wrapping_add(mix16(data, len - 16, secret, 16, seed));
+    avalanche(acc)
+}
+
+#[inline(always)]
+fn hash_len_129_to_240(data: &[u8], secret: &[u8], seed: u64) -> u64 {
+    let len = data.len();
+    let n_rounds = len / 16;
+    let mut acc = (len as u64).wrapping_mul(PRIME64_1);
+    for i in 0..8 {
+        acc = acc.wrapping_add(mix16(data, 16 * i, secret, 16 * i, seed));
+    }
+    acc = avalanche(acc);
+    for i in 8..n_rounds {
+        let s = 16 * (i - 8) + MIDSIZE_STARTOFFSET;
+        acc = acc.wrapping_add(mix16(data, 16 * i, secret, s, seed));
+    }
+    let s = XXH3_SECRET_SIZE_MIN - MIDSIZE_LASTOFFSET;
+    acc = acc.wrapping_add(mix16(data, len - 16, secret, s, seed));
+    avalanche(acc)
+}
+
+#[inline(always)]
+fn rrmxmx(mut h64: u64, len: u64) -> u64 {
+    h64 ^= h64.rotate_left(49) ^ h64.rotate_left(24);
+    h64 = h64.wrapping_mul(PRIME_MX2);
+    h64 ^= (h64 >> 35).wrapping_add(len);
+    h64 = h64.wrapping_mul(PRIME_MX2);
+    h64 ^ (h64 >> 28)
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    fn input() -> Vec<u8> {
+        (0..3000u32)
+            .map(|i| (i.wrapping_mul(2654435761) >> 13) as u8)
+            .collect()
+    }
+
+    fn secret() -> Vec<u8> {
+        (0..200u32)
+            .map(|i| (i.wrapping_mul(0x9e3779b1) >> 7) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn compliance_test() {
+        assert_eq!(crate::xxhash::xxh3_64(""), 0x2d06800538d394c2);
+        assert_eq!(crate::xxhash::xxh3_64("a"), 0xe6c632b61e964e1f);
+        assert_eq!(crate::xxhash::xxh3_64("abc"), 0x78af5f94892f3950);
+        assert_eq!(
+            crate::xxhash::xxh3_64("Nobody inspects the spammish repetition"),
+            0x6cb00603b5cc47e9
+        );
+    }
+
+    #[test]
+    fn length_classes_test() {
+        let input = input();
+        let secret = secret();
+        // (length, seeded with 4919, with custom secret)
+        let expected = [
+            (0, 0x034dd73f4a670965, 0xaf72e667ca4c9b69),
+            (3, 0x442bb292f79092b5, 0x2637d43fb2365673),
+            (8, 0x20df8b20318b7c00, 0x952a92f4bb45d3b5),
+            (16, 0x606c0539fea13783, 0xdc66789d7312a14c),
+            (17, 0x00a3a33922276f73, 0xe9a39f31380eacbf),
+            (128, 0x00be969e48baee0c, 0x2dfb172825e779f2),
+            (129, 0xbc881d8ea8f2bf6e, 0x86448b5693b8f0a4),
+            (240, 0x5376e368065c1cd0, 0x9b16841c462cdc5c),
+            (241, 0xde35f6a6f512d387, 0x5889ee05396f864f),
+            (1024, 0x140f19c9cb1c367e, 0x737c18dea1a1bdd9),
+            (2999, 0xb8a5833a605273ee, 0x9dbb3d429718ed93),
+        ];
+        for &(len, seeded, secreted) in expected.iter() {
+            let data = &input[..len];
+            assert_eq!(crate::xxhash::xxh3_64_with_seed(data, 4919), seeded);
+            assert_eq!(crate::xxhash::xxh3_64_with_secret(data, &secret), secreted);
+        }
+    }
+
+    #[test]
+    fn streaming_test() {
+        let input = input();
+        let secret = secret();
+        for len in (0..input.len()).step_by(7) {
+            let data = &input[..len];
+            for split in [1, 63, 64, 257, 1000] {
+                let mut seeded = crate::xxhash::Xxh3Hasher64::with_seed(4919);
+                let mut secreted = crate::xxhash::Xxh3Hasher64::with_secret(secret.clone());
+                for chunk in data.chunks(split) {
+                    seeded.write(chunk);
+                    secreted.write(chunk);
+                }
+                assert_eq!(
+                    seeded.finish(),
+                    crate::xxhash::xxh3_64_with_seed(data, 4919)
+                );
+                assert_eq!(
+                    secreted.finish(),
+                    crate::xxhash::xxh3_64_with_secret(data, &secret)
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn short_secret_test() {
+        crate::xxhash::xxh3_64_with_secret("abc", &[0; 135]);
+    }
+}
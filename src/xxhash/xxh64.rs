@@ -0,0 +1,315 @@
+use std::hash::{BuildHasher, Hasher};
+
+use crate::HashAlgorithm;
+
+/// Hasher for xxHash implementation of the 64-bit hashing algorithm.
+pub struct XxHasher64 {
+    seed: u64,
+    v: [u64; 4],
+    tail: [u8; 32],
+    tail_len: usize,
+    length: u64,
+}
+
+impl XxHasher64 {
+    /// Create a hasher which uses the given seed.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            seed,
+            v: init(seed),
+            tail: [0; 32],
+            tail_len: 0,
+            length: 0,
+        }
+    }
+}
+
+impl Default for XxHasher64 {
+    fn default() -> Self {
+        Self::with_seed(0)
+    }
+}
+
+impl Hasher for XxHasher64 {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut data = bytes;
+        self.length += data.len() as u64;
+        // complete a previously started stripe
+        if self.tail_len != 0 {
+            let fill = (32 - self.tail_len).min(data.len());
+            self.tail[self.tail_len..self.tail_len + fill].copy_from_slice(&data[..fill]);
+            self.tail_len += fill;
+            data = &data[fill..];
+            if self.tail_len < 32 {
+                return;
+            }
+            stripe(&mut self.v, &self.tail, 0);
+            self.tail_len = 0;
+        }
+        // process full stripes directly from the input
+        let n_stripes = data.len() / 32;
+        for i in 0..n_stripes {
+            stripe(&mut self.v, data, i * 32);
+        }
+        // keep the remainder for the next write
+        let rest = &data[n_stripes * 32..];
+        self.tail[..rest.len()].copy_from_slice(rest);
+        self.tail_len = rest.len();
+    }
+
+    fn finish(&self) -> u64 {
+        let h64 = if self.length >= 32 {
+            converge(&self.v)
+        } else {
+            self.seed.wrapping_add(PRIME64_5)
+        };
+        finalize(h64, &self.tail[..self.tail_len], self.length)
+    }
+}
+
+/// Hash builder for xxHash implementation of the 64-bit hashing algorithm.
+#[derive(Clone, Copy, Default)]
+pub struct XxHash64 {
+    seed: u64,
+}
+
+impl XxHash64 {
+    /// Create a hash builder whose hashers use the given seed.
+    pub fn with_seed(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl BuildHasher for XxHash64 {
+    type Hasher = XxHasher64;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher::with_seed(self.seed)
+    }
+}
+
+crate::random::random_state! {
+    /// Randomly seeded hash builder for xxHash implementation of the 64-bit hashing algorithm.
+    #[derive(Clone, Copy)]
+    pub struct XxRandomState64(XxHash64::with_seed(seed: u64));
+}
+
+// The code below is adapted from C code with the following license
+//-----------------------------------------------------------------------------
+// xxHash - Extremely Fast Hash algorithm
+// Copyright (C) 2012-2020 Yann Collet
+//
+// BSD 2-Clause License (https://www.opensource.org/licenses/bsd-license.php)
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are
+// met:
+//
+//    * Redistributions of source code must retain the above copyright
+//      notice, this list of conditions and the following disclaimer.
+//    * Redistributions in binary form must reproduce the above
+//      copyright notice, this list of conditions and the following disclaimer
+//      in the documentation and/or other materials provided with the
+//      distribution.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT
+// OWNER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT
+// LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+// DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+// THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+// (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+const PRIME64_1: u64 = 0x9e3779b185ebca87;
+const PRIME64_2: u64 = 0xc2b2ae3d27d4eb4f;
+const PRIME64_3: u64 = 0x165667b19e3779f9;
+const PRIME64_4: u64 = 0x85ebca77c2b2ae63;
+const PRIME64_5: u64 = 0x27d4eb2f165667c5;
+
+/// xxHash implementation of the 64-bit hashing algorithm.
+pub struct Xxh64;
+
+impl HashAlgorithm for Xxh64 {
+    const NAME: &'static str = "xxh64";
+    type Seed = u64;
+    type Output = u64;
+
+    fn hash<T: AsRef<[u8]>>(v: T) -> Self::Output {
+        hash64(v)
+    }
+
+    fn hash_with_seed<T: AsRef<[u8]>>(v: T, seed: Self::Seed) -> Self::Output {
+        hash64_with_seed(v, seed)
+    }
+}
+
+/// xxHash implementation of the 64-bit hashing algorithm.
+/// This version allows you to specify a seed.
+pub fn hash64_with_seed<T: AsRef<[u8]>>(v: T, seed: u64) -> u64 {
+    let data = v.as_ref();
+    let n_stripes = data.len() / 32;
+
+    let h64 = if n_stripes != 0 {
+        let mut v = init(seed);
+        for i in 0..n_stripes {
+            stripe(&mut v, data, i * 32);
+        }
+        converge(&v)
+    } else {
+        seed.wrapping_add(PRIME64_5)
+    };
+
+    finalize(h64, &data[n_stripes * 32..], data.len() as u64)
+}
+
+/// xxHash implementation of the 64-bit hashing algorithm.
+/// The seed is always 0 in this version.
+pub fn hash64<T: AsRef<[u8]>>(v: T) -> u64 {
+    hash64_with_seed(v, 0)
+}
+
+#[inline(always)]
+fn init(seed: u64) -> [u64; 4] {
+    [
+        seed.wrapping_add(PRIME64_1).wrapping_add(PRIME64_2),
+        seed.wrapping_add(PRIME64_2),
+        seed,
+        seed.wrapping_sub(PRIME64_1),
+    ]
+}
+
+#[inline(always)]
+fn round(acc: u64, input: u64) -> u64 {
+    acc.wrapping_add(input.wrapping_mul(PRIME64_2))
+        .rotate_left(31)
+        .wrapping_mul(PRIME64_1)
+}
+
+#[inline(always)]
+fn merge_round(acc: u64, val: u64) -> u64 {
+    (acc ^ round(0, val))
+        .wrapping_mul(PRIME64_1)
+        .wrapping_add(PRIME64_4)
+}
+
+#[inline(always)]
+fn stripe(v: &mut [u64; 4], data: &[u8], i: usize) {
+    v[0] = round(v[0], get_u64(data, i));
+    v[1] = round(v[1], get_u64(data, i + 8));
+    v[2] = round(v[2], get_u64(data, i + 16));
+    v[3] = round(v[3], get_u64(data, i + 24));
+}
+
+#[inline(always)]
+fn converge(v: &[u64; 4]) -> u64 {
+    let mut h64 = v[0]
+        .rotate_left(1)
+        .wrapping_add(v[1].rotate_left(7))
+        .wrapping_add(v[2].rotate_left(12))
+        .wrapping_add(v[3].rotate_left(18));
+    h64 = merge_round(h64, v[0]);
+    h64 = merge_round(h64, v[1]);
+    h64 = merge_round(h64, v[2]);
+    merge_round(h64, v[3])
+}
+
+#[inline(always)]
+fn finalize(mut h64: u64, tail: &[u8], length: u64) -> u64 {
+    h64 = h64.wrapping_add(length);
+
+    let mut chunks = tail.chunks_exact(8);
+    for chunk in &mut chunks {
+        h64 ^= round(0, get_u64(chunk, 0));
+        h64 = h64
+            .rotate_left(27)
+            .wrapping_mul(PRIME64_1)
+            .wrapping_add(PRIME64_4);
+    }
+    let mut rest = chunks.remainder();
+    if rest.len() >= 4 {
+        h64 ^= (get_u32(rest, 0) as u64).wrapping_mul(PRIME64_1);
+        h64 = h64
+            .rotate_left(23)
+            .wrapping_mul(PRIME64_2)
+            .wrapping_add(PRIME64_3);
+        rest = &rest[4..];
+    }
+    for &byte in rest {
+        h64 ^= (byte as u64).wrapping_mul(PRIME64_5);
+        h64 = h64.rotate_left(11).wrapping_mul(PRIME64_1);
+    }
+
+    // avalanche
+    h64 ^= h64 >> 33;
+    h64 = h64.wrapping_mul(PRIME64_2);
+    h64 ^= h64 >> 29;
+    h64 = h64.wrapping_mul(PRIME64_3);
+    h64 ^= h64 >> 32;
+    h64
+}
+
+#[inline(always)]
+fn get_u64(data: &[u8], i: usize) -> u64 {
+    let buf = [
+        data[i],
+        data[i + 1],
+        data[i + 2],
+        data[i + 3],
+        data[i + 4],
+        data[i + 5],
+        data[i + 6],
+        data[i + 7],
+    ];
+    u64::from_le_bytes(buf)
+}
+
+#[inline(always)]
+fn get_u32(data: &[u8], i: usize) -> u32 {
+    let buf = [data[i], data[i + 1], data[i + 2], data[i + 3]];
+    u32::from_le_bytes(buf)
+}
+
+#[cfg(test)]
+mod test {
+    use std::hash::Hasher;
+
+    #[test]
+    fn compliance_test() {
+        assert_eq!(crate::xxhash::xxh64::hash64(""), 0xef46db3751d8e999);
+        assert_eq!(crate::xxhash::xxh64::hash64("abc"), 0x44bc2cf5ad770999);
+        assert_eq!(
+            crate::xxhash::xxh64::hash64("Nobody inspects the spammish repetition"),
+            0xfbcea83c8a378bf1
+        );
+    }
+
+    #[test]
+    fn streaming_test() {
+        let input: Vec<u8> = (0..=255).collect();
+        for len in 0..input.len() {
+            let expected = crate::xxhash::xxh64::hash64_with_seed(&input[..len], 4919);
+            for split in [1, 3, 32, 65] {
+                let mut hasher = crate::xxhash::XxHasher64::with_seed(4919);
+                for chunk in input[..len].chunks(split) {
+                    hasher.write(chunk);
+                }
+                assert_eq!(hasher.finish(), expected);
+            }
+        }
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn fasthash_interop_test() {
+        let input = "This is a very long test string to make sure this project produces the same results as fasthash";
+        let seed = 4919;
+        assert_eq!(
+            crate::xxhash::xxh64::hash64_with_seed(input, seed),
+            fasthash::xx::hash64_with_seed(input, seed)
+        );
+    }
+}